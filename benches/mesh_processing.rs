@@ -0,0 +1,53 @@
+//!
+//! Measures the time it takes to compute normals, tangents and the bounding box of a large mesh.
+//! Run with and without the `rayon` feature to compare:
+//! ```sh
+//! cargo bench --bench mesh_processing
+//! cargo bench --bench mesh_processing --features rayon
+//! ```
+//!
+
+use std::time::{Duration, Instant};
+use three_d_asset::*;
+
+fn measure(name: &str, iterations: u32, mut f: impl FnMut()) {
+    f();
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    let elapsed: Duration = start.elapsed() / iterations;
+    println!("{:<20} {:>10.2?}", name, elapsed);
+}
+
+fn main() {
+    let mut mesh = TriMesh::sphere(128);
+    for i in 0..3 {
+        let mut copy = TriMesh::sphere(128);
+        copy.transform(Mat4::from_translation(vec3(3.0 * (i + 1) as f32, 0.0, 0.0)))
+            .unwrap();
+        let offset = mesh.vertex_count() as u32;
+        let mut indices = mesh.indices.into_u32().unwrap();
+        indices.extend(copy.indices.into_u32().unwrap().iter().map(|i| i + offset));
+        mesh.indices = Indices::U32(indices);
+        let mut positions = mesh.positions.into_f32();
+        positions.extend(copy.positions.into_f32());
+        mesh.positions = Positions::F32(positions);
+        mesh.normals.as_mut().unwrap().extend(copy.normals.unwrap());
+    }
+    mesh.generate_spherical_uvs();
+    println!(
+        "{} vertices and {} triangles, rayon: {}",
+        mesh.vertex_count(),
+        mesh.triangle_count(),
+        cfg!(feature = "rayon")
+    );
+
+    measure("compute_normals", 20, || mesh.compute_normals());
+    measure("compute_tangents", 20, || mesh.compute_tangents().unwrap());
+
+    let positions = Positions::F64(mesh.positions.to_f64());
+    measure("compute_aabb_f64", 20, || {
+        std::hint::black_box(positions.compute_aabb());
+    });
+}
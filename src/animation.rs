@@ -1,4 +1,20 @@
-use crate::{prelude::*, Interpolation};
+use crate::prelude::*;
+
+///
+/// Possible modes of interpolation which determines the value of an animation in between the key frames.
+///
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnimationInterpolation {
+    /// The value of the previous key frame is used until the next key frame.
+    Step,
+    /// The values of the two closest key frames are linearly interpolated, or spherically linearly interpolated for rotations.
+    #[default]
+    Linear,
+    /// The values are interpolated using a cubic spline.
+    /// Each key frame then consists of three values; an in-tangent, a value and an out-tangent.
+    CubicSpline,
+}
 
 /// A  set of key frames and transformations associated with a specific animation for a specific [Primitive](crate::Primitive).
 #[derive(Debug, Clone, Default)]
@@ -9,6 +25,7 @@ pub struct KeyFrameAnimation {
     /// Can be used to distinguish between different types of animations for the same [Primitive](crate::Primitive), for example between a "walk" and a "run" animation.
     pub name: Option<String>,
     /// A list of [KeyFrames] and transformations which are the result of traversing a [Scene](crate::Scene) from root to the leaves.
+    /// The key frames are shared between the primitives affected by the same node, but when serialized with serde, they are stored by value for each primitive.
     pub key_frames: Vec<(Mat4, std::sync::Arc<KeyFrames>)>,
 }
 
@@ -32,7 +49,7 @@ pub struct KeyFrames {
     /// Optional time where the animation repeats itself.
     pub loop_time: Option<f32>,
     /// The type of interpolation used in between the key frames.
-    pub interpolation: Interpolation,
+    pub interpolation: AnimationInterpolation,
     /// The time value for each key frame.
     pub times: Vec<f32>,
     /// The rotation for each key frame.
@@ -88,6 +105,52 @@ impl KeyFrames {
             .map(|values| self.interpolate_array(time, values))
     }
 
+    ///
+    /// Returns a copy of these key frames that plays backwards.
+    /// The key frame times are mirrored such that the first and last key frame times are the same as before,
+    /// which means that the [KeyFrames::loop_time] is still valid.
+    ///
+    pub fn reversed(&self) -> KeyFrames {
+        let (start, end) = match (self.times.first(), self.times.last()) {
+            (Some(start), Some(end)) => (*start, *end),
+            _ => return self.clone(),
+        };
+        let cubic = self.interpolation == AnimationInterpolation::CubicSpline;
+        KeyFrames {
+            loop_time: self.loop_time,
+            interpolation: self.interpolation,
+            times: self.times.iter().rev().map(|t| start + end - t).collect(),
+            rotations: self.rotations.as_ref().map(|v| reverse(v, cubic)),
+            translations: self.translations.as_ref().map(|v| reverse(v, cubic)),
+            scales: self.scales.as_ref().map(|v| reverse(v, cubic)),
+            weights: self.weights.as_ref().map(|values| {
+                values
+                    .iter()
+                    .rev()
+                    .enumerate()
+                    .map(|(i, w)| {
+                        if cubic && i % 3 != 1 {
+                            w.iter().map(|w| -w).collect()
+                        } else {
+                            w.clone()
+                        }
+                    })
+                    .collect()
+            }),
+        }
+    }
+
+    ///
+    /// Adds the given time to the time of all key frames, which for example can be used to delay the start of the animation.
+    /// If the animation loops, the [KeyFrames::loop_time] is increased by the same amount so the loop contains the entire animation.
+    ///
+    pub fn offset_time(&mut self, dt: f32) {
+        self.times.iter_mut().for_each(|t| *t += dt);
+        if let Some(loop_time) = self.loop_time.as_mut() {
+            *loop_time += dt;
+        }
+    }
+
     fn interpolate_rotation(&self, time: f32, values: &[Quat]) -> Quat {
         let time = self.loop_time.map(|t| time % t).unwrap_or(time);
         if time < self.times[0] {
@@ -141,3 +204,85 @@ impl KeyFrames {
         }
     }
 }
+
+///
+/// Reverses the values of a key frame sequence.
+/// With cubic spline interpolation, each key frame consists of an in-tangent, a value and an out-tangent,
+/// so after reversing the order, the tangents are swapped and therefore also negated.
+///
+fn reverse<T: Copy + std::ops::Neg<Output = T>>(values: &[T], cubic: bool) -> Vec<T> {
+    values
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, v)| if cubic && i % 3 != 1 { -*v } else { *v })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn key_frames() -> KeyFrames {
+        KeyFrames {
+            loop_time: Some(5.0),
+            times: vec![1.0, 2.0, 4.0],
+            translations: Some(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(1.0, 0.0, 0.0),
+                vec3(1.0, 3.0, 0.0),
+            ]),
+            weights: Some(vec![vec![0.0], vec![0.5], vec![1.0]]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    pub fn reversed() {
+        let key_frames = key_frames();
+        let reversed = key_frames.reversed();
+        assert_eq!(reversed.times, vec![1.0, 3.0, 4.0]);
+        assert_eq!(reversed.loop_time, Some(5.0));
+        for time in [0.5, 1.0, 1.5, 2.5, 3.0, 3.7, 4.0, 4.5] {
+            assert_eq!(
+                reversed.translation(time),
+                key_frames.translation(5.0 - time)
+            );
+        }
+        assert_eq!(reversed.weights(1.0), Some(vec![1.0]));
+        assert_eq!(reversed.weights(3.0), Some(vec![0.5]));
+    }
+
+    #[test]
+    pub fn offset_time() {
+        let key_frames = key_frames();
+        let mut delayed = key_frames.clone();
+        delayed.offset_time(2.0);
+        assert_eq!(delayed.times, vec![3.0, 4.0, 6.0]);
+        assert_eq!(delayed.loop_time, Some(7.0));
+        for time in [0.5, 1.5, 2.5, 3.0, 4.5] {
+            assert_eq!(
+                delayed.translation(time + 2.0),
+                key_frames.translation(time)
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn serde() {
+        let animation = KeyFrameAnimation {
+            name: Some("move".to_owned()),
+            key_frames: vec![(Mat4::from_scale(2.0), std::sync::Arc::new(key_frames()))],
+        };
+        let json = serde_json::to_string(&animation).unwrap();
+        let deserialized: KeyFrameAnimation = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.name, animation.name);
+        for time in [0.0, 1.5, 3.0, 4.5] {
+            assert_eq!(
+                deserialized.transformation(time),
+                animation.transformation(time)
+            );
+        }
+    }
+}
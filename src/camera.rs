@@ -141,28 +141,118 @@ impl Viewport {
             height,
         }
     }
+
+    ///
+    /// Splits this viewport in the horizontal direction into a left and a right viewport placed side by side.
+    /// The left viewport gets the given ratio (between `0.0` and `1.0`) of the width, rounded to the nearest pixel
+    /// (with halfway cases rounded up), and the right viewport gets the remaining pixels.
+    ///
+    pub fn split_horizontal(&self, ratio: f32) -> (Self, Self) {
+        let left_width = ((self.width as f32 * ratio).round() as u32).min(self.width);
+        (
+            Self {
+                width: left_width,
+                ..*self
+            },
+            Self {
+                x: self.x + left_width as i32,
+                width: self.width - left_width,
+                ..*self
+            },
+        )
+    }
+
+    ///
+    /// Splits this viewport in the vertical direction into a bottom and a top viewport placed on top of each other.
+    /// The bottom viewport gets the given ratio (between `0.0` and `1.0`) of the height, rounded to the nearest pixel
+    /// (with halfway cases rounded up), and the top viewport gets the remaining pixels.
+    ///
+    pub fn split_vertical(&self, ratio: f32) -> (Self, Self) {
+        let bottom_height = ((self.height as f32 * ratio).round() as u32).min(self.height);
+        (
+            Self {
+                height: bottom_height,
+                ..*self
+            },
+            Self {
+                y: self.y + bottom_height as i32,
+                height: self.height - bottom_height,
+                ..*self
+            },
+        )
+    }
+
+    ///
+    /// Returns whether or not the given pixel coordinate is inside this viewport.
+    ///
+    pub fn contains(&self, pixel: impl Into<PixelPoint>) -> bool {
+        let pixel = pixel.into();
+        pixel.x >= self.x as f32
+            && pixel.x < (self.x + self.width as i32) as f32
+            && pixel.y >= self.y as f32
+            && pixel.y < (self.y + self.height as i32) as f32
+    }
 }
 
 ///
 /// The view frustum which can be used for frustum culling.
 ///
-pub struct Frustum([Vec4; 6]);
+pub struct Frustum {
+    planes: [Vec4; 6],
+    /// The corners in homogeneous coordinates, where the corners of an infinite far plane have a `w` value of zero.
+    corners: Option<[Vec4; 8]>,
+}
 
 impl Frustum {
+    ///
     /// Computes the frustum for the given view-projection matrix.
+    /// The projection can have the far plane at infinity, in which case the far plane is degenerate and therefore not used for culling.
+    ///
     pub fn new(view_projection: Mat4) -> Self {
         let m = view_projection;
-        Self([
+        let planes = [
             vec4(m.x.w + m.x.x, m.y.w + m.y.x, m.z.w + m.z.x, m.w.w + m.w.x),
             vec4(m.x.w - m.x.x, m.y.w - m.y.x, m.z.w - m.z.x, m.w.w - m.w.x),
             vec4(m.x.w + m.x.y, m.y.w + m.y.y, m.z.w + m.z.y, m.w.w + m.w.y),
             vec4(m.x.w - m.x.y, m.y.w - m.y.y, m.z.w - m.z.y, m.w.w - m.w.y),
             vec4(m.x.w + m.x.z, m.y.w + m.y.z, m.z.w + m.z.z, m.w.w + m.w.z),
             vec4(m.x.w - m.x.z, m.y.w - m.y.z, m.z.w - m.z.z, m.w.w - m.w.z),
-        ])
+        ]
+        .map(|plane| {
+            // A plane without a normal, for example the far plane of an infinite projection, never culls anything
+            if plane.truncate().magnitude() <= f32::EPSILON * plane.w.abs() {
+                vec4(0.0, 0.0, 0.0, 1.0)
+            } else {
+                plane
+            }
+        });
+        let corners = view_projection.invert().map(|inverse| {
+            [
+                vec4(-1.0, -1.0, -1.0, 1.0),
+                vec4(1.0, -1.0, -1.0, 1.0),
+                vec4(-1.0, 1.0, -1.0, 1.0),
+                vec4(1.0, 1.0, -1.0, 1.0),
+                vec4(-1.0, -1.0, 1.0, 1.0),
+                vec4(1.0, -1.0, 1.0, 1.0),
+                vec4(-1.0, 1.0, 1.0, 1.0),
+                vec4(1.0, 1.0, 1.0, 1.0),
+            ]
+            .map(|corner| inverse * corner)
+        });
+        Self { planes, corners }
+    }
+
+    ///
+    /// Computes the frustum for the given view and projection matrices, see [Frustum::new].
+    ///
+    pub fn from_view_projection_parts(view: Mat4, projection: Mat4) -> Self {
+        Self::new(projection * view)
     }
 
+    ///
     /// Used for frustum culling. Returns false if the entire bounding box is outside of the frustum.
+    /// The test is conservative, so it might return true for a few bounding boxes that are just outside the frustum, but never false for a bounding box that intersects the frustum.
+    ///
     pub fn contains(&self, aabb: AxisAlignedBoundingBox) -> bool {
         if aabb.is_infinite() {
             return true;
@@ -170,47 +260,112 @@ impl Frustum {
         if aabb.is_empty() {
             return false;
         }
+        let (min, max) = (aabb.min(), aabb.max());
+        let box_corners = [
+            vec4(min.x, min.y, min.z, 1.0),
+            vec4(max.x, min.y, min.z, 1.0),
+            vec4(min.x, max.y, min.z, 1.0),
+            vec4(max.x, max.y, min.z, 1.0),
+            vec4(min.x, min.y, max.z, 1.0),
+            vec4(max.x, min.y, max.z, 1.0),
+            vec4(min.x, max.y, max.z, 1.0),
+            vec4(max.x, max.y, max.z, 1.0),
+        ];
+
         // check box outside/inside of frustum
-        for i in 0..6 {
-            let mut out = 0;
-            if self.0[i].dot(vec4(aabb.min().x, aabb.min().y, aabb.min().z, 1.0)) < 0.0 {
-                out += 1
-            };
-            if self.0[i].dot(vec4(aabb.max().x, aabb.min().y, aabb.min().z, 1.0)) < 0.0 {
-                out += 1
-            };
-            if self.0[i].dot(vec4(aabb.min().x, aabb.max().y, aabb.min().z, 1.0)) < 0.0 {
-                out += 1
-            };
-            if self.0[i].dot(vec4(aabb.max().x, aabb.max().y, aabb.min().z, 1.0)) < 0.0 {
-                out += 1
-            };
-            if self.0[i].dot(vec4(aabb.min().x, aabb.min().y, aabb.max().z, 1.0)) < 0.0 {
-                out += 1
-            };
-            if self.0[i].dot(vec4(aabb.max().x, aabb.min().y, aabb.max().z, 1.0)) < 0.0 {
-                out += 1
-            };
-            if self.0[i].dot(vec4(aabb.min().x, aabb.max().y, aabb.max().z, 1.0)) < 0.0 {
-                out += 1
-            };
-            if self.0[i].dot(vec4(aabb.max().x, aabb.max().y, aabb.max().z, 1.0)) < 0.0 {
-                out += 1
-            };
-            if out == 8 {
+        for plane in self.planes.iter() {
+            if box_corners.iter().all(|corner| plane.dot(*corner) < 0.0) {
                 return false;
             }
         }
-        // TODO: Test the frustum corners against the box planes (http://www.iquilezles.org/www/articles/frustumcorrect/frustumcorrect.htm)
 
+        // check frustum outside/inside box, see http://www.iquilezles.org/www/articles/frustumcorrect/frustumcorrect.htm
+        // The corners are homogeneous, so the comparisons are scaled by w to also handle corners at infinity
+        if let Some(corners) = self.corners {
+            for i in 0..3 {
+                if corners.iter().all(|c| c[i] > max[i] * c.w)
+                    || corners.iter().all(|c| c[i] < min[i] * c.w)
+                {
+                    return false;
+                }
+            }
+        }
         true
     }
 }
 
 ///
-/// The type of projection used by a camera (orthographic or perspective) including parameters.
+/// Generates the rays of a [Camera] for many pixels or uv coordinates, for example for a CPU ray tracer,
+/// without inverting the view and projection matrices for each ray, see [Camera::ray_generator].
+///
+/// **Note:** The generator is a snapshot of the camera, so it must be recreated if the camera changes.
 ///
 #[derive(Clone, Debug)]
+pub struct RayGenerator {
+    projection_type: ProjectionType,
+    screen2ray: Mat4,
+    position: Vec3,
+    view_direction: Vec3,
+    viewport: Viewport,
+}
+
+impl RayGenerator {
+    ///
+    /// Returns the origin and direction of the ray through the given pixel coordinate, see [Camera::position_at_pixel] and [Camera::view_direction_at_pixel].
+    ///
+    pub fn ray_at_pixel(&self, pixel: impl Into<PixelPoint>) -> (Vec3, Vec3) {
+        let pixel = pixel.into();
+        self.ray_at_uv_coordinates((
+            (pixel.x - self.viewport.x as f32) / self.viewport.width as f32,
+            (pixel.y - self.viewport.y as f32) / self.viewport.height as f32,
+        ))
+    }
+
+    ///
+    /// Returns the origin and direction of the ray through the given uv coordinate of the viewport,
+    /// see [Camera::position_at_uv_coordinates] and [Camera::view_direction_at_uv_coordinates].
+    ///
+    pub fn ray_at_uv_coordinates(&self, coords: impl Into<UvCoordinate>) -> (Vec3, Vec3) {
+        let coords = coords.into();
+        (
+            self.position_at_uv_coordinates(coords),
+            self.view_direction_at_uv_coordinates(coords),
+        )
+    }
+
+    fn position_at_uv_coordinates(&self, coords: UvCoordinate) -> Vec3 {
+        match self.projection_type {
+            ProjectionType::Orthographic { .. } | ProjectionType::Planar { .. } => {
+                let screen_pos = vec4(2. * coords.u - 1., 2. * coords.v - 1.0, 0.0, 1.);
+                let p = (self.screen2ray * screen_pos).truncate();
+                p + (self.position - p).project_on(self.view_direction) // Project onto the image plane
+            }
+            ProjectionType::Perspective { .. } => self.position,
+        }
+    }
+
+    fn view_direction_at_uv_coordinates(&self, coords: UvCoordinate) -> Vec3 {
+        match self.projection_type {
+            ProjectionType::Orthographic { .. } => self.view_direction,
+            ProjectionType::Perspective { .. } => {
+                let screen_pos = vec4(2. * coords.u - 1., 2. * coords.v - 1.0, 0., 1.);
+                (self.screen2ray * screen_pos).truncate().normalize()
+            }
+            ProjectionType::Planar { .. } => {
+                let start_pos = Point3::new(2. * coords.u - 1., 2. * coords.v - 1.0, -0.5);
+                let end_pos = Point3::new(2. * coords.u - 1., 2. * coords.v - 1.0, 0.5);
+                (self.screen2ray.transform_point(end_pos)
+                    - self.screen2ray.transform_point(start_pos))
+                .normalize()
+            }
+        }
+    }
+}
+
+///
+/// The type of projection used by a camera (orthographic or perspective) including parameters.
+///
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ProjectionType {
     /// Orthographic projection
@@ -247,7 +402,202 @@ pub struct Camera {
     projection: Mat4,
 }
 
+///
+/// A builder for a [Camera], which is an alternative to the [Camera::new_perspective], [Camera::new_orthographic] and [Camera::new_planar] constructors.
+/// All parameters that are not specified are the same as for [Camera::default].
+///
+/// ```
+/// # use three_d_asset::*;
+/// let camera = CameraBuilder::new()
+///     .viewport(Viewport::new_at_origo(1280, 720))
+///     .perspective(degrees(60.0))
+///     .look_at(vec3(0.0, 2.0, 5.0), vec3(0.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0))
+///     .z_range(0.1, 100.0)
+///     .build();
+/// ```
+///
+#[derive(Clone, Debug)]
+pub struct CameraBuilder {
+    viewport: Viewport,
+    projection_type: ProjectionType,
+    position: Vec3,
+    target: Vec3,
+    up: Vec3,
+    z_near: f32,
+    z_far: f32,
+}
+
+impl CameraBuilder {
+    ///
+    /// Creates a new builder with the same parameters as [Camera::default].
+    ///
+    pub fn new() -> Self {
+        Self {
+            viewport: Viewport::new_at_origo(1, 1),
+            projection_type: ProjectionType::Perspective {
+                field_of_view_y: degrees(45.0).into(),
+            },
+            position: vec3(0.0, 0.0, 0.0),
+            target: vec3(0.0, 0.0, -1.0),
+            up: vec3(0.0, 1.0, 0.0),
+            z_near: 0.1,
+            z_far: 1000.0,
+        }
+    }
+
+    ///
+    /// Sets the viewport that the camera is projecting into.
+    ///
+    pub fn viewport(mut self, viewport: Viewport) -> Self {
+        self.viewport = viewport;
+        self
+    }
+
+    ///
+    /// Use a perspective projection with the given field of view in the y-direction.
+    ///
+    pub fn perspective(mut self, field_of_view_y: impl Into<Radians>) -> Self {
+        self.projection_type = ProjectionType::Perspective {
+            field_of_view_y: field_of_view_y.into(),
+        };
+        self
+    }
+
+    ///
+    /// Use an orthographic projection with the given height of the camera film/sensor.
+    ///
+    pub fn orthographic(mut self, height: f32) -> Self {
+        self.projection_type = ProjectionType::Orthographic { height };
+        self
+    }
+
+    ///
+    /// Use a general planar projection with the given field of view in the y-direction.
+    ///
+    pub fn planar(mut self, field_of_view_y: impl Into<Radians>) -> Self {
+        self.projection_type = ProjectionType::Planar {
+            field_of_view_y: field_of_view_y.into(),
+        };
+        self
+    }
+
+    ///
+    /// Places the camera at the given position looking towards the target with the given up direction.
+    ///
+    pub fn look_at(mut self, position: Vec3, target: Vec3, up: Vec3) -> Self {
+        self.position = position;
+        self.target = target;
+        self.up = up;
+        self
+    }
+
+    ///
+    /// Sets the distance to the near and far plane of the camera frustum.
+    ///
+    pub fn z_range(mut self, z_near: f32, z_far: f32) -> Self {
+        self.z_near = z_near;
+        self.z_far = z_far;
+        self
+    }
+
+    ///
+    /// Constructs the [Camera].
+    ///
+    pub fn build(self) -> Camera {
+        let mut camera = Camera::new(self.viewport);
+        camera.set_view(self.position, self.target, self.up);
+        match self.projection_type {
+            ProjectionType::Perspective { field_of_view_y } => {
+                camera.set_perspective_projection(field_of_view_y, self.z_near, self.z_far)
+            }
+            ProjectionType::Orthographic { height } => {
+                camera.set_orthographic_projection(height, self.z_near, self.z_far)
+            }
+            ProjectionType::Planar { field_of_view_y } => {
+                camera.set_planar_projection(field_of_view_y, self.z_near, self.z_far)
+            }
+        }
+        camera
+    }
+}
+
+impl Default for CameraBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///
+/// All of the parameters needed to construct an identical [Camera], see [Camera::projection_parameters].
+/// The orthographic and planar projections also depend on the distance between the position and the target.
+///
+/// ```
+/// # use three_d_asset::*;
+/// let camera = Camera::new_planar(
+///     Viewport::new_at_origo(1280, 720),
+///     vec3(0.0, 2.0, 5.0),
+///     vec3(0.0, 0.0, 0.0),
+///     vec3(0.0, 1.0, 0.0),
+///     degrees(-30.0),
+///     0.1,
+///     100.0,
+/// );
+/// let copy = Camera::from(camera.projection_parameters());
+/// assert_eq!(copy.projection(), camera.projection());
+/// ```
+///
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProjectionParameters {
+    /// The viewport that the camera is projecting into.
+    pub viewport: Viewport,
+    /// The type of projection including parameters.
+    pub projection_type: ProjectionType,
+    /// The distance to the near plane of the camera frustum.
+    pub z_near: f32,
+    /// The distance to the far plane of the camera frustum.
+    pub z_far: f32,
+    /// The position of the camera.
+    pub position: Vec3,
+    /// The point that the camera looks towards.
+    pub target: Vec3,
+    /// The up direction of the camera.
+    pub up: Vec3,
+}
+
+impl From<ProjectionParameters> for Camera {
+    fn from(parameters: ProjectionParameters) -> Self {
+        CameraBuilder {
+            viewport: parameters.viewport,
+            projection_type: parameters.projection_type,
+            position: parameters.position,
+            target: parameters.target,
+            up: parameters.up,
+            z_near: parameters.z_near,
+            z_far: parameters.z_far,
+        }
+        .build()
+    }
+}
+
+impl Default for Camera {
+    ///
+    /// A camera with a perspective projection with a 45 degrees field of view, placed at origo looking down the negative z-axis with the y-axis as up direction.
+    /// The viewport is 1x1 pixels, so it should be set using [Camera::set_viewport] before use.
+    ///
+    fn default() -> Self {
+        CameraBuilder::new().build()
+    }
+}
+
 impl Camera {
+    ///
+    /// Returns a [CameraBuilder] for constructing a camera.
+    ///
+    pub fn builder() -> CameraBuilder {
+        CameraBuilder::new()
+    }
+
     ///
     /// New camera which projects the world with an orthographic projection.
     ///
@@ -448,12 +798,9 @@ impl Camera {
     ///
     pub fn position_at_uv_coordinates(&self, coords: impl Into<UvCoordinate>) -> Vec3 {
         match self.projection_type() {
-            ProjectionType::Orthographic { .. } | ProjectionType::Planar { .. } => {
-                let coords = coords.into();
-                let screen_pos = vec4(2. * coords.u - 1., 2. * coords.v - 1.0, 0.0, 1.);
-                let p = (self.screen2ray() * screen_pos).truncate();
-                p + (self.position - p).project_on(self.view_direction()) // Project onto the image plane
-            }
+            ProjectionType::Orthographic { .. } | ProjectionType::Planar { .. } => self
+                .ray_generator()
+                .position_at_uv_coordinates(coords.into()),
             ProjectionType::Perspective { .. } => self.position,
         }
     }
@@ -477,19 +824,24 @@ impl Camera {
     pub fn view_direction_at_uv_coordinates(&self, coords: impl Into<UvCoordinate>) -> Vec3 {
         match self.projection_type() {
             ProjectionType::Orthographic { .. } => self.view_direction(),
-            ProjectionType::Perspective { .. } => {
-                let coords = coords.into();
-                let screen_pos = vec4(2. * coords.u - 1., 2. * coords.v - 1.0, 0., 1.);
-                (self.screen2ray() * screen_pos).truncate().normalize()
-            }
-            ProjectionType::Planar { .. } => {
-                let coords = coords.into();
-                let start_pos = Point3::new(2. * coords.u - 1., 2. * coords.v - 1.0, -0.5);
-                let end_pos = Point3::new(2. * coords.u - 1., 2. * coords.v - 1.0, 0.5);
-                (self.screen2ray().transform_point(end_pos)
-                    - self.screen2ray().transform_point(start_pos))
-                .normalize()
-            }
+            ProjectionType::Perspective { .. } | ProjectionType::Planar { .. } => self
+                .ray_generator()
+                .view_direction_at_uv_coordinates(coords.into()),
+        }
+    }
+
+    ///
+    /// Returns a [RayGenerator] which computes the rays through many pixels or uv coordinates faster than calling
+    /// [Camera::position_at_pixel] and [Camera::view_direction_at_pixel] for each pixel, since the matrices are only inverted once.
+    /// The generator must be recreated if the camera changes.
+    ///
+    pub fn ray_generator(&self) -> RayGenerator {
+        RayGenerator {
+            projection_type: self.projection_type.clone(),
+            screen2ray: self.screen2ray(),
+            position: self.position,
+            view_direction: self.view_direction(),
+            viewport: self.viewport,
         }
     }
 
@@ -536,6 +888,111 @@ impl Camera {
         self.pixel_at_uv_coordinates(self.uv_coordinates_at_position(position))
     }
 
+    ///
+    /// Returns the 3D position at the given pixel coordinate and depth, ie. the inverse of projecting a world position to the screen.
+    /// The depth is given in normalized device coordinates which, using the OpenGL convention of the projection matrices in this crate,
+    /// is `-1.0` at the near plane and `1.0` at the far plane.
+    /// Note that for a perspective projection, the depth in normalized device coordinates is not linear in the distance to the camera.
+    ///
+    pub fn unproject(&self, pixel: impl Into<PixelPoint>, depth_ndc: f32) -> Vec3 {
+        let coords = self.uv_coordinates_at_pixel(pixel);
+        self.ndc_to_world(vec3(2. * coords.u - 1., 2. * coords.v - 1.0, depth_ndc))
+    }
+
+    ///
+    /// Returns the normalized device coordinates of the given world position, ie. the position transformed by the projection and view matrices followed by the perspective division.
+    /// This crate uses the OpenGL convention where the x and y coordinates are `-1.0` at the left and bottom of the viewport and `1.0` at the right and top,
+    /// and the z coordinate is `-1.0` at the near plane and `1.0` at the far plane.
+    /// Positions behind the camera of a perspective projection are not meaningful in normalized device coordinates.
+    ///
+    pub fn world_to_ndc(&self, position: Vec3) -> Vec3 {
+        let p = self.projection * self.view * position.extend(1.0);
+        p.truncate() / p.w
+    }
+
+    ///
+    /// Returns the world position of the given normalized device coordinates, ie. the inverse of [Camera::world_to_ndc].
+    ///
+    pub fn ndc_to_world(&self, ndc: Vec3) -> Vec3 {
+        let p = (self.projection * self.view)
+            .invert()
+            .unwrap_or_else(Mat4::identity)
+            * ndc.extend(1.0);
+        p.truncate() / p.w
+    }
+
+    ///
+    /// Returns the minimum and maximum pixel coordinates of the rectangle on the screen covered by the given bounding box.
+    /// The parts of the box that are behind the near plane are clipped away before projecting it to the screen.
+    /// Returns `None` if the box is empty or entirely behind the near plane.
+    ///
+    /// **Note:** The rectangle is not clipped to the viewport, so it might extend outside the screen.
+    ///
+    pub fn screen_bounds(&self, aabb: AxisAlignedBoundingBox) -> Option<(PixelPoint, PixelPoint)> {
+        if aabb.is_empty() {
+            return None;
+        }
+        let view_projection = self.projection * self.view;
+        let corners = (0..8)
+            .map(|i| {
+                let corner = vec3(
+                    if i & 1 == 0 {
+                        aabb.min().x
+                    } else {
+                        aabb.max().x
+                    },
+                    if i & 2 == 0 {
+                        aabb.min().y
+                    } else {
+                        aabb.max().y
+                    },
+                    if i & 4 == 0 {
+                        aabb.min().z
+                    } else {
+                        aabb.max().z
+                    },
+                );
+                view_projection * corner.extend(1.0)
+            })
+            .collect::<Vec<_>>();
+
+        // A point in clip space is in front of the near plane if z >= -w
+        let near_distance = |p: Vec4| p.z + p.w;
+        let mut points = corners
+            .iter()
+            .filter(|p| near_distance(**p) >= 0.0)
+            .copied()
+            .collect::<Vec<_>>();
+        for i in 0..8 {
+            for bit in [1, 2, 4] {
+                if i & bit == 0 {
+                    let (a, b) = (corners[i], corners[i | bit]);
+                    let (da, db) = (near_distance(a), near_distance(b));
+                    if (da < 0.0) != (db < 0.0) {
+                        points.push(a + (b - a) * (da / (da - db)));
+                    }
+                }
+            }
+        }
+
+        let mut min = vec2(f32::INFINITY, f32::INFINITY);
+        let mut max = vec2(f32::NEG_INFINITY, f32::NEG_INFINITY);
+        for p in points {
+            // Points on the near plane of a perspective projection can have w equal to zero
+            let w = p.w.max(f32::EPSILON);
+            let pixel: Vec2 = self
+                .pixel_at_uv_coordinates((0.5 * (p.x / w + 1.0), 0.5 * (p.y / w + 1.0)))
+                .into();
+            min = vec2(min.x.min(pixel.x), min.y.min(pixel.y));
+            max = vec2(max.x.max(pixel.x), max.y.max(pixel.y));
+        }
+        if min.x > max.x {
+            None
+        } else {
+            Some((min.into(), max.into()))
+        }
+    }
+
     ///
     /// Returns the type of projection (orthographic or perspective) including parameters.
     ///
@@ -543,6 +1000,21 @@ impl Camera {
         &self.projection_type
     }
 
+    ///
+    /// Returns all of the parameters needed to construct an identical camera using `Camera::from`.
+    ///
+    pub fn projection_parameters(&self) -> ProjectionParameters {
+        ProjectionParameters {
+            viewport: self.viewport,
+            projection_type: self.projection_type.clone(),
+            z_near: self.z_near,
+            z_far: self.z_far,
+            position: self.position,
+            target: self.target,
+            up: self.up,
+        }
+    }
+
     ///
     /// Returns the view matrix, ie. the matrix that transforms objects from world space (as placed in the world) to view space (as seen from this camera).
     ///
@@ -798,3 +1270,279 @@ impl Camera {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!((a - b).magnitude() < 0.0001, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    pub fn projection_parameters() {
+        let viewport = Viewport::new_at_origo(1280, 720);
+        let (position, target, up) = (
+            vec3(1.0, 2.0, 5.0),
+            vec3(0.0, 0.5, 0.0),
+            vec3(0.0, 1.0, 0.0),
+        );
+        let cameras = [
+            Camera::new_orthographic(viewport, position, target, up, 3.0, 0.1, 100.0),
+            Camera::new_perspective(viewport, position, target, up, degrees(60.0), 0.1, 100.0),
+            Camera::new_planar(viewport, position, target, up, degrees(30.0), 0.1, 100.0),
+            Camera::new_planar(viewport, position, target, up, degrees(-30.0), 0.1, 100.0),
+            // The near plane is moved in front of the focal point, which must not be stored
+            Camera::new_planar(viewport, position, target, up, degrees(-150.0), 0.1, 100.0),
+        ];
+        for camera in cameras {
+            let parameters = camera.projection_parameters();
+            let copy = Camera::from(parameters.clone());
+            assert_eq!(copy.projection_parameters(), parameters);
+            assert_eq!(copy.projection(), camera.projection());
+            assert_eq!(copy.view(), camera.view());
+        }
+    }
+
+    #[test]
+    pub fn frustum_infinite_far_plane() {
+        let z_near = 0.1;
+        let f = 1.0 / (degrees(45.0) / 2.0).tan();
+        #[rustfmt::skip]
+        let projection = Mat4::new(
+            f, 0.0, 0.0, 0.0,
+            0.0, f, 0.0, 0.0,
+            0.0, 0.0, -1.0, -1.0,
+            0.0, 0.0, -2.0 * z_near, 0.0,
+        );
+        let view = Mat4::look_at_rh(
+            Point3::new(0.0, 0.0, 10.0),
+            Point3::new(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+        );
+        let frustum = Frustum::from_view_projection_parts(view, projection);
+        let aabb = |center: Vec3| {
+            AxisAlignedBoundingBox::new_with_positions(&[
+                center - vec3(1.0, 1.0, 1.0),
+                center + vec3(1.0, 1.0, 1.0),
+            ])
+        };
+        assert!(frustum.contains(aabb(vec3(0.0, 0.0, 0.0))));
+        assert!(frustum.contains(aabb(vec3(0.0, 0.0, -1.0e6))));
+        assert!(frustum.contains(aabb(vec3(1.0e5, 0.0, -1.0e6))));
+        assert!(!frustum.contains(aabb(vec3(0.0, 0.0, 20.0))));
+        assert!(!frustum.contains(aabb(vec3(0.0, 0.0, 10.95))));
+        assert!(!frustum.contains(aabb(vec3(20.0, 0.0, 0.0))));
+        assert!(!frustum.contains(aabb(vec3(0.0, -20.0, 0.0))));
+        assert!(!frustum.contains(aabb(vec3(1.0e6, 0.0, -1.0e6))));
+        // Only the corners of the near plane are finite, so the frustum extends infinitely in all directions except backwards
+        assert!(frustum.contains(aabb(vec3(400.0, -1.0, -1.0e3))));
+    }
+
+    #[test]
+    pub fn frustum_corners() {
+        let frustum = Frustum::new(cgmath::perspective(degrees(90.0), 1.0, 1.0, 10.0));
+        let aabb = |min: Vec3, max: Vec3| AxisAlignedBoundingBox::new_with_positions(&[min, max]);
+
+        // A thin box passing diagonally by the far right edge of the frustum without intersecting it.
+        // Every frustum plane has a box corner on the inside, so only the frustum corners reveal that it is outside.
+        let outside = aabb(vec3(10.5, -1.0, -30.0), vec3(11.0, 1.0, -9.5));
+        assert!(!frustum.contains(outside));
+        let outside = aabb(vec3(-1.0, 10.5, -30.0), vec3(1.0, 11.0, -9.5));
+        assert!(!frustum.contains(outside));
+
+        let intersecting = aabb(vec3(9.0, -1.0, -30.0), vec3(11.0, 1.0, -9.5));
+        assert!(frustum.contains(intersecting));
+        let inside = aabb(vec3(-1.0, -1.0, -5.0), vec3(1.0, 1.0, -4.0));
+        assert!(frustum.contains(inside));
+        let enclosing = aabb(vec3(-100.0, -100.0, -100.0), vec3(100.0, 100.0, 100.0));
+        assert!(frustum.contains(enclosing));
+    }
+
+    #[test]
+    pub fn ray_generator() {
+        let viewport = Viewport {
+            x: 10,
+            y: 20,
+            width: 200,
+            height: 100,
+        };
+        let (position, target, up) = (
+            vec3(1.0, 2.0, 5.0),
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+        );
+        let cameras = [
+            Camera::new_perspective(viewport, position, target, up, degrees(60.0), 0.1, 100.0),
+            Camera::new_orthographic(viewport, position, target, up, 3.0, 0.1, 100.0),
+            Camera::new_planar(viewport, position, target, up, degrees(60.0), 0.1, 100.0),
+        ];
+        for camera in cameras {
+            let generator = camera.ray_generator();
+            for pixel in [(10.0, 20.0), (60.5, 70.0), (209.0, 119.0)] {
+                let (origin, direction) = generator.ray_at_pixel(pixel);
+                assert_eq!(origin, camera.position_at_pixel(pixel));
+                assert_eq!(direction, camera.view_direction_at_pixel(pixel));
+            }
+            let (origin, direction) = generator.ray_at_uv_coordinates((0.25, 0.75));
+            assert_eq!(origin, camera.position_at_uv_coordinates((0.25, 0.75)));
+            assert_eq!(
+                direction,
+                camera.view_direction_at_uv_coordinates((0.25, 0.75))
+            );
+        }
+    }
+
+    #[test]
+    pub fn viewport_split() {
+        let viewport = Viewport {
+            x: 10,
+            y: 20,
+            width: 101,
+            height: 51,
+        };
+        let (left, right) = viewport.split_horizontal(0.5);
+        assert_eq!((left.x, left.width, right.x, right.width), (10, 51, 61, 50));
+        assert_eq!(
+            (left.y, left.height, right.y, right.height),
+            (20, 51, 20, 51)
+        );
+        let (bottom, top) = viewport.split_vertical(0.25);
+        assert_eq!(
+            (bottom.y, bottom.height, top.y, top.height),
+            (20, 13, 33, 38)
+        );
+
+        assert!(viewport.contains((10.0, 20.0)));
+        assert!(viewport.contains((110.5, 70.5)));
+        assert!(!viewport.contains((111.0, 30.0)));
+        assert!(!viewport.contains((50.0, 19.9)));
+    }
+
+    #[test]
+    pub fn unproject() {
+        let camera = Camera::new_perspective(
+            Viewport::new_at_origo(200, 100),
+            vec3(0.0, 0.0, 5.0),
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            degrees(60.0),
+            1.0,
+            100.0,
+        );
+        assert_near(camera.unproject((100.0, 50.0), -1.0), vec3(0.0, 0.0, 4.0));
+        assert_near(camera.unproject((100.0, 50.0), 1.0), vec3(0.0, 0.0, -95.0));
+
+        let position = vec3(0.5, -0.3, 1.0);
+        let proj = camera.projection() * camera.view() * position.extend(1.0);
+        assert_near(
+            camera.unproject(camera.pixel_at_position(position), proj.z / proj.w),
+            position,
+        );
+    }
+
+    #[test]
+    pub fn world_to_ndc() {
+        let perspective = Camera::new_perspective(
+            Viewport::new_at_origo(200, 100),
+            vec3(1.0, 2.0, 5.0),
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            degrees(60.0),
+            1.0,
+            100.0,
+        );
+        let orthographic = Camera::new_orthographic(
+            Viewport::new_at_origo(200, 100),
+            vec3(1.0, 2.0, 5.0),
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            4.0,
+            1.0,
+            100.0,
+        );
+        for camera in [perspective, orthographic] {
+            for position in [
+                vec3(0.0, 0.0, 0.0),
+                vec3(0.5, -0.3, 1.0),
+                vec3(-2.0, 1.0, -10.0),
+                vec3(3.0, 0.2, 2.0),
+            ] {
+                assert_near(camera.ndc_to_world(camera.world_to_ndc(position)), position);
+            }
+            let near = camera.position() + camera.view_direction() * camera.z_near();
+            assert_near(camera.world_to_ndc(near), vec3(0.0, 0.0, -1.0));
+            let far = camera.position() + camera.view_direction() * camera.z_far();
+            assert_near(camera.world_to_ndc(far), vec3(0.0, 0.0, 1.0));
+        }
+    }
+
+    #[test]
+    pub fn screen_bounds() {
+        let camera = Camera::new_perspective(
+            Viewport::new_at_origo(200, 100),
+            vec3(0.0, 0.0, 5.0),
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            degrees(60.0),
+            0.1,
+            100.0,
+        );
+        let aabb = AxisAlignedBoundingBox::new_with_positions(&[
+            vec3(-1.0, -1.0, 0.0),
+            vec3(1.0, 1.0, 0.0),
+        ]);
+        let (min, max) = camera.screen_bounds(aabb).unwrap();
+        let expected_min: Vec2 = camera.pixel_at_position(vec3(-1.0, -1.0, 0.0)).into();
+        let expected_max: Vec2 = camera.pixel_at_position(vec3(1.0, 1.0, 0.0)).into();
+        assert!((Vec2::from(min) - expected_min).magnitude() < 0.001);
+        assert!((Vec2::from(max) - expected_max).magnitude() < 0.001);
+
+        let behind = AxisAlignedBoundingBox::new_with_positions(&[
+            vec3(-1.0, -1.0, 6.0),
+            vec3(1.0, 1.0, 7.0),
+        ]);
+        assert!(camera.screen_bounds(behind).is_none());
+
+        // A box the camera is inside covers more than the entire screen
+        let around = AxisAlignedBoundingBox::new_with_positions(&[
+            vec3(-1.0, -1.0, 4.0),
+            vec3(1.0, 1.0, 6.0),
+        ]);
+        let (min, max) = camera.screen_bounds(around).unwrap();
+        assert!(min.x < 0.0 && min.y < 0.0 && max.x > 200.0 && max.y > 100.0);
+    }
+
+    #[test]
+    pub fn builder() {
+        let camera = Camera::builder()
+            .viewport(Viewport::new_at_origo(200, 100))
+            .perspective(degrees(60.0))
+            .look_at(
+                vec3(0.0, 0.0, 5.0),
+                vec3(0.0, 0.0, 0.0),
+                vec3(0.0, 1.0, 0.0),
+            )
+            .z_range(1.0, 100.0)
+            .build();
+        let expected = Camera::new_perspective(
+            Viewport::new_at_origo(200, 100),
+            vec3(0.0, 0.0, 5.0),
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.0, 1.0, 0.0),
+            degrees(60.0),
+            1.0,
+            100.0,
+        );
+        assert_eq!(camera.view(), expected.view());
+        assert_eq!(camera.projection(), expected.projection());
+        assert_eq!(camera.viewport(), expected.viewport());
+
+        let camera = Camera::default();
+        assert_eq!(camera.view_direction(), vec3(0.0, 0.0, -1.0));
+        assert!(matches!(
+            camera.projection_type(),
+            ProjectionType::Perspective { .. }
+        ));
+    }
+}
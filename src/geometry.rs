@@ -13,7 +13,7 @@ pub use crate::prelude::*;
 ///
 /// A CPU-side version of a geometry.
 ///
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Geometry {
     /// Points geometry
@@ -23,6 +23,26 @@ pub enum Geometry {
 }
 
 impl Geometry {
+    ///
+    /// Returns the number of vertices, ie. the number of points for a [PointCloud].
+    ///
+    pub fn vertex_count(&self) -> usize {
+        match self {
+            Self::Triangles(mesh) => mesh.vertex_count(),
+            Self::Points(point_cloud) => point_cloud.positions.len(),
+        }
+    }
+
+    ///
+    /// Returns the number of triangles, ie. zero for a [PointCloud].
+    ///
+    pub fn triangle_count(&self) -> usize {
+        match self {
+            Self::Triangles(mesh) => mesh.triangle_count(),
+            Self::Points(_) => 0,
+        }
+    }
+
     ///
     /// Computes normals if it is relevant for the geometry.
     ///
@@ -34,17 +54,29 @@ impl Geometry {
 
     ///
     /// Computes tangents if it is relevant for the geometry.
+    /// Returns an error if the geometry is a [TriMesh] without normals or uv coordinates.
     ///
-    pub fn compute_tangents(&mut self) {
+    pub fn compute_tangents(&mut self) -> crate::Result<()> {
         if let Self::Triangles(mesh) = self {
-            mesh.compute_tangents()
+            mesh.compute_tangents()?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Transforms the geometry by the given transformation.
+    ///
+    pub fn transform(&mut self, transform: Mat4) -> crate::Result<()> {
+        match self {
+            Self::Triangles(mesh) => mesh.transform(transform),
+            Self::Points(point_cloud) => point_cloud.transform(transform),
         }
     }
 
     ///
     /// Computes the [AxisAlignedBoundingBox] for this geometry.
     ///
-    pub fn compute_aabb(&mut self) -> AxisAlignedBoundingBox {
+    pub fn compute_aabb(&self) -> AxisAlignedBoundingBox {
         match self {
             Self::Triangles(mesh) => mesh.compute_aabb(),
             Self::Points(point_cloud) => point_cloud.compute_aabb(),
@@ -52,10 +84,43 @@ impl Geometry {
     }
 }
 
+///
+/// Returns the matrix that transforms normals and tangents when positions are transformed by the given transformation, ie. the inverse transpose of the upper left 3x3 part.
+/// Rotations, translations and uniform scales are handled without inverting the matrix.
+///
+pub(crate) fn normal_transformation(transform: Mat4) -> crate::Result<Mat3> {
+    let m = Mat3::from_cols(
+        transform.x.truncate(),
+        transform.y.truncate(),
+        transform.z.truncate(),
+    );
+    let scale2 = m.x.magnitude2();
+    let tolerance = 1e-5 * scale2;
+    let is_rotation_and_uniform_scale = scale2 > 0.0
+        && (m.y.magnitude2() - scale2).abs() <= tolerance
+        && (m.z.magnitude2() - scale2).abs() <= tolerance
+        && m.x.dot(m.y).abs() <= tolerance
+        && m.x.dot(m.z).abs() <= tolerance
+        && m.y.dot(m.z).abs() <= tolerance;
+    if is_rotation_and_uniform_scale {
+        Ok(m / scale2)
+    } else {
+        let normal_transform = transform
+            .invert()
+            .ok_or(crate::Error::FailedInvertingTransformationMatrix)?
+            .transpose();
+        Ok(Mat3::from_cols(
+            normal_transform.x.truncate(),
+            normal_transform.y.truncate(),
+            normal_transform.z.truncate(),
+        ))
+    }
+}
+
 ///
 /// An array of indices. Supports different data types.
 ///
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Indices {
     /// Do not use indices, ie. the faces are all unconnected.
@@ -69,6 +134,43 @@ pub enum Indices {
 }
 
 impl Indices {
+    ///
+    /// Converts the indices of a triangle strip, where each index after the first two defines a triangle together with the two previous indices,
+    /// into a list of triangles where three contiguous indices defines a triangle.
+    /// Every other triangle is flipped to keep the winding order consistent, as specified by glTF and OpenGL.
+    /// The indices are stored using the smallest data type that can represent all of them.
+    ///
+    pub fn from_triangle_strip(strip: &[u32]) -> Self {
+        let mut indices = Self::U32(
+            (0..strip.len().saturating_sub(2))
+                .flat_map(|i| {
+                    if i % 2 == 0 {
+                        [strip[i], strip[i + 1], strip[i + 2]]
+                    } else {
+                        [strip[i], strip[i + 2], strip[i + 1]]
+                    }
+                })
+                .collect(),
+        );
+        indices.shrink();
+        indices
+    }
+
+    ///
+    /// Converts the indices of a triangle fan, where each index after the first two defines a triangle together with the previous index and the first index,
+    /// into a list of triangles where three contiguous indices defines a triangle.
+    /// The indices are stored using the smallest data type that can represent all of them.
+    ///
+    pub fn from_triangle_fan(fan: &[u32]) -> Self {
+        let mut indices = Self::U32(
+            (1..fan.len().saturating_sub(1))
+                .flat_map(|i| [fan[i], fan[i + 1], fan[0]])
+                .collect(),
+        );
+        indices.shrink();
+        indices
+    }
+
     ///
     /// Converts all the indices as `u32` data type.
     ///
@@ -111,6 +213,40 @@ impl Indices {
     pub fn is_empty(&self) -> bool {
         self.len().map(|i| i == 0).unwrap_or(true)
     }
+
+    ///
+    /// Returns whether or not all the indices can be represented using the `u16` data type.
+    /// Returns `true` for [Indices::None].
+    ///
+    pub fn fits_u16(&self) -> bool {
+        self.max_index().unwrap_or(0) <= u16::MAX as u32
+    }
+
+    ///
+    /// Converts the indices to the smallest data type that can represent all of the indices without loss.
+    /// Does nothing for [Indices::None].
+    ///
+    pub fn shrink(&mut self) {
+        if let Some(max) = self.max_index() {
+            let indices = std::mem::take(self).into_u32().unwrap();
+            *self = if max <= u8::MAX as u32 {
+                Self::U8(indices.into_iter().map(|i| i as u8).collect())
+            } else if max <= u16::MAX as u32 {
+                Self::U16(indices.into_iter().map(|i| i as u16).collect())
+            } else {
+                Self::U32(indices)
+            };
+        }
+    }
+
+    fn max_index(&self) -> Option<u32> {
+        match self {
+            Self::None => None,
+            Self::U8(values) => Some(values.iter().max().map_or(0, |i| *i as u32)),
+            Self::U16(values) => Some(values.iter().max().map_or(0, |i| *i as u32)),
+            Self::U32(values) => Some(values.iter().max().copied().unwrap_or(0)),
+        }
+    }
 }
 
 impl std::default::Default for Indices {
@@ -122,7 +258,7 @@ impl std::default::Default for Indices {
 ///
 /// An array of positions. Supports f32 and f64 data types.
 ///
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Positions {
     /// Uses 32 bit float for the vertex positions.
@@ -207,12 +343,13 @@ impl Positions {
     pub fn compute_aabb(&self) -> AxisAlignedBoundingBox {
         match self {
             Positions::F32(ref positions) => AxisAlignedBoundingBox::new_with_positions(positions),
-            Positions::F64(ref positions) => AxisAlignedBoundingBox::new_with_positions(
-                &positions
-                    .iter()
-                    .map(|v| Vec3::new(v.x as f32, v.y as f32, v.z as f32))
-                    .collect::<Vec<_>>(),
-            ),
+            Positions::F64(ref positions) => {
+                let mut aabb = AxisAlignedBoundingBox::EMPTY;
+                for v in positions {
+                    aabb.expand(&[Vec3::new(v.x as f32, v.y as f32, v.z as f32)]);
+                }
+                aabb
+            }
         }
     }
 }
@@ -233,3 +370,61 @@ impl std::fmt::Debug for Positions {
         d.finish()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn shrink_indices() {
+        let mut indices = Indices::U32(vec![0, 1, 255]);
+        assert!(indices.fits_u16());
+        indices.shrink();
+        assert_eq!(indices, Indices::U8(vec![0, 1, 255]));
+
+        let mut indices = Indices::U32(vec![0, 256, 65535]);
+        indices.shrink();
+        assert_eq!(indices, Indices::U16(vec![0, 256, 65535]));
+
+        let mut indices = Indices::U32(vec![0, 65536]);
+        assert!(!indices.fits_u16());
+        indices.shrink();
+        assert_eq!(indices, Indices::U32(vec![0, 65536]));
+
+        let mut indices = Indices::None;
+        indices.shrink();
+        assert_eq!(indices, Indices::None);
+    }
+
+    #[test]
+    pub fn from_triangle_strip_and_fan() {
+        assert_eq!(
+            Indices::from_triangle_strip(&[0, 1, 2, 3, 4]),
+            Indices::U8(vec![0, 1, 2, 1, 3, 2, 2, 3, 4])
+        );
+        assert_eq!(
+            Indices::from_triangle_fan(&[0, 1, 2, 3, 300]),
+            Indices::U16(vec![1, 2, 0, 2, 3, 0, 3, 300, 0])
+        );
+        assert_eq!(Indices::from_triangle_strip(&[0, 1]), Indices::U8(vec![]));
+        assert_eq!(Indices::from_triangle_fan(&[]), Indices::U8(vec![]));
+    }
+
+    #[test]
+    pub fn compute_aabb_f64() {
+        let positions = vec![
+            Vector3::new(1.0, -2.0, 3.0),
+            Vector3::new(-4.0, 5.0, 0.5),
+            Vector3::new(0.0, 0.0, -6.0),
+        ];
+        let aabb = Positions::F64(positions.clone()).compute_aabb();
+        let expected = Positions::F64(positions).to_f32();
+        let expected = AxisAlignedBoundingBox::new_with_positions(&expected);
+        assert_eq!(aabb.min(), expected.min());
+        assert_eq!(aabb.max(), expected.max());
+        assert_eq!(aabb.min(), vec3(-4.0, -2.0, -6.0));
+        assert_eq!(aabb.max(), vec3(1.0, 5.0, 3.0));
+
+        assert!(Positions::F64(Vec::new()).compute_aabb().is_empty());
+    }
+}
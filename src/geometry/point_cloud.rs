@@ -1,16 +1,18 @@
-use super::Positions;
-use crate::prelude::*;
+use super::{Indices, Positions, TriMesh};
+use crate::{prelude::*, Error, Result};
 
 ///
 /// Represents a set of points in 3D space, usually created with a scanner.
 ///
-#[derive(Default, Clone)]
+#[derive(Default, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointCloud {
     /// The positions of the points.
     pub positions: Positions,
     /// The colors of the points.
     pub colors: Option<Vec<Srgba>>,
+    /// The normals of the points, usually the estimated normal of the surface the point was sampled from.
+    pub normals: Option<Vec<Vec3>>,
 }
 
 impl std::fmt::Debug for PointCloud {
@@ -18,6 +20,7 @@ impl std::fmt::Debug for PointCloud {
         let mut d = f.debug_struct("PointCloud");
         d.field("positions", &self.positions.len());
         d.field("colors", &self.colors.as_ref().map(|v| v.len()));
+        d.field("normals", &self.normals.as_ref().map(|v| v.len()));
         d.finish()
     }
 }
@@ -43,10 +46,786 @@ impl PointCloud {
         }
     }
 
+    ///
+    /// Transforms the point cloud by the given transformation.
+    ///
+    pub fn transform(&mut self, transform: Mat4) -> Result<()> {
+        match self.positions {
+            Positions::F32(ref mut positions) => {
+                for pos in positions.iter_mut() {
+                    *pos = (transform * pos.extend(1.0)).truncate();
+                }
+            }
+            Positions::F64(ref mut positions) => {
+                let t = transform.cast::<f64>().unwrap();
+                for pos in positions.iter_mut() {
+                    *pos = (t * pos.extend(1.0)).truncate();
+                }
+            }
+        };
+
+        if let Some(ref mut normals) = self.normals {
+            let normal_transform = super::normal_transformation(transform)?;
+            for n in normals.iter_mut() {
+                *n = (normal_transform * *n).normalize();
+            }
+        }
+        Ok(())
+    }
+
     ///
     /// Computes the [AxisAlignedBoundingBox] for this point cloud.
     ///
     pub fn compute_aabb(&self) -> AxisAlignedBoundingBox {
         self.positions.compute_aabb()
     }
+
+    ///
+    /// Returns a new point cloud with at most one point per cube of size `voxel_size`.
+    /// The position, color and normal of each output point is the average of all the points inside the cube.
+    /// The output points are in the order of the first input point in each cube.
+    /// If `voxel_size` is not a finite positive number, a copy of the point cloud is returned.
+    ///
+    pub fn voxel_downsample(&self, voxel_size: f32) -> PointCloud {
+        if !voxel_size.is_finite() || voxel_size <= 0.0 {
+            return self.clone();
+        }
+        let positions = self.positions.to_f64();
+        let voxel_size = voxel_size as f64;
+        let mut cells = std::collections::HashMap::new();
+        let mut position_sums = Vec::new();
+        let mut color_sums = Vec::new();
+        let mut normal_sums = Vec::new();
+        let mut counts = Vec::new();
+        for (i, p) in positions.iter().enumerate() {
+            let cell = (
+                (p.x / voxel_size).floor() as i64,
+                (p.y / voxel_size).floor() as i64,
+                (p.z / voxel_size).floor() as i64,
+            );
+            let index = *cells.entry(cell).or_insert_with(|| {
+                position_sums.push(Vector3::zero());
+                color_sums.push(Vector4::zero());
+                normal_sums.push(Vector3::zero());
+                counts.push(0.0);
+                counts.len() - 1
+            });
+            position_sums[index] += *p;
+            if let Some(ref colors) = self.colors {
+                let c = colors[i];
+                color_sums[index] += vec4(c.r as f64, c.g as f64, c.b as f64, c.a as f64);
+            }
+            if let Some(ref normals) = self.normals {
+                normal_sums[index] += normals[i].cast::<f64>().unwrap();
+            }
+            counts[index] += 1.0;
+        }
+
+        let positions = position_sums.iter().zip(&counts).map(|(p, n)| p / *n);
+        PointCloud {
+            positions: match self.positions {
+                Positions::F32(_) => {
+                    Positions::F32(positions.map(|p| p.cast::<f32>().unwrap()).collect())
+                }
+                Positions::F64(_) => Positions::F64(positions.collect()),
+            },
+            colors: self.colors.as_ref().map(|_| {
+                color_sums
+                    .iter()
+                    .zip(&counts)
+                    .map(|(c, n)| {
+                        let c = c / *n;
+                        Srgba::new(
+                            c.x.round() as u8,
+                            c.y.round() as u8,
+                            c.z.round() as u8,
+                            c.w.round() as u8,
+                        )
+                    })
+                    .collect()
+            }),
+            normals: self.normals.as_ref().map(|_| {
+                normal_sums
+                    .iter()
+                    .map(|n| {
+                        if n.magnitude2() > 0.0 {
+                            n.normalize().cast::<f32>().unwrap()
+                        } else {
+                            Vec3::zero()
+                        }
+                    })
+                    .collect()
+            }),
+        }
+    }
+
+    ///
+    /// Estimates the normal of each point as the normal of the plane that best fits the `k` nearest points (including the point itself).
+    /// The normals are stored in [PointCloud::normals].
+    ///
+    /// The plane fitting cannot determine which side of the surface a normal should point to.
+    /// If a `viewpoint`, for example the position of the scanner, is given, the normals are oriented towards it,
+    /// otherwise the orientation of each normal is arbitrary.
+    ///
+    pub fn estimate_normals(&mut self, k: usize, viewpoint: Option<Vec3>) {
+        let positions = self.positions.to_f64();
+        let grid = PointGrid::new(&positions, k);
+        let viewpoint = viewpoint.map(|v| v.cast::<f64>().unwrap());
+        self.normals = Some(
+            positions
+                .iter()
+                .map(|p| {
+                    let neighbours = grid.k_nearest(&positions, *p, k);
+                    let mut normal = fit_plane_normal(neighbours.iter().map(|i| positions[*i]));
+                    if let Some(viewpoint) = viewpoint {
+                        if normal.dot(viewpoint - p) < 0.0 {
+                            normal = -normal;
+                        }
+                    }
+                    normal.cast::<f32>().unwrap()
+                })
+                .collect(),
+        );
+    }
+
+    ///
+    /// Reconstructs a triangle surface from the points using the ball pivoting algorithm,
+    /// which requires that the point cloud has [normals](PointCloud::normals) (see also [PointCloud::estimate_normals]).
+    ///
+    /// A ball is rolled over the points and each triangle consist of three points that the ball touches without containing any other points.
+    /// The radius of the ball is one and a half times the average distance between a point and its nearest neighbour.
+    /// This works best for evenly sampled surfaces, holes appear where the points are sparser than that and
+    /// details smaller than the ball are smoothed out. The normals are only used to determine the orientation of the triangles.
+    ///
+    /// The returned mesh contains all of the points as vertices, including the points which are not part of any triangle.
+    ///
+    pub fn to_mesh(&self) -> Result<TriMesh> {
+        let vertex_normals = self
+            .normals
+            .as_ref()
+            .ok_or(Error::PointCloudMissingNormals)?;
+        let positions = self.positions.to_f64();
+        let normals = vertex_normals
+            .iter()
+            .map(|n| n.cast::<f64>().unwrap())
+            .collect::<Vec<_>>();
+        let grid = PointGrid::new(&positions, 8);
+        let spacing = positions
+            .iter()
+            .filter_map(|p| {
+                grid.k_nearest(&positions, *p, 2)
+                    .get(1)
+                    .map(|i| positions[*i].distance(*p))
+            })
+            .sum::<f64>()
+            / positions.len().max(1) as f64;
+        let indices = BallPivoting {
+            positions: &positions,
+            normals: &normals,
+            grid: &grid,
+            radius: 1.5 * spacing,
+            used: vec![false; positions.len()],
+            edges: std::collections::HashSet::new(),
+            indices: Vec::new(),
+        }
+        .run();
+        Ok(TriMesh {
+            positions: self.positions.clone(),
+            indices: Indices::U32(indices),
+            normals: Some(vertex_normals.clone()),
+            colors: self.colors.clone(),
+            ..Default::default()
+        })
+    }
+
+    ///
+    /// Returns the convex hull of the points, ie. the smallest convex closed mesh containing all of the points.
+    /// The triangles are oriented counter clockwise seen from the outside and the normals point outwards.
+    /// The mesh only contains the points on the hull as vertices.
+    ///
+    /// If all of the points lie in a plane, on a line or in the same position, the hull has no volume and an empty mesh is returned.
+    ///
+    pub fn convex_hull(&self) -> TriMesh {
+        let positions = self.positions.to_f64();
+        let triangles = convex_hull(&positions);
+        let mut new_indices = vec![None; positions.len()];
+        let mut hull_positions = Vec::new();
+        let mut indices = Vec::with_capacity(triangles.len() * 3);
+        for i in triangles.into_iter().flatten() {
+            let index = *new_indices[i].get_or_insert_with(|| {
+                hull_positions.push(positions[i]);
+                hull_positions.len() - 1
+            });
+            indices.push(index as u32);
+        }
+        let mut mesh = TriMesh {
+            positions: match self.positions {
+                Positions::F32(_) => Positions::F32(
+                    hull_positions
+                        .into_iter()
+                        .map(|p| p.cast::<f32>().unwrap())
+                        .collect(),
+                ),
+                Positions::F64(_) => Positions::F64(hull_positions),
+            },
+            indices: Indices::U32(indices),
+            ..Default::default()
+        };
+        if !mesh.positions.is_empty() {
+            mesh.compute_normals();
+        }
+        mesh
+    }
+}
+
+///
+/// Returns the normal of the plane that best fits the given points (in the least squares sense) or the zero vector if the points are degenerate.
+///
+fn fit_plane_normal(points: impl Iterator<Item = Vector3<f64>> + Clone) -> Vector3<f64> {
+    let count = points.clone().count() as f64;
+    if count < 3.0 {
+        return Vector3::zero();
+    }
+    let centroid = points.clone().fold(Vector3::zero(), |a, p| a + p) / count;
+    let (mut xx, mut xy, mut xz, mut yy, mut yz, mut zz) = (0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    for p in points {
+        let r = p - centroid;
+        xx += r.x * r.x;
+        xy += r.x * r.y;
+        xz += r.x * r.z;
+        yy += r.y * r.y;
+        yz += r.y * r.z;
+        zz += r.z * r.z;
+    }
+
+    // The normal is the eigenvector of the covariance matrix with the smallest eigenvalue.
+    // It is found by assuming one of its components is 1 and solving for the other two,
+    // picking the component that gives the best conditioned system.
+    let det_x = yy * zz - yz * yz;
+    let det_y = xx * zz - xz * xz;
+    let det_z = xx * yy - xy * xy;
+    let det_max = det_x.max(det_y).max(det_z);
+    if det_max <= 0.0 {
+        return Vector3::zero();
+    }
+    let normal = if det_max == det_x {
+        vec3(det_x, xz * yz - xy * zz, xy * yz - xz * yy)
+    } else if det_max == det_y {
+        vec3(xz * yz - xy * zz, det_y, xy * xz - yz * xx)
+    } else {
+        vec3(xy * yz - xz * yy, xy * xz - yz * xx, det_z)
+    };
+    normal.normalize()
+}
+
+///
+/// Computes the convex hull using the incremental algorithm and returns the triangles oriented counter clockwise seen from the outside.
+/// Returns no triangles if the points do not span a volume.
+///
+fn convex_hull(positions: &[Vector3<f64>]) -> Vec<[usize; 3]> {
+    if positions.len() < 4 {
+        return Vec::new();
+    }
+    let mut min = positions[0];
+    let mut max = positions[0];
+    for p in positions {
+        min = vec3(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+        max = vec3(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+    }
+    let epsilon = 1.0e-10 * (max - min).magnitude().max(f64::MIN_POSITIVE);
+    let farthest = |distance: &dyn Fn(Vector3<f64>) -> f64| {
+        (0..positions.len())
+            .max_by(|a, b| distance(positions[*a]).total_cmp(&distance(positions[*b])))
+            .unwrap()
+    };
+
+    // Find an initial tetrahedron
+    let i0 = 0;
+    let p0 = positions[i0];
+    let i1 = farthest(&|p| p.distance(p0));
+    let p1 = positions[i1];
+    if p1.distance(p0) <= epsilon {
+        return Vec::new();
+    }
+    let i2 = farthest(&|p| (p - p0).cross(p1 - p0).magnitude() / p1.distance(p0));
+    let p2 = positions[i2];
+    let normal = (p1 - p0).cross(p2 - p0);
+    if normal.magnitude() / p1.distance(p0) <= epsilon {
+        return Vec::new();
+    }
+    let normal = normal.normalize();
+    let i3 = farthest(&|p| normal.dot(p - p0).abs());
+    if normal.dot(positions[i3] - p0).abs() <= epsilon {
+        return Vec::new();
+    }
+    let mut triangles = if normal.dot(positions[i3] - p0) < 0.0 {
+        vec![[i0, i1, i2], [i0, i3, i1], [i1, i3, i2], [i2, i3, i0]]
+    } else {
+        vec![[i0, i2, i1], [i0, i1, i3], [i1, i2, i3], [i2, i0, i3]]
+    };
+
+    let is_visible = |triangle: &[usize; 3], p: Vector3<f64>| {
+        let [a, b, c] = triangle.map(|i| positions[i]);
+        let n = (b - a).cross(c - a);
+        n.dot(p - a) > epsilon * n.magnitude()
+    };
+    for (i, p) in positions.iter().enumerate() {
+        if i == i0 || i == i1 || i == i2 || i == i3 {
+            continue;
+        }
+        let (visible, hidden): (Vec<_>, Vec<_>) =
+            triangles.into_iter().partition(|t| is_visible(t, *p));
+        triangles = hidden;
+        if visible.is_empty() {
+            continue;
+        }
+        // The horizon consists of the edges of the visible triangles which are not shared with another visible triangle
+        let edges = visible
+            .iter()
+            .flat_map(|t| [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])])
+            .collect::<std::collections::HashSet<_>>();
+        for &(a, b) in edges.iter() {
+            if !edges.contains(&(b, a)) {
+                triangles.push([a, b, i]);
+            }
+        }
+    }
+    triangles
+}
+
+struct BallPivoting<'a> {
+    positions: &'a [Vector3<f64>],
+    normals: &'a [Vector3<f64>],
+    grid: &'a PointGrid,
+    radius: f64,
+    used: Vec<bool>,
+    edges: std::collections::HashSet<(usize, usize)>,
+    indices: Vec<u32>,
+}
+
+impl BallPivoting<'_> {
+    fn run(mut self) -> Vec<u32> {
+        for i in 0..self.positions.len() {
+            if self.used[i] {
+                continue;
+            }
+            if let Some((triangle, center)) = self.find_seed(i) {
+                let mut front = std::collections::VecDeque::new();
+                self.add_triangle(triangle, center, &mut front);
+                while let Some((a, b, center)) = front.pop_front() {
+                    if self.edges.contains(&(b, a)) {
+                        continue;
+                    }
+                    if let Some((k, center)) = self.pivot(a, b, center) {
+                        self.add_triangle([b, a, k], center, &mut front);
+                    }
+                }
+            }
+        }
+        self.indices
+    }
+
+    fn find_seed(&self, i: usize) -> Option<([usize; 3], Vector3<f64>)> {
+        let neighbours =
+            self.grid
+                .within_radius(self.positions, self.positions[i], 2.0 * self.radius);
+        for (n, j) in neighbours.iter().enumerate() {
+            for k in neighbours.iter().skip(n + 1) {
+                let (j, k) = (*j, *k);
+                if j == i || k == i || self.used[j] || self.used[k] {
+                    continue;
+                }
+                let triangle = if self.is_oriented([i, j, k]) {
+                    [i, j, k]
+                } else {
+                    [i, k, j]
+                };
+                if let Some(center) = self.ball_center(triangle) {
+                    if self.is_empty(center, &triangle) {
+                        return Some((triangle, center));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    ///
+    /// Rolls the ball around the edge from `a` to `b` starting at `center` and returns the first point it touches.
+    ///
+    fn pivot(&self, a: usize, b: usize, center: Vector3<f64>) -> Option<(usize, Vector3<f64>)> {
+        let (pa, pb) = (self.positions[a], self.positions[b]);
+        let midpoint = 0.5 * (pa + pb);
+        // The triangle is oriented counter clockwise, so the ball rolls in the negative direction around the edge.
+        let axis = (pa - pb).normalize();
+        let start = center - midpoint;
+        let mut best = None;
+        let mut best_angle = f64::MAX;
+        for k in self
+            .grid
+            .within_radius(self.positions, midpoint, 2.0 * self.radius)
+        {
+            if k == a || k == b {
+                continue;
+            }
+            let triangle = [b, a, k];
+            if !self.is_oriented(triangle)
+                || self.edges.contains(&(a, k))
+                || self.edges.contains(&(k, b))
+            {
+                continue;
+            }
+            if let Some(new_center) = self.ball_center(triangle) {
+                let end = new_center - midpoint;
+                let mut angle = axis.cross(start).dot(end).atan2(start.dot(end));
+                if angle <= 1.0e-9 {
+                    angle += 2.0 * std::f64::consts::PI;
+                }
+                if angle < best_angle && self.is_empty(new_center, &triangle) {
+                    best_angle = angle;
+                    best = Some((k, new_center));
+                }
+            }
+        }
+        best
+    }
+
+    fn add_triangle(
+        &mut self,
+        triangle: [usize; 3],
+        center: Vector3<f64>,
+        front: &mut std::collections::VecDeque<(usize, usize, Vector3<f64>)>,
+    ) {
+        for i in 0..3 {
+            let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+            self.used[a] = true;
+            self.edges.insert((a, b));
+            if !self.edges.contains(&(b, a)) {
+                front.push_back((a, b, center));
+            }
+            self.indices.push(a as u32);
+        }
+    }
+
+    fn is_oriented(&self, triangle: [usize; 3]) -> bool {
+        let [a, b, c] = triangle.map(|i| self.positions[i]);
+        let normal =
+            self.normals[triangle[0]] + self.normals[triangle[1]] + self.normals[triangle[2]];
+        (b - a).cross(c - a).dot(normal) > 0.0
+    }
+
+    ///
+    /// Returns the center of the ball touching the three points on the side the triangle is facing or
+    /// `None` if the points are too far apart for the ball to touch all of them.
+    ///
+    fn ball_center(&self, triangle: [usize; 3]) -> Option<Vector3<f64>> {
+        let [a, b, c] = triangle.map(|i| self.positions[i]);
+        let (ab, ac) = (b - a, c - a);
+        let normal = ab.cross(ac);
+        let length2 = normal.magnitude2();
+        if length2 < 1.0e-18 {
+            return None;
+        }
+        let circumcenter = a
+            + (normal.cross(ab) * ac.magnitude2() + ac.cross(normal) * ab.magnitude2())
+                / (2.0 * length2);
+        let height2 = self.radius * self.radius - circumcenter.distance2(a);
+        if height2 < 0.0 {
+            return None;
+        }
+        Some(circumcenter + normal.normalize() * height2.sqrt())
+    }
+
+    fn is_empty(&self, center: Vector3<f64>, triangle: &[usize; 3]) -> bool {
+        self.grid
+            .within_radius(self.positions, center, self.radius * (1.0 - 1.0e-6))
+            .iter()
+            .all(|i| triangle.contains(i))
+    }
+}
+
+///
+/// A uniform grid which speeds up finding the points close to a given position.
+///
+struct PointGrid {
+    cell_size: f64,
+    cells: std::collections::HashMap<(i64, i64, i64), Vec<usize>>,
+    max_ring: i64,
+}
+
+impl PointGrid {
+    ///
+    /// Creates a grid where each cell contains roughly `points_per_cell` points, assuming the points are evenly spread over a surface.
+    ///
+    fn new(positions: &[Vector3<f64>], points_per_cell: usize) -> Self {
+        let mut min = vec3(f64::MAX, f64::MAX, f64::MAX);
+        let mut max = vec3(f64::MIN, f64::MIN, f64::MIN);
+        for p in positions {
+            min = vec3(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+            max = vec3(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+        }
+        let size = max - min;
+        let extent = size.x.max(size.y).max(size.z);
+        let cell_size = if positions.len() > 1 && extent > 0.0 {
+            let area = (size.x * size.y + size.y * size.z + size.z * size.x).max(extent * extent);
+            (area * points_per_cell.max(1) as f64 / positions.len() as f64)
+                .sqrt()
+                .min(extent)
+        } else {
+            1.0
+        };
+        let mut grid = Self {
+            cell_size,
+            cells: std::collections::HashMap::new(),
+            max_ring: (extent / cell_size).ceil() as i64 + 1,
+        };
+        for (i, p) in positions.iter().enumerate() {
+            grid.cells.entry(grid.cell(*p)).or_default().push(i);
+        }
+        grid
+    }
+
+    fn cell(&self, p: Vector3<f64>) -> (i64, i64, i64) {
+        (
+            (p.x / self.cell_size).floor() as i64,
+            (p.y / self.cell_size).floor() as i64,
+            (p.z / self.cell_size).floor() as i64,
+        )
+    }
+
+    ///
+    /// Returns the indices of the points within the distance `radius` from `p`.
+    ///
+    fn within_radius(
+        &self,
+        positions: &[Vector3<f64>],
+        p: Vector3<f64>,
+        radius: f64,
+    ) -> Vec<usize> {
+        let center = self.cell(p);
+        let rings = (radius / self.cell_size).ceil() as i64;
+        let mut result = Vec::new();
+        for x in -rings..=rings {
+            for y in -rings..=rings {
+                for z in -rings..=rings {
+                    if let Some(cell) = self.cells.get(&(center.0 + x, center.1 + y, center.2 + z))
+                    {
+                        result.extend(
+                            cell.iter()
+                                .copied()
+                                .filter(|i| positions[*i].distance2(p) <= radius * radius),
+                        );
+                    }
+                }
+            }
+        }
+        result.sort_by(|a, b| {
+            positions[*a]
+                .distance2(p)
+                .total_cmp(&positions[*b].distance2(p))
+        });
+        result
+    }
+
+    ///
+    /// Returns the indices of the `k` points closest to `p` sorted by distance.
+    ///
+    fn k_nearest(&self, positions: &[Vector3<f64>], p: Vector3<f64>, k: usize) -> Vec<usize> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let center = self.cell(p);
+        let mut candidates = Vec::new();
+        for ring in 0..=self.max_ring {
+            for x in -ring..=ring {
+                for y in -ring..=ring {
+                    for z in -ring..=ring {
+                        if x.abs().max(y.abs()).max(z.abs()) != ring {
+                            continue;
+                        }
+                        if let Some(cell) =
+                            self.cells.get(&(center.0 + x, center.1 + y, center.2 + z))
+                        {
+                            candidates
+                                .extend(cell.iter().map(|i| (positions[*i].distance2(p), *i)));
+                        }
+                    }
+                }
+            }
+            // Points outside the visited rings are at least this far away.
+            let min_unvisited = ring as f64 * self.cell_size;
+            if candidates.len() >= k {
+                candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+                if candidates[k - 1].0 <= min_unvisited * min_unvisited {
+                    break;
+                }
+            }
+        }
+        candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+        candidates.into_iter().take(k).map(|(_, i)| i).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn voxel_downsample() {
+        let point_cloud = PointCloud {
+            positions: Positions::F32(vec![
+                vec3(0.1, 0.1, 0.1),
+                vec3(5.0, 5.0, 5.0),
+                vec3(0.3, 0.5, 0.7),
+                vec3(-0.5, 0.5, 0.5),
+            ]),
+            colors: Some(vec![
+                Srgba::new_opaque(0, 0, 0),
+                Srgba::new_opaque(10, 10, 10),
+                Srgba::new_opaque(100, 50, 20),
+                Srgba::new_opaque(255, 255, 255),
+            ]),
+            normals: None,
+        };
+        let downsampled = point_cloud.voxel_downsample(1.0);
+        assert_eq!(
+            downsampled.positions.to_f32(),
+            vec![
+                vec3(0.2, 0.3, 0.4),
+                vec3(5.0, 5.0, 5.0),
+                vec3(-0.5, 0.5, 0.5)
+            ]
+        );
+        assert_eq!(
+            downsampled.colors.unwrap(),
+            vec![
+                Srgba::new_opaque(50, 25, 10),
+                Srgba::new_opaque(10, 10, 10),
+                Srgba::new_opaque(255, 255, 255)
+            ]
+        );
+
+        for voxel_size in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            assert!(point_cloud.voxel_downsample(voxel_size) == point_cloud);
+        }
+    }
+
+    #[test]
+    pub fn estimate_normals() {
+        let mut positions = Vec::new();
+        for x in 0..10 {
+            for y in 0..10 {
+                positions.push(vec3(x as f32, y as f32, 0.5 * x as f32));
+            }
+        }
+        let mut point_cloud = PointCloud {
+            positions: Positions::F32(positions),
+            ..Default::default()
+        };
+        point_cloud.estimate_normals(8, Some(vec3(0.0, 0.0, 100.0)));
+        let expected = vec3(-0.5, 0.0, 1.0).normalize();
+        for normal in point_cloud.normals.unwrap() {
+            assert!(normal.distance(expected) < 0.0001);
+        }
+    }
+
+    #[test]
+    pub fn to_mesh_plane() {
+        let mut positions = Vec::new();
+        for x in 0..6 {
+            for y in 0..6 {
+                positions.push(vec3(x as f32, y as f32, 0.0));
+            }
+        }
+        let point_cloud = PointCloud {
+            normals: Some(vec![vec3(0.0, 0.0, 1.0); positions.len()]),
+            positions: Positions::F32(positions),
+            ..Default::default()
+        };
+        let mesh = point_cloud.to_mesh().unwrap();
+        assert_eq!(mesh.triangle_count(), 50);
+        mesh.for_each_triangle(|a, b, c| {
+            let p = mesh.positions.to_f32();
+            assert!((p[b] - p[a]).cross(p[c] - p[a]).z > 0.0);
+        });
+    }
+
+    #[test]
+    pub fn to_mesh_sphere() {
+        // Evenly distributed points on a sphere
+        let count = 500;
+        let golden_angle = std::f32::consts::PI * (3.0 - 5.0f32.sqrt());
+        let normals = (0..count)
+            .map(|i| {
+                let y = 1.0 - 2.0 * (i as f32 + 0.5) / count as f32;
+                let r = (1.0 - y * y).sqrt();
+                let theta = golden_angle * i as f32;
+                vec3(r * theta.cos(), y, r * theta.sin())
+            })
+            .collect::<Vec<_>>();
+        let point_cloud = PointCloud {
+            positions: Positions::F32(normals.clone()),
+            normals: Some(normals),
+            ..Default::default()
+        };
+        let mesh = point_cloud.to_mesh().unwrap();
+        let mut edges = std::collections::HashMap::new();
+        let indices = mesh.indices.to_u32().unwrap();
+        for t in indices.chunks(3) {
+            for i in 0..3 {
+                let (a, b) = (t[i], t[(i + 1) % 3]);
+                *edges.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+            }
+        }
+        // A closed surface has two triangles per edge
+        assert!(edges.values().all(|c| *c == 2));
+        assert_eq!(mesh.triangle_count(), 2 * count - 4);
+    }
+
+    #[test]
+    pub fn to_mesh_without_normals() {
+        assert!(matches!(
+            PointCloud::cube().to_mesh(),
+            Err(Error::PointCloudMissingNormals)
+        ));
+    }
+
+    #[test]
+    pub fn convex_hull() {
+        let mut positions = PointCloud::cube().positions.to_f32();
+        positions.extend([
+            vec3(0.0, 0.0, 0.0),
+            vec3(0.5, -0.5, 0.9),
+            vec3(1.0, 0.0, 0.0),
+        ]);
+        let point_cloud = PointCloud {
+            positions: Positions::F32(positions),
+            ..Default::default()
+        };
+        let mesh = point_cloud.convex_hull();
+        assert_eq!(mesh.positions.len(), 8);
+        assert_eq!(mesh.triangle_count(), 12);
+        let positions = mesh.positions.to_f32();
+        let normals = mesh.normals.as_ref().unwrap();
+        for i in 0..positions.len() {
+            assert!(normals[i].dot(positions[i]) > 0.0);
+        }
+        mesh.for_each_triangle(|a, b, c| {
+            let normal = (positions[b] - positions[a]).cross(positions[c] - positions[a]);
+            assert!(normal.dot(positions[a]) > 0.0);
+        });
+
+        let mut plane = PointCloud {
+            positions: Positions::F32(vec![
+                vec3(0.0, 0.0, 0.0),
+                vec3(1.0, 0.0, 0.0),
+                vec3(0.0, 1.0, 0.0),
+                vec3(1.0, 1.0, 0.0),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(plane.convex_hull().triangle_count(), 0);
+        plane.transform(Mat4::from_angle_x(degrees(30.0))).unwrap();
+        assert_eq!(plane.convex_hull().triangle_count(), 0);
+    }
 }
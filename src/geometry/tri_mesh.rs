@@ -1,9 +1,9 @@
-use crate::{prelude::*, Error, Indices, Positions, Result};
+use crate::{prelude::*, Error, Indices, PointCloud, Positions, Result, Texture2D, TextureData};
 
 ///
 /// A CPU-side version of a triangle mesh.
 ///
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TriMesh {
     /// The positions of the vertices.
@@ -48,7 +48,126 @@ impl std::fmt::Debug for TriMesh {
     }
 }
 
+///
+/// A builder for a [TriMesh], which is an alternative to constructing the [Positions], [Indices] and vertex attributes directly.
+/// The attributes that are not specified are `None` and if no indices are specified, three contiguous positions defines a triangle.
+///
+/// ```
+/// # use three_d_asset::*;
+/// let mesh = TriMesh::builder()
+///     .positions(vec![vec3(0.0, 0.0, 0.0), vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0)])
+///     .indices(vec![0, 1, 2])
+///     .uvs(vec![vec2(0.0, 0.0), vec2(1.0, 0.0), vec2(0.0, 1.0)])
+///     .build()
+///     .unwrap();
+/// assert_eq!(mesh.indices, Indices::U8(vec![0, 1, 2]));
+/// ```
+///
+#[derive(Clone, Debug, Default)]
+pub struct TriMeshBuilder {
+    mesh: TriMesh,
+}
+
+impl TriMeshBuilder {
+    ///
+    /// Creates a new builder for a mesh without any vertices.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Sets the positions of the vertices.
+    ///
+    pub fn positions(mut self, positions: Vec<Vec3>) -> Self {
+        self.mesh.positions = Positions::F32(positions);
+        self
+    }
+
+    ///
+    /// Sets the positions of the vertices in double precision.
+    ///
+    pub fn positions_f64(mut self, positions: Vec<Vector3<f64>>) -> Self {
+        self.mesh.positions = Positions::F64(positions);
+        self
+    }
+
+    ///
+    /// Sets the indices, three contiguous indices defines a triangle.
+    /// The indices are stored using the smallest data type that can represent all of them.
+    ///
+    pub fn indices(mut self, indices: Vec<u32>) -> Self {
+        self.mesh.indices = Indices::U32(indices);
+        self.mesh.indices.shrink();
+        self
+    }
+
+    ///
+    /// Sets the normals of the vertices.
+    ///
+    pub fn normals(mut self, normals: Vec<Vec3>) -> Self {
+        self.mesh.normals = Some(normals);
+        self
+    }
+
+    ///
+    /// Sets the tangents of the vertices, see [TriMesh::tangents].
+    ///
+    pub fn tangents(mut self, tangents: Vec<Vec4>) -> Self {
+        self.mesh.tangents = Some(tangents);
+        self
+    }
+
+    ///
+    /// Sets the uv coordinates of the vertices.
+    ///
+    pub fn uvs(mut self, uvs: Vec<Vec2>) -> Self {
+        self.mesh.uvs = Some(uvs);
+        self
+    }
+
+    ///
+    /// Sets the colors of the vertices.
+    ///
+    pub fn colors(mut self, colors: Vec<Srgba>) -> Self {
+        self.mesh.colors = Some(colors);
+        self
+    }
+
+    ///
+    /// Constructs the [TriMesh].
+    /// Returns an error if the number of values of a vertex attribute is different from the number of positions or if the mesh is not valid, see [TriMesh::validate].
+    ///
+    pub fn build(self) -> Result<TriMesh> {
+        let mesh = self.mesh;
+        let vertex_count = mesh.vertex_count();
+        for (length, name) in [
+            (mesh.normals.as_ref().map(|b| b.len()), "normal"),
+            (mesh.tangents.as_ref().map(|b| b.len()), "tangent"),
+            (mesh.uvs.as_ref().map(|b| b.len()), "uv coordinate"),
+            (mesh.colors.as_ref().map(|b| b.len()), "color"),
+        ] {
+            if let Some(length) = length.filter(|l| *l != vertex_count) {
+                Err(Error::InvalidBufferLength(
+                    name.to_string(),
+                    vertex_count,
+                    length,
+                ))?;
+            }
+        }
+        mesh.validate()?;
+        Ok(mesh)
+    }
+}
+
 impl TriMesh {
+    ///
+    /// Returns a [TriMeshBuilder] for constructing a mesh.
+    ///
+    pub fn builder() -> TriMeshBuilder {
+        TriMeshBuilder::new()
+    }
+
     /// Returns the number of vertices in this mesh.
     pub fn vertex_count(&self) -> usize {
         self.positions.len()
@@ -65,6 +184,9 @@ impl TriMesh {
     ///
     /// Transforms the mesh by the given transformation.
     ///
+    /// If the transformation mirrors the mesh, ie. has a negative determinant, the winding order of the triangles is reversed
+    /// and the sign of the tangents flipped, so that the front faces still face outward.
+    ///
     pub fn transform(&mut self, transform: Mat4) -> Result<()> {
         match self.positions {
             Positions::F32(ref mut positions) => {
@@ -81,27 +203,62 @@ impl TriMesh {
         };
 
         if self.normals.is_some() || self.tangents.is_some() {
-            let normal_transform = transform
-                .invert()
-                .ok_or(Error::FailedInvertingTransformationMatrix)?
-                .transpose();
+            let normal_transform = super::normal_transformation(transform)?;
 
             if let Some(ref mut normals) = self.normals {
                 for n in normals.iter_mut() {
-                    *n = (normal_transform * n.extend(1.0)).truncate();
+                    *n = normal_transform * *n;
+                }
+            }
+            if let Some(ref mut tangents) = self.tangents {
+                for t in tangents.iter_mut() {
+                    *t = (normal_transform * t.truncate()).extend(t.w);
                 }
             }
+        }
+
+        if transform.determinant() < 0.0 {
+            self.reverse_winding();
             if let Some(ref mut tangents) = self.tangents {
                 for t in tangents.iter_mut() {
-                    *t = (normal_transform * t.truncate().extend(1.0))
-                        .truncate()
-                        .extend(t.w);
+                    t.w = -t.w;
                 }
             }
         }
         Ok(())
     }
 
+    fn reverse_winding(&mut self) {
+        fn swap<T>(values: &mut [T]) {
+            for triangle in values.chunks_exact_mut(3) {
+                triangle.swap(1, 2);
+            }
+        }
+        match self.indices {
+            Indices::U8(ref mut indices) => swap(indices),
+            Indices::U16(ref mut indices) => swap(indices),
+            Indices::U32(ref mut indices) => swap(indices),
+            Indices::None => {
+                match self.positions {
+                    Positions::F32(ref mut positions) => swap(positions),
+                    Positions::F64(ref mut positions) => swap(positions),
+                }
+                if let Some(ref mut normals) = self.normals {
+                    swap(normals);
+                }
+                if let Some(ref mut tangents) = self.tangents {
+                    swap(tangents);
+                }
+                if let Some(ref mut uvs) = self.uvs {
+                    swap(uvs);
+                }
+                if let Some(ref mut colors) = self.colors {
+                    swap(colors);
+                }
+            }
+        }
+    }
+
     ///
     /// Returns a square mesh spanning the xy-plane with positions in the range `[-1..1]` in the x and y axes.
     ///
@@ -142,6 +299,68 @@ impl TriMesh {
         }
     }
 
+    ///
+    /// Returns a terrain mesh created from the given height map, where the first channel of each texel specifies the height at that point.
+    /// The values are normalized to the range `[0, 1]` for unsigned byte textures and used directly for floating point textures.
+    /// The mesh spans the xz-plane with center in `(0, 0, 0)`, size `width_scale` in the x-direction and `depth_scale` in the z-direction,
+    /// and the heights are scaled by `height_scale` in the y-direction.
+    /// The first row of the texture is placed at the lowest z-value and the uv coordinates map each vertex to the corresponding texel.
+    ///
+    pub fn from_heightmap(
+        height: &Texture2D,
+        width_scale: f32,
+        depth_scale: f32,
+        height_scale: f32,
+    ) -> Self {
+        let heights: Vec<f32> = match &height.data {
+            TextureData::RU8(data) => data.iter().map(|v| *v as f32 / 255.0).collect(),
+            TextureData::RgU8(data) => data.iter().map(|v| v[0] as f32 / 255.0).collect(),
+            TextureData::RgbU8(data) => data.iter().map(|v| v[0] as f32 / 255.0).collect(),
+            TextureData::RgbaU8(data) => data.iter().map(|v| v[0] as f32 / 255.0).collect(),
+            TextureData::RF16(data) => data.iter().map(|v| v.to_f32()).collect(),
+            TextureData::RgF16(data) => data.iter().map(|v| v[0].to_f32()).collect(),
+            TextureData::RgbF16(data) => data.iter().map(|v| v[0].to_f32()).collect(),
+            TextureData::RgbaF16(data) => data.iter().map(|v| v[0].to_f32()).collect(),
+            TextureData::RF32(data) => data.clone(),
+            TextureData::RgF32(data) => data.iter().map(|v| v[0]).collect(),
+            TextureData::RgbF32(data) => data.iter().map(|v| v[0]).collect(),
+            TextureData::RgbaF32(data) => data.iter().map(|v| v[0]).collect(),
+        };
+        let width = height.width as usize;
+        let depth = height.height as usize;
+        let mut positions = Vec::with_capacity(width * depth);
+        let mut uvs = Vec::with_capacity(width * depth);
+        for row in 0..depth {
+            for column in 0..width {
+                let u = column as f32 / (width - 1).max(1) as f32;
+                let v = row as f32 / (depth - 1).max(1) as f32;
+                positions.push(vec3(
+                    (u - 0.5) * width_scale,
+                    heights[row * width + column] * height_scale,
+                    (v - 0.5) * depth_scale,
+                ));
+                uvs.push(vec2(u, v));
+            }
+        }
+        let mut indices = Vec::new();
+        for row in 0..depth.saturating_sub(1) {
+            for column in 0..width.saturating_sub(1) {
+                let i = (row * width + column) as u32;
+                let right = i + 1;
+                let below = i + width as u32;
+                indices.extend([i, below, right, right, below, below + 1]);
+            }
+        }
+        let mut mesh = Self {
+            positions: Positions::F32(positions),
+            indices: Indices::U32(indices),
+            uvs: Some(uvs),
+            ..Default::default()
+        };
+        mesh.compute_normals();
+        mesh
+    }
+
     ///
     /// Returns a circle mesh spanning the xy-plane with radius 1 and center in `(0, 0, 0)`.
     ///
@@ -330,7 +549,7 @@ impl TriMesh {
             ..Default::default()
         };
         mesh.compute_normals();
-        mesh.compute_tangents();
+        mesh.compute_tangents().unwrap();
         mesh
     }
 
@@ -451,9 +670,9 @@ impl TriMesh {
     /// It will override the current normals if they already exist.
     ///
     pub fn compute_normals(&mut self) {
-        let mut normals = vec![Vec3::new(0.0, 0.0, 0.0); self.positions.len()];
-        self.for_each_triangle(|i0, i1, i2| {
-            let normal = match self.positions {
+        let positions = &self.positions;
+        let mut normals = self.accumulate_triangles(|i0, i1, i2| {
+            let normal = match positions {
                 Positions::F32(ref positions) => {
                     let p0 = positions[i0];
                     let p1 = positions[i1];
@@ -468,30 +687,430 @@ impl TriMesh {
                     Vec3::new(n.x as f32, n.y as f32, n.z as f32)
                 }
             };
-            normals[i0] += normal;
-            normals[i1] += normal;
-            normals[i2] += normal;
+            Some([normal])
         });
 
-        for n in normals.iter_mut() {
+        for [n] in normals.iter_mut() {
             *n = n.normalize();
         }
-        self.normals = Some(normals);
+        self.normals = Some(normals.into_iter().map(|[n]| n).collect());
+    }
+
+    ///
+    /// Estimates the mean curvature at each vertex from its one-ring of neighbouring triangles using the cotangent Laplacian,
+    /// ie. the mean curvature is half the length of `1 / (2A) * Σ (cot α + cot β) * (p_j - p_i)`
+    /// where `α` and `β` are the angles opposite the edge to the neighbour `p_j` and `A` is the mixed Voronoi area around the vertex as described by Meyer et al.
+    /// in "Discrete Differential-Geometry Operators for Triangulated 2-Manifolds".
+    /// The curvature is positive where the surface bends away from the normal given by the winding of the triangles, for example on a sphere with outward facing triangles,
+    /// and negative where it bends towards it. A sphere with radius `r` has a mean curvature of `1 / r`.
+    ///
+    /// Returns one value for each vertex. Vertices on the boundary of the mesh, ie. vertices on an edge used by only one triangle,
+    /// and vertices that are not part of any triangle with an area have a curvature of zero, since their one-ring is incomplete.
+    /// Note that this means that all vertices of a mesh where the triangles do not share vertices have a curvature of zero.
+    ///
+    pub fn compute_curvature(&self) -> Vec<f32> {
+        let positions = self.positions.to_f64();
+        let mut laplacians = vec![Vector3::new(0.0, 0.0, 0.0); positions.len()];
+        let mut normals = vec![Vector3::new(0.0, 0.0, 0.0); positions.len()];
+        let mut areas = vec![0.0; positions.len()];
+        self.for_each_triangle(|i0, i1, i2| {
+            let triangle = [i0, i1, i2];
+            let normal = (positions[i1] - positions[i0]).cross(positions[i2] - positions[i0]);
+            let area = 0.5 * normal.magnitude();
+            if area <= 0.0 {
+                return;
+            }
+            // The cotangent of the angle at each corner
+            let cot = [0, 1, 2].map(|k| {
+                let a = positions[triangle[(k + 1) % 3]] - positions[triangle[k]];
+                let b = positions[triangle[(k + 2) % 3]] - positions[triangle[k]];
+                a.dot(b) / a.cross(b).magnitude()
+            });
+            let is_obtuse = cot.iter().any(|c| *c < 0.0);
+            for k in 0..3 {
+                let (i, j, l) = (triangle[k], triangle[(k + 1) % 3], triangle[(k + 2) % 3]);
+                let (cot_j, cot_l) = (cot[(k + 1) % 3], cot[(k + 2) % 3]);
+                normals[i] += normal;
+                laplacians[i] +=
+                    (positions[j] - positions[i]) * cot_l + (positions[l] - positions[i]) * cot_j;
+                areas[i] += if cot[k] < 0.0 {
+                    area / 2.0
+                } else if is_obtuse {
+                    area / 4.0
+                } else {
+                    ((positions[j] - positions[i]).magnitude2() * cot_l
+                        + (positions[l] - positions[i]).magnitude2() * cot_j)
+                        / 8.0
+                };
+            }
+        });
+        let mut is_boundary = vec![false; positions.len()];
+        for (i, j) in self.boundary_edges() {
+            is_boundary[i as usize] = true;
+            is_boundary[j as usize] = true;
+        }
+        (0..positions.len())
+            .map(|i| {
+                if is_boundary[i] || areas[i] <= 0.0 {
+                    0.0
+                } else {
+                    let laplacian = laplacians[i] / (2.0 * areas[i]);
+                    (-0.5 * laplacian.dot(normals[i].normalize())) as f32
+                }
+            })
+            .collect()
+    }
+
+    ///
+    /// Returns the edges which are used by only one triangle, ie. the edges on the boundary of the holes in the mesh.
+    /// Each edge is given by the indices of its two vertices in the order given by the winding of the triangle using the edge,
+    /// and the edges are sorted by these indices.
+    /// Note that edges are identified by vertex indices, so triangles that do not share vertices, for example because of seams in the uv coordinates,
+    /// are not connected even though the positions are the same.
+    ///
+    pub fn boundary_edges(&self) -> Vec<(u32, u32)> {
+        let mut edges = self
+            .count_edges()
+            .into_values()
+            .filter(|(count, _)| *count == 1)
+            .map(|(_, (i, j))| (i as u32, j as u32))
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+        edges
+    }
+
+    ///
+    /// Returns whether or not the mesh is watertight, ie. whether every edge is used by exactly two triangles.
+    /// This means the mesh does not have any holes, see [TriMesh::boundary_edges], and that no edge is shared by more than two triangles.
+    /// A mesh without any triangles is not watertight.
+    ///
+    pub fn is_watertight(&self) -> bool {
+        let edges = self.count_edges();
+        !edges.is_empty() && edges.values().all(|(count, _)| *count == 2)
+    }
+
+    ///
+    /// Fills the holes in the mesh which are bounded by a closed loop of at most `max_edges` boundary edges, see [TriMesh::boundary_edges].
+    /// A hole bounded by three edges is filled with a single triangle, larger holes are filled with a fan of triangles around a new vertex
+    /// at the centroid of the loop. The new triangles have the same winding as the triangles next to the hole.
+    /// The uv coordinates, colors and tangents of a new vertex are the average of the loop and its normal is computed from the new triangles.
+    ///
+    /// Loops where a vertex is shared by several holes are ambiguous and are not filled.
+    /// Also, the edges of a triangle which does not share any edges with other triangles are not considered a hole,
+    /// so a mesh without indices, like [TriMesh::cube], is left as it is.
+    /// Note that the outer border of an open surface is also a closed loop of boundary edges and is therefore filled like any other hole,
+    /// for example [TriMesh::square] is turned into a closed surface by `fill_holes(4)` which adds a fan of four triangles facing the opposite way.
+    /// Returns the number of filled holes.
+    ///
+    pub fn fill_holes(&mut self, max_edges: usize) -> usize {
+        let boundary_edges = self.boundary_edges();
+        let triangles = (0..self.triangle_count())
+            .map(|t| {
+                let mut triangle = self.triangle(t).map(|i| i as u32);
+                triangle.sort_unstable();
+                triangle
+            })
+            .collect::<std::collections::HashSet<_>>();
+        let mut next = std::collections::HashMap::new();
+        let mut is_ambiguous = std::collections::HashSet::new();
+        for (i, j) in boundary_edges.iter() {
+            if next.insert(*i, *j).is_some() {
+                is_ambiguous.insert(*i);
+            }
+        }
+        let mut is_visited = std::collections::HashSet::new();
+        let mut holes = Vec::new();
+        for (start, _) in boundary_edges {
+            let mut hole = vec![start];
+            let mut current = start;
+            let is_closed = loop {
+                is_visited.insert(current);
+                if is_ambiguous.contains(&current) {
+                    break false;
+                }
+                match next.get(&current) {
+                    Some(j) if *j == start => break true,
+                    Some(j) if !is_visited.contains(j) => {
+                        hole.push(*j);
+                        current = *j;
+                    }
+                    _ => break false,
+                }
+            };
+            if is_closed && hole.len() <= max_edges {
+                let mut triangle = hole.clone();
+                triangle.sort_unstable();
+                if triangle.len() != 3 || !triangles.contains(&triangle[..]) {
+                    holes.push(hole);
+                }
+            }
+        }
+        if holes.is_empty() {
+            return 0;
+        }
+
+        let vertex_count = self.vertex_count();
+        let mut indices = self
+            .indices
+            .to_u32()
+            .unwrap_or_else(|| (0..vertex_count as u32).collect());
+        let mut positions = self.positions.to_f64();
+        for hole in holes.iter() {
+            if let [a, b, c] = hole[..] {
+                indices.extend([c, b, a]);
+                continue;
+            }
+            let center = positions.len() as u32;
+            let count = hole.len() as f64;
+            let centroid = hole
+                .iter()
+                .map(|i| positions[*i as usize])
+                .sum::<Vector3<f64>>()
+                / count;
+            positions.push(centroid);
+            let mut normal = Vector3::new(0.0, 0.0, 0.0);
+            for k in 0..hole.len() {
+                let (i, j) = (hole[k], hole[(k + 1) % hole.len()]);
+                indices.extend([j, i, center]);
+                normal +=
+                    (positions[i as usize] - centroid).cross(positions[j as usize] - centroid);
+            }
+            if let Some(normals) = self.normals.as_mut() {
+                normals.push(-normal.cast::<f32>().unwrap().normalize());
+            }
+            if let Some(uvs) = self.uvs.as_mut() {
+                let uv = hole.iter().map(|i| uvs[*i as usize]).sum::<Vec2>() / count as f32;
+                uvs.push(uv);
+            }
+            if let Some(colors) = self.colors.as_mut() {
+                let c = |f: fn(&Srgba) -> u8| {
+                    (hole
+                        .iter()
+                        .map(|i| f(&colors[*i as usize]) as f64)
+                        .sum::<f64>()
+                        / count)
+                        .round() as u8
+                };
+                let color = Srgba::new(c(|c| c.r), c(|c| c.g), c(|c| c.b), c(|c| c.a));
+                colors.push(color);
+            }
+            if let Some(tangents) = self.tangents.as_mut() {
+                let tangent = hole
+                    .iter()
+                    .map(|i| tangents[*i as usize].truncate())
+                    .sum::<Vec3>()
+                    .normalize()
+                    .extend(tangents[hole[0] as usize].w);
+                tangents.push(tangent);
+            }
+        }
+
+        self.positions = match self.positions {
+            Positions::F32(_) => Positions::F32(
+                positions
+                    .into_iter()
+                    .map(|p| p.cast::<f32>().unwrap())
+                    .collect(),
+            ),
+            Positions::F64(_) => Positions::F64(positions),
+        };
+        let max_index = self.positions.len().saturating_sub(1);
+        self.indices = match self.indices {
+            Indices::U8(_) if max_index <= u8::MAX as usize => {
+                indices_of_same_type(&self.indices, indices)
+            }
+            Indices::U16(_) if max_index <= u16::MAX as usize => {
+                indices_of_same_type(&self.indices, indices)
+            }
+            _ => Indices::U32(indices),
+        };
+        holes.len()
+    }
+
+    ///
+    /// Splits the mesh into its connected components, ie. sets of triangles which are connected through shared vertices.
+    /// The components are ordered by their first triangle and each component only contains the vertices used by its triangles, see [TriMesh::remove_unused_vertices].
+    /// Note that triangles are only connected if they share vertex indices, not if they just have the same positions.
+    ///
+    pub fn split_connected_components(&self) -> Vec<TriMesh> {
+        fn find(parents: &mut [usize], mut i: usize) -> usize {
+            while parents[i] != i {
+                parents[i] = parents[parents[i]];
+                i = parents[i];
+            }
+            i
+        }
+        let mut parents = (0..self.vertex_count()).collect::<Vec<_>>();
+        self.for_each_triangle(|i0, i1, i2| {
+            for (i, j) in [(i0, i1), (i1, i2)] {
+                let (root_i, root_j) = (find(&mut parents, i), find(&mut parents, j));
+                parents[root_i] = root_j;
+            }
+        });
+        let mut component_indices = std::collections::HashMap::new();
+        let mut components: Vec<Vec<[usize; 3]>> = Vec::new();
+        self.for_each_triangle(|i0, i1, i2| {
+            let root = find(&mut parents, i0);
+            let component = *component_indices.entry(root).or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+            components[component].push([i0, i1, i2]);
+        });
+        components
+            .iter()
+            .map(|triangles| self.extract_triangles(triangles))
+            .collect()
+    }
+
+    ///
+    /// Removes the vertices which are not used by any triangle and updates the indices accordingly.
+    /// The remaining vertices are ordered by their first use in the triangles.
+    ///
+    pub fn remove_unused_vertices(&mut self) {
+        let mut triangles = Vec::with_capacity(self.triangle_count());
+        self.for_each_triangle(|i0, i1, i2| triangles.push([i0, i1, i2]));
+        *self = self.extract_triangles(&triangles);
+    }
+
+    ///
+    /// Returns a mesh with the given triangles and only the vertices they use.
+    ///
+    fn extract_triangles(&self, triangles: &[[usize; 3]]) -> TriMesh {
+        let mut new_indices = std::collections::HashMap::new();
+        let mut vertices = Vec::new();
+        let mut indices = Vec::with_capacity(triangles.len() * 3);
+        for i in triangles.iter().flatten() {
+            indices.push(*new_indices.entry(*i).or_insert_with(|| {
+                vertices.push(*i);
+                vertices.len() as u32 - 1
+            }));
+        }
+        fn gather<T: Copy>(values: &[T], vertices: &[usize]) -> Vec<T> {
+            vertices.iter().map(|i| values[*i]).collect()
+        }
+        TriMesh {
+            positions: match &self.positions {
+                Positions::F32(positions) => Positions::F32(gather(positions, &vertices)),
+                Positions::F64(positions) => Positions::F64(gather(positions, &vertices)),
+            },
+            indices: indices_of_same_type(&self.indices, indices),
+            normals: self.normals.as_ref().map(|n| gather(n, &vertices)),
+            tangents: self.tangents.as_ref().map(|t| gather(t, &vertices)),
+            uvs: self.uvs.as_ref().map(|uvs| gather(uvs, &vertices)),
+            colors: self.colors.as_ref().map(|c| gather(c, &vertices)),
+        }
+    }
+
+    ///
+    /// Returns the number of triangles using each edge together with the edge as given by the first triangle using it.
+    /// The key is the indices of the two vertices of the edge with the smallest index first.
+    ///
+    fn count_edges(&self) -> std::collections::HashMap<(usize, usize), (usize, (usize, usize))> {
+        let mut edges = std::collections::HashMap::new();
+        self.for_each_triangle(|i0, i1, i2| {
+            for (i, j) in [(i0, i1), (i1, i2), (i2, i0)] {
+                edges.entry((i.min(j), i.max(j))).or_insert((0, (i, j))).0 += 1;
+            }
+        });
+        edges
+    }
+
+    ///
+    /// Generates uv coordinates by projecting the positions along the given axis onto the bounding box of the mesh,
+    /// which means the uv coordinates span `[0, 1]` in both directions.
+    /// The projection is seen from the positive side of the axis, with the y-axis as up direction for the x- and z-axis
+    /// and the negative z-axis as up direction for the y-axis. It will override the current uv coordinates if they already exist.
+    ///
+    pub fn generate_planar_uvs(&mut self, axis: Axis) {
+        let aabb = self.compute_aabb();
+        self.uvs = Some(
+            self.positions
+                .to_f32()
+                .into_iter()
+                .map(|p| planar_uv(p, &aabb, axis))
+                .collect(),
+        );
+    }
+
+    ///
+    /// Generates uv coordinates by projecting each vertex onto the side of the bounding box of the mesh that its normal is most aligned with,
+    /// see [TriMesh::generate_planar_uvs]. The normals are computed if the mesh does not have normals.
+    /// It will override the current uv coordinates if they already exist.
+    ///
+    /// **Note:** A vertex shared between triangles that face different sides of the box only gets one uv coordinate,
+    /// so split the vertices if that gives stretched textures.
+    ///
+    pub fn generate_box_uvs(&mut self) {
+        if self.normals.is_none() {
+            self.compute_normals();
+        }
+        let aabb = self.compute_aabb();
+        let normals = self.normals.as_ref().unwrap();
+        self.uvs = Some(
+            self.positions
+                .to_f32()
+                .into_iter()
+                .zip(normals)
+                .map(|(p, n)| {
+                    let axis = if n.x.abs() >= n.y.abs() && n.x.abs() >= n.z.abs() {
+                        Axis::X
+                    } else if n.y.abs() >= n.z.abs() {
+                        Axis::Y
+                    } else {
+                        Axis::Z
+                    };
+                    planar_uv(p, &aabb, axis)
+                })
+                .collect(),
+        );
+    }
+
+    ///
+    /// Generates uv coordinates by projecting the positions onto a sphere around the center of the bounding box of the mesh.
+    /// The u coordinate is the longitude around the y-axis and the v coordinate is the latitude, which is `0` at the top and `1` at the bottom.
+    /// It will override the current uv coordinates if they already exist.
+    ///
+    /// **Note:** Triangles crossing the seam where u goes from `1` to `0` interpolates across the entire texture.
+    ///
+    pub fn generate_spherical_uvs(&mut self) {
+        let center = self.compute_aabb().center();
+        self.uvs = Some(
+            self.positions
+                .to_f32()
+                .into_iter()
+                .map(|p| {
+                    let d = p - center;
+                    let length = d.magnitude();
+                    if length < f32::EPSILON {
+                        return vec2(0.5, 0.5);
+                    }
+                    let d = d / length;
+                    vec2(
+                        0.5 + d.z.atan2(d.x) / (2.0 * std::f32::consts::PI),
+                        d.y.clamp(-1.0, 1.0).acos() / std::f32::consts::PI,
+                    )
+                })
+                .collect(),
+        );
     }
 
     ///
     /// Computes the per vertex tangents and updates the tangents of the mesh.
     /// It will override the current tangents if they already exist.
+    /// Returns an error if the mesh does not have both normals and uv coordinates.
     ///
-    pub fn compute_tangents(&mut self) {
+    /// The tangent of a vertex is the average of the tangents of the triangles using that vertex.
+    /// Vertices on a uv seam must therefore be split into several vertices, one for each set of uv coordinates,
+    /// to avoid averaging the tangents across the seam.
+    ///
+    pub fn compute_tangents(&mut self) -> Result<()> {
         if self.normals.is_none() || self.uvs.is_none() {
-            panic!("mesh must have both normals and uv coordinates to be able to compute tangents");
+            Err(Error::MissingNormalsOrUvs)?;
         }
-        let mut tan1 = vec![Vec3::new(0.0, 0.0, 0.0); self.positions.len()];
-        let mut tan2 = vec![Vec3::new(0.0, 0.0, 0.0); self.positions.len()];
-
-        self.for_each_triangle(|i0, i1, i2| {
-            let (a, b, c) = match self.positions {
+        let (positions, uvs) = (&self.positions, self.uvs.as_ref().unwrap());
+        let tan = self.accumulate_triangles(|i0, i1, i2| {
+            let (a, b, c) = match positions {
                 Positions::F32(ref positions) => (positions[i0], positions[i1], positions[i2]),
                 Positions::F64(ref positions) => {
                     let (a, b, c) = (positions[i0], positions[i1], positions[i2]);
@@ -502,9 +1121,9 @@ impl TriMesh {
                     )
                 }
             };
-            let uva = self.uvs.as_ref().unwrap()[i0];
-            let uvb = self.uvs.as_ref().unwrap()[i1];
-            let uvc = self.uvs.as_ref().unwrap()[i2];
+            let uva = uvs[i0];
+            let uvb = uvs[i1];
+            let uvc = uvs[i2];
 
             let ba = b - a;
             let ca = c - a;
@@ -517,21 +1136,27 @@ impl TriMesh {
                 let r = 1.0 / d;
                 let sdir = (ba * uvca.y - ca * uvba.y) * r;
                 let tdir = (ca * uvba.x - ba * uvca.x) * r;
-                tan1[i0] += sdir;
-                tan1[i1] += sdir;
-                tan1[i2] += sdir;
-                tan2[i0] += tdir;
-                tan2[i1] += tdir;
-                tan2[i2] += tdir;
+                Some([sdir, tdir])
+            } else {
+                None
             }
         });
 
         let mut tangents = vec![Vec4::new(0.0, 0.0, 0.0, 0.0); self.positions.len()];
         self.for_each_vertex(|index| {
             let normal = self.normals.as_ref().unwrap()[index];
-            let t = tan1[index];
-            let tangent = (t - normal * normal.dot(t)).normalize();
-            let handedness = if normal.cross(tangent).dot(tan2[index]) < 0.0 {
+            let [tan1, tan2] = tan[index];
+            let t = tan1 - normal * normal.dot(tan1);
+            // If the uv coordinates are degenerate, any direction orthogonal to the normal is as good as any other.
+            let t = if t.magnitude2() > 0.0 {
+                t
+            } else if normal.x.abs() < 0.9 {
+                normal.cross(Vec3::unit_x())
+            } else {
+                normal.cross(Vec3::unit_y())
+            };
+            let tangent = t.normalize();
+            let handedness = if normal.cross(tangent).dot(tan2) < 0.0 {
                 1.0
             } else {
                 -1.0
@@ -540,6 +1165,7 @@ impl TriMesh {
         });
 
         self.tangents = Some(tangents);
+        Ok(())
     }
 
     ///
@@ -588,6 +1214,133 @@ impl TriMesh {
         }
     }
 
+    ///
+    /// Returns an iterator over the positions of the three vertices of each triangle in this mesh.
+    /// Positions with `f64` precision are converted to `f32`, use [TriMesh::triangles_f64] to keep the precision.
+    ///
+    /// ```
+    /// # use three_d_asset::{prelude::*, TriMesh};
+    /// let mesh = TriMesh::square();
+    /// let area: f32 = mesh
+    ///     .triangles()
+    ///     .map(|[a, b, c]| 0.5 * (b - a).cross(c - a).magnitude())
+    ///     .sum();
+    /// assert_eq!(area, 4.0);
+    /// ```
+    ///
+    pub fn triangles(&self) -> impl Iterator<Item = [Vec3; 3]> + '_ {
+        (0..self.triangle_count()).map(move |t| {
+            self.triangle(t).map(|i| match self.positions {
+                Positions::F32(ref positions) => positions[i],
+                Positions::F64(ref positions) => {
+                    let p = positions[i];
+                    Vec3::new(p.x as f32, p.y as f32, p.z as f32)
+                }
+            })
+        })
+    }
+
+    ///
+    /// Returns an iterator over the positions of the three vertices of each triangle in this mesh with `f64` precision, see [TriMesh::triangles].
+    ///
+    pub fn triangles_f64(&self) -> impl Iterator<Item = [Vector3<f64>; 3]> + '_ {
+        (0..self.triangle_count()).map(move |t| {
+            self.triangle(t).map(|i| match self.positions {
+                Positions::F32(ref positions) => {
+                    let p = positions[i];
+                    Vector3::new(p.x as f64, p.y as f64, p.z as f64)
+                }
+                Positions::F64(ref positions) => positions[i],
+            })
+        })
+    }
+
+    ///
+    /// Returns the three vertex indices of the triangle with the given index.
+    ///
+    fn triangle(&self, triangle: usize) -> [usize; 3] {
+        let i = triangle * 3;
+        match self.indices {
+            Indices::U8(ref indices) => {
+                [indices[i], indices[i + 1], indices[i + 2]].map(|i| i as usize)
+            }
+            Indices::U16(ref indices) => {
+                [indices[i], indices[i + 1], indices[i + 2]].map(|i| i as usize)
+            }
+            Indices::U32(ref indices) => {
+                [indices[i], indices[i + 1], indices[i + 2]].map(|i| i as usize)
+            }
+            Indices::None => [i, i + 1, i + 2],
+        }
+    }
+
+    ///
+    /// Calls the callback for each triangle and adds the returned values to each of the three vertices of the triangle.
+    /// Returns the sums for each vertex. With the `rayon` feature enabled, large meshes are split into chunks of triangles
+    /// which are summed in parallel on the rayon thread pool and then added together.
+    ///
+    fn accumulate_triangles<const N: usize>(
+        &self,
+        callback: impl Fn(usize, usize, usize) -> Option<[Vec3; N]> + Sync,
+    ) -> Vec<[Vec3; N]> {
+        #[cfg(feature = "rayon")]
+        {
+            // Each chunk is summed into its own copy of the vertex data, so only split when there are enough triangles to make up for that
+            const MIN_TRIANGLES_PER_CHUNK: usize = 1 << 14;
+            if rayon::current_num_threads() > 1
+                && self.triangle_count() >= 2 * MIN_TRIANGLES_PER_CHUNK
+            {
+                return self.accumulate_triangles_in_parallel(&callback, MIN_TRIANGLES_PER_CHUNK);
+            }
+        }
+        let mut sums = vec![[Vec3::new(0.0, 0.0, 0.0); N]; self.positions.len()];
+        for t in 0..self.triangle_count() {
+            self.accumulate_triangle(&mut sums, &callback, t);
+        }
+        sums
+    }
+
+    #[cfg(feature = "rayon")]
+    fn accumulate_triangles_in_parallel<const N: usize>(
+        &self,
+        callback: &(impl Fn(usize, usize, usize) -> Option<[Vec3; N]> + Sync),
+        min_triangles_per_chunk: usize,
+    ) -> Vec<[Vec3; N]> {
+        use rayon::prelude::*;
+        let zero = || vec![[Vec3::new(0.0, 0.0, 0.0); N]; self.positions.len()];
+        (0..self.triangle_count())
+            .into_par_iter()
+            .with_min_len(min_triangles_per_chunk)
+            .fold(zero, |mut sums, t| {
+                self.accumulate_triangle(&mut sums, callback, t);
+                sums
+            })
+            .reduce(zero, |mut sums, other| {
+                for (sum, other) in sums.iter_mut().zip(other) {
+                    for (sum, value) in sum.iter_mut().zip(other) {
+                        *sum += value;
+                    }
+                }
+                sums
+            })
+    }
+
+    fn accumulate_triangle<const N: usize>(
+        &self,
+        sums: &mut [[Vec3; N]],
+        callback: &impl Fn(usize, usize, usize) -> Option<[Vec3; N]>,
+        t: usize,
+    ) {
+        let [i0, i1, i2] = self.triangle(t);
+        if let Some(values) = callback(i0, i1, i2) {
+            for i in [i0, i1, i2] {
+                for (sum, value) in sums[i].iter_mut().zip(values) {
+                    *sum += value;
+                }
+            }
+        }
+    }
+
     ///
     /// Computes the [AxisAlignedBoundingBox] for this triangle mesh.
     ///
@@ -595,6 +1348,224 @@ impl TriMesh {
         self.positions.compute_aabb()
     }
 
+    ///
+    /// Samples the given number of points uniformly on the surface of this mesh, ie. the number of points sampled on each triangle is proportional to its area.
+    /// The colors and normals of the points are interpolated from the vertex colors and normals if the mesh has them.
+    /// The same seed always gives the same points, so use different seeds to get different samplings of the same mesh.
+    /// Returns an empty point cloud if the mesh has no area.
+    ///
+    pub fn sample_points(&self, count: usize, seed: u64) -> PointCloud {
+        let positions = self.positions.to_f64();
+        let mut triangles = Vec::new();
+        let mut cumulative_areas = Vec::new();
+        let mut total_area = 0.0;
+        self.for_each_triangle(|i0, i1, i2| {
+            let area = 0.5
+                * (positions[i1] - positions[i0])
+                    .cross(positions[i2] - positions[i0])
+                    .magnitude();
+            if area > 0.0 {
+                total_area += area;
+                triangles.push([i0, i1, i2]);
+                cumulative_areas.push(total_area);
+            }
+        });
+        if triangles.is_empty() {
+            return PointCloud::default();
+        }
+
+        let mut random = SplitMix64(seed);
+        let mut samples = Vec::with_capacity(count);
+        for _ in 0..count {
+            let a = random.next_f64() * total_area;
+            let t = cumulative_areas
+                .partition_point(|x| *x < a)
+                .min(triangles.len() - 1);
+            // Uniform barycentric coordinates by folding the unit square onto the triangle
+            let (mut u, mut v) = (random.next_f64(), random.next_f64());
+            if u + v > 1.0 {
+                u = 1.0 - u;
+                v = 1.0 - v;
+            }
+            samples.push((triangles[t], [1.0 - u - v, u, v]));
+        }
+
+        let sampled_positions = samples
+            .iter()
+            .map(|(t, w)| positions[t[0]] * w[0] + positions[t[1]] * w[1] + positions[t[2]] * w[2])
+            .collect::<Vec<_>>();
+        PointCloud {
+            positions: match self.positions {
+                Positions::F32(_) => Positions::F32(
+                    sampled_positions
+                        .into_iter()
+                        .map(|p| p.cast::<f32>().unwrap())
+                        .collect(),
+                ),
+                Positions::F64(_) => Positions::F64(sampled_positions),
+            },
+            colors: self.colors.as_ref().map(|colors| {
+                samples
+                    .iter()
+                    .map(|(t, w)| {
+                        let c = |f: fn(&Srgba) -> u8| {
+                            (0..3)
+                                .map(|i| f(&colors[t[i]]) as f64 * w[i])
+                                .sum::<f64>()
+                                .round() as u8
+                        };
+                        Srgba::new(c(|c| c.r), c(|c| c.g), c(|c| c.b), c(|c| c.a))
+                    })
+                    .collect()
+            }),
+            normals: self.normals.as_ref().map(|normals| {
+                samples
+                    .iter()
+                    .map(|(t, w)| {
+                        (0..3)
+                            .map(|i| normals[t[i]] * w[i] as f32)
+                            .sum::<Vec3>()
+                            .normalize()
+                    })
+                    .collect()
+            }),
+        }
+    }
+
+    ///
+    /// Reorders the triangles to improve the use of the vertex cache on the GPU, which increases the rendering performance.
+    /// The order is computed using [Tom Forsyth's algorithm](https://tomforsyth1000.github.io/papers/fast_vert_cache_opt.html).
+    /// Does nothing if the mesh has no indices.
+    ///
+    pub fn optimize_vertex_cache(&mut self) {
+        const CACHE_SIZE: usize = 32;
+        fn vertex_score(cache_position: Option<usize>, remaining_triangles: usize) -> f32 {
+            if remaining_triangles == 0 {
+                return -1.0;
+            }
+            let cache_score = match cache_position {
+                None => 0.0,
+                Some(p) if p < 3 => 0.75,
+                Some(p) => (1.0 - (p - 3) as f32 / (CACHE_SIZE - 3) as f32).powf(1.5),
+            };
+            cache_score + 2.0 / (remaining_triangles as f32).sqrt()
+        }
+
+        let Some(indices) = self.indices.to_u32() else {
+            return;
+        };
+        let triangle_count = indices.len() / 3;
+        let mut vertex_triangles = vec![Vec::new(); self.positions.len()];
+        for (t, triangle) in indices.chunks_exact(3).enumerate() {
+            for v in triangle {
+                vertex_triangles[*v as usize].push(t);
+            }
+        }
+        let mut cache_positions = vec![None; self.positions.len()];
+        let mut vertex_scores = vertex_triangles
+            .iter()
+            .map(|t| vertex_score(None, t.len()))
+            .collect::<Vec<_>>();
+        let triangle_score = |vertex_scores: &[f32], t: usize| {
+            indices[t * 3..t * 3 + 3]
+                .iter()
+                .map(|v| vertex_scores[*v as usize])
+                .sum::<f32>()
+        };
+        let mut is_added = vec![false; triangle_count];
+        let mut cache: Vec<usize> = Vec::new();
+        let mut new_indices = Vec::with_capacity(indices.len());
+        let mut best = None;
+        for _ in 0..triangle_count {
+            // If none of the vertices in the cache are used by the remaining triangles, find the best of all the remaining triangles.
+            let t = best.unwrap_or_else(|| {
+                (0..triangle_count)
+                    .filter(|t| !is_added[*t])
+                    .max_by(|a, b| {
+                        triangle_score(&vertex_scores, *a)
+                            .total_cmp(&triangle_score(&vertex_scores, *b))
+                    })
+                    .unwrap()
+            });
+            is_added[t] = true;
+            let triangle = &indices[t * 3..t * 3 + 3];
+            new_indices.extend_from_slice(triangle);
+            for v in triangle {
+                vertex_triangles[*v as usize].retain(|x| *x != t);
+            }
+
+            let mut new_cache = triangle.iter().map(|v| *v as usize).collect::<Vec<_>>();
+            new_cache.extend(cache.iter().filter(|v| !triangle.contains(&(**v as u32))));
+            for v in new_cache.iter().skip(CACHE_SIZE) {
+                cache_positions[*v] = None;
+                vertex_scores[*v] = vertex_score(None, vertex_triangles[*v].len());
+            }
+            new_cache.truncate(CACHE_SIZE);
+            for (p, v) in new_cache.iter().enumerate() {
+                cache_positions[*v] = Some(p);
+                vertex_scores[*v] = vertex_score(Some(p), vertex_triangles[*v].len());
+            }
+            cache = new_cache;
+
+            best = cache
+                .iter()
+                .flat_map(|v| vertex_triangles[*v].iter())
+                .max_by(|a, b| {
+                    triangle_score(&vertex_scores, **a)
+                        .total_cmp(&triangle_score(&vertex_scores, **b))
+                })
+                .copied();
+        }
+        self.indices = indices_of_same_type(&self.indices, new_indices);
+    }
+
+    ///
+    /// Reorders the vertices such that they appear in the same order as they are first used by the indices,
+    /// which improves the memory access pattern when rendering.
+    /// Vertices which are not used by any triangle are placed last.
+    /// This should be done after [TriMesh::optimize_vertex_cache] since that changes the order of the indices.
+    /// Does nothing if the mesh has no indices.
+    ///
+    pub fn optimize_vertex_fetch(&mut self) {
+        let Some(indices) = self.indices.to_u32() else {
+            return;
+        };
+        let vertex_count = self.positions.len();
+        let mut new_index = vec![None; vertex_count];
+        let mut order = Vec::with_capacity(vertex_count);
+        for i in indices.iter() {
+            new_index[*i as usize].get_or_insert_with(|| {
+                order.push(*i as usize);
+                order.len() - 1
+            });
+        }
+        for (i, index) in new_index.iter_mut().enumerate() {
+            index.get_or_insert_with(|| {
+                order.push(i);
+                order.len() - 1
+            });
+        }
+
+        fn reorder<T: Copy>(values: &[T], order: &[usize]) -> Vec<T> {
+            order.iter().map(|i| values[*i]).collect()
+        }
+        self.positions = match &self.positions {
+            Positions::F32(values) => Positions::F32(reorder(values, &order)),
+            Positions::F64(values) => Positions::F64(reorder(values, &order)),
+        };
+        self.normals = self.normals.as_ref().map(|v| reorder(v, &order));
+        self.tangents = self.tangents.as_ref().map(|v| reorder(v, &order));
+        self.uvs = self.uvs.as_ref().map(|v| reorder(v, &order));
+        self.colors = self.colors.as_ref().map(|v| reorder(v, &order));
+        self.indices = indices_of_same_type(
+            &self.indices,
+            indices
+                .iter()
+                .map(|i| new_index[*i as usize].unwrap() as u32)
+                .collect(),
+        );
+    }
+
     ///
     /// Returns an error if the mesh is not valid.
     ///
@@ -631,6 +1602,657 @@ impl TriMesh {
         buffer_check(self.colors.as_ref().map(|b| b.len()), "color")?;
         buffer_check(self.uvs.as_ref().map(|b| b.len()), "uv coordinate")?;
 
+        fn finite_check(mut values: impl Iterator<Item = bool>, name: &str) -> Result<()> {
+            if let Some(i) = values.position(|is_finite| !is_finite) {
+                Err(Error::NonFiniteValue(name.to_string(), i))?;
+            }
+            Ok(())
+        }
+        match self.positions {
+            Positions::F32(ref positions) => finite_check(
+                positions
+                    .iter()
+                    .map(|p| p.x.is_finite() && p.y.is_finite() && p.z.is_finite()),
+                "position",
+            )?,
+            Positions::F64(ref positions) => finite_check(
+                positions
+                    .iter()
+                    .map(|p| p.x.is_finite() && p.y.is_finite() && p.z.is_finite()),
+                "position",
+            )?,
+        }
+        if let Some(ref normals) = self.normals {
+            finite_check(
+                normals
+                    .iter()
+                    .map(|n| n.x.is_finite() && n.y.is_finite() && n.z.is_finite()),
+                "normal",
+            )?;
+        }
+        if let Some(ref tangents) = self.tangents {
+            finite_check(
+                tangents.iter().map(|t| {
+                    t.x.is_finite() && t.y.is_finite() && t.z.is_finite() && t.w.is_finite()
+                }),
+                "tangent",
+            )?;
+        }
+        if let Some(ref uvs) = self.uvs {
+            finite_check(
+                uvs.iter().map(|uv| uv.x.is_finite() && uv.y.is_finite()),
+                "uv coordinate",
+            )?;
+        }
+
         Ok(())
     }
+
+    ///
+    /// Returns an error if the mesh is not valid (see [TriMesh::validate]) or if it contains degenerate triangles, ie. triangles with zero area.
+    ///
+    pub fn validate_strict(&self) -> Result<()> {
+        self.validate()?;
+        let positions = self.positions.to_f64();
+        let mut triangle = 0;
+        let mut degenerate = None;
+        self.for_each_triangle(|i0, i1, i2| {
+            let (a, b, c) = (positions[i0], positions[i1], positions[i2]);
+            let (ab, ac) = (b - a, c - a);
+            let scale = ab.magnitude2().max(ac.magnitude2());
+            if degenerate.is_none()
+                && ab.cross(ac).magnitude2() <= scale * scale * f32::EPSILON as f64
+            {
+                degenerate = Some(triangle);
+            }
+            triangle += 1;
+        });
+        if let Some(triangle) = degenerate {
+            Err(Error::DegenerateTriangle(triangle))?;
+        }
+        Ok(())
+    }
+}
+
+fn planar_uv(p: Vec3, aabb: &AxisAlignedBoundingBox, axis: Axis) -> Vec2 {
+    let (min, max) = (aabb.min(), aabb.max());
+    let normalize = |value: f32, min: f32, max: f32| {
+        if max - min > 0.0 {
+            (value - min) / (max - min)
+        } else {
+            0.5
+        }
+    };
+    match axis {
+        Axis::X => vec2(
+            normalize(-p.z, -max.z, -min.z),
+            normalize(-p.y, -max.y, -min.y),
+        ),
+        Axis::Y => vec2(normalize(p.x, min.x, max.x), normalize(p.z, min.z, max.z)),
+        Axis::Z => vec2(
+            normalize(p.x, min.x, max.x),
+            normalize(-p.y, -max.y, -min.y),
+        ),
+    }
+}
+
+///
+/// A small and fast pseudo random number generator, see <https://prng.di.unimi.it/splitmix64.c>.
+///
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random number in the range `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+fn indices_of_same_type(indices: &Indices, values: Vec<u32>) -> Indices {
+    match indices {
+        Indices::None => Indices::None,
+        Indices::U8(_) => Indices::U8(values.into_iter().map(|i| i as u8).collect()),
+        Indices::U16(_) => Indices::U16(values.into_iter().map(|i| i as u16).collect()),
+        Indices::U32(_) => Indices::U32(values),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn compute_tangents() {
+        let mut mesh = TriMesh::square();
+        mesh.uvs = None;
+        assert!(matches!(
+            mesh.compute_tangents(),
+            Err(Error::MissingNormalsOrUvs)
+        ));
+
+        mesh.uvs = Some(vec![Vec2::zero(); mesh.positions.len()]);
+        mesh.compute_tangents().unwrap();
+        let normals = mesh.normals.as_ref().unwrap();
+        for (tangent, normal) in mesh.tangents.unwrap().iter().zip(normals) {
+            assert!((tangent.truncate().magnitude() - 1.0).abs() < 0.0001);
+            assert!(tangent.truncate().dot(*normal).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    pub fn triangle_positions() {
+        let mut mesh = TriMesh::cube();
+        let triangles = mesh.triangles().collect::<Vec<_>>();
+        assert_eq!(triangles.len(), 12);
+        let mut i = 0;
+        mesh.for_each_triangle(|i0, i1, i2| {
+            let positions = mesh.positions.to_f32();
+            assert_eq!(triangles[i], [positions[i0], positions[i1], positions[i2]]);
+            i += 1;
+        });
+
+        mesh.positions = Positions::F64(mesh.positions.to_f64());
+        mesh.indices = Indices::None;
+        assert_eq!(mesh.triangles().count(), mesh.positions.len() / 3);
+        for ([a, b, c], [a64, b64, c64]) in mesh.triangles().zip(mesh.triangles_f64()) {
+            assert_eq!(a, a64.cast().unwrap());
+            assert_eq!(b, b64.cast().unwrap());
+            assert_eq!(c, c64.cast().unwrap());
+        }
+    }
+
+    #[test]
+    pub fn transform() {
+        let rotation = Mat4::from_angle_y(degrees(30.0)) * Mat4::from_angle_x(degrees(70.0));
+        let mut mesh = TriMesh::cube();
+        mesh.compute_tangents().unwrap();
+        let normals = mesh.normals.clone().unwrap();
+        let tangents = mesh.tangents.clone().unwrap();
+        mesh.transform(Mat4::from_translation(vec3(1.0, 2.0, 3.0)) * rotation)
+            .unwrap();
+        for (n, expected) in mesh.normals.as_ref().unwrap().iter().zip(&normals) {
+            assert!((n - (rotation * expected.extend(0.0)).truncate()).magnitude() < 0.0001);
+        }
+        for (t, expected) in mesh.tangents.as_ref().unwrap().iter().zip(&tangents) {
+            let expected = (rotation * expected.truncate().extend(0.0)).truncate();
+            assert!((t.truncate() - expected).magnitude() < 0.0001);
+        }
+
+        // The determinant of this matrix is too small to invert
+        let mut mesh = TriMesh::cube();
+        mesh.transform(Mat4::from_scale(1e-15) * rotation).unwrap();
+        for (n, expected) in mesh.normals.as_ref().unwrap().iter().zip(&normals) {
+            let expected = (rotation * expected.extend(0.0)).truncate();
+            assert!((n.normalize() - expected).magnitude() < 0.0001);
+        }
+
+        let mut mesh = TriMesh::cube();
+        mesh.transform(Mat4::from_nonuniform_scale(2.0, 1.0, 1.0))
+            .unwrap();
+        let n = mesh.normals.as_ref().unwrap()[0];
+        assert!((n.normalize() - normals[0]).magnitude() < 0.0001);
+        assert!(matches!(
+            mesh.transform(Mat4::from_nonuniform_scale(1.0, 0.0, 1.0)),
+            Err(Error::FailedInvertingTransformationMatrix)
+        ));
+
+        // A mirroring transformation keeps the front faces facing outward
+        let mut mesh = TriMesh::cube();
+        mesh.compute_tangents().unwrap();
+        mesh.transform(Mat4::from_nonuniform_scale(-1.0, 1.0, 1.0))
+            .unwrap();
+        let mut expected = mesh.clone();
+        expected.compute_normals();
+        expected.compute_tangents().unwrap();
+        for (n, expected) in mesh.normals.unwrap().iter().zip(&expected.normals.unwrap()) {
+            assert!((n - expected).magnitude() < 0.0001);
+        }
+        for (t, expected) in mesh
+            .tangents
+            .unwrap()
+            .iter()
+            .zip(&expected.tangents.unwrap())
+        {
+            assert!((t - expected).magnitude() < 0.0001);
+        }
+    }
+
+    #[test]
+    pub fn builder() {
+        let square = TriMesh::square();
+        let mesh = TriMesh::builder()
+            .positions(square.positions.to_f32())
+            .indices(square.indices.to_u32().unwrap())
+            .normals(square.normals.clone().unwrap())
+            .tangents(square.tangents.clone().unwrap())
+            .uvs(square.uvs.clone().unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(mesh, square);
+
+        let mesh = TriMesh::builder()
+            .positions_f64(vec![Vector3::new(0.0, 0.0, 0.0); 3])
+            .build()
+            .unwrap();
+        assert_eq!(mesh.indices, Indices::None);
+        assert_eq!(mesh.triangle_count(), 1);
+
+        let mesh = TriMesh::builder()
+            .positions(vec![vec3(0.0, 0.0, 0.0); 300])
+            .indices((0..300).collect())
+            .build()
+            .unwrap();
+        assert!(matches!(mesh.indices, Indices::U16(_)));
+
+        assert!(matches!(
+            TriMesh::builder()
+                .positions(vec![vec3(0.0, 0.0, 0.0); 3])
+                .colors(vec![Srgba::WHITE; 4])
+                .build(),
+            Err(Error::InvalidBufferLength(name, 3, 4)) if name == "color"
+        ));
+        assert!(matches!(
+            TriMesh::builder()
+                .positions(vec![vec3(0.0, 0.0, 0.0); 3])
+                .indices(vec![0, 1, 3])
+                .build(),
+            Err(Error::InvalidIndices(3, 3))
+        ));
+    }
+
+    #[test]
+    pub fn validate() {
+        let mut mesh = TriMesh::cube();
+        mesh.validate_strict().unwrap();
+
+        let mut uvs = mesh.uvs.clone().unwrap();
+        uvs[3].y = f32::NAN;
+        mesh.uvs = Some(uvs);
+        assert!(
+            matches!(mesh.validate(), Err(Error::NonFiniteValue(name, 3)) if name == "uv coordinate")
+        );
+
+        let mut mesh = TriMesh::square();
+        mesh.validate_strict().unwrap();
+        let mut positions = mesh.positions.to_f32();
+        positions[1] = positions[0];
+        mesh.positions = Positions::F32(positions);
+        mesh.validate().unwrap();
+        assert!(matches!(
+            mesh.validate_strict(),
+            Err(Error::DegenerateTriangle(0))
+        ));
+    }
+
+    #[test]
+    pub fn generate_uvs() {
+        let mut mesh = TriMesh::square();
+        let uvs = mesh.uvs.take();
+        mesh.generate_planar_uvs(Axis::Z);
+        assert_eq!(mesh.uvs, uvs);
+        mesh.generate_box_uvs();
+        assert_eq!(mesh.uvs, uvs);
+
+        let mut mesh = TriMesh::cube();
+        mesh.generate_box_uvs();
+        for uv in mesh.uvs.as_ref().unwrap() {
+            assert!(uv.x >= 0.0 && uv.x <= 1.0 && uv.y >= 0.0 && uv.y <= 1.0);
+        }
+
+        let mut mesh = TriMesh::sphere(16);
+        mesh.generate_spherical_uvs();
+        for (p, uv) in mesh
+            .positions
+            .to_f32()
+            .iter()
+            .zip(mesh.uvs.as_ref().unwrap())
+        {
+            assert!((uv.y - p.y.clamp(-1.0, 1.0).acos() / std::f32::consts::PI).abs() < 0.001);
+            assert!(uv.x >= 0.0 && uv.x <= 1.0);
+        }
+        let top = mesh
+            .positions
+            .to_f32()
+            .iter()
+            .position(|p| p.y > 0.999)
+            .unwrap();
+        assert!(mesh.uvs.unwrap()[top].y < 0.01);
+    }
+
+    #[test]
+    pub fn compute_normals() {
+        // Large enough to be split into chunks when the rayon feature is enabled
+        let mut sphere = TriMesh::sphere(128);
+        assert!(sphere.triangle_count() > 1 << 15);
+        let expected = sphere.normals.take().unwrap();
+        sphere.compute_normals();
+        for (normal, expected) in sphere.normals.unwrap().iter().zip(expected) {
+            assert!((normal - expected).magnitude() < 0.01);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    pub fn accumulate_triangles_in_parallel() {
+        let mesh = TriMesh::sphere(16);
+        let callback =
+            |i0: usize, i1: usize, i2: usize| Some([vec3(i0 as f32, i1 as f32, i2 as f32)]);
+        let expected = mesh.accumulate_triangles(callback);
+        for min_triangles_per_chunk in [1, 5, 64] {
+            let sums = mesh.accumulate_triangles_in_parallel(&callback, min_triangles_per_chunk);
+            assert_eq!(sums, expected);
+        }
+    }
+
+    #[test]
+    pub fn boundary_edges() {
+        let cube = TriMesh::cube();
+        assert!(!cube.boundary_edges().is_empty());
+        assert!(!cube.is_watertight());
+
+        let sphere = TriMesh::sphere(8);
+        assert!(sphere.boundary_edges().is_empty());
+        assert!(sphere.is_watertight());
+
+        let square = TriMesh::square();
+        assert_eq!(
+            square.boundary_edges(),
+            vec![(0, 1), (1, 2), (2, 3), (3, 0)]
+        );
+        assert!(!square.is_watertight());
+
+        let mut indices = sphere.indices.to_u32().unwrap();
+        let removed = indices.split_off(indices.len() - 3);
+        let open = TriMesh {
+            indices: Indices::U32(indices),
+            ..sphere
+        };
+        assert_eq!(open.boundary_edges().len(), 3);
+        for (i, j) in open.boundary_edges() {
+            assert!(removed.contains(&i) && removed.contains(&j));
+        }
+        assert!(!open.is_watertight());
+        assert!(!TriMesh {
+            indices: Indices::U32(Vec::new()),
+            ..TriMesh::square()
+        }
+        .is_watertight());
+    }
+
+    #[test]
+    pub fn fill_holes() {
+        let sphere = TriMesh::sphere(8);
+        let indices = sphere.indices.to_u32().unwrap();
+
+        // Remove a single triangle
+        let mut mesh = TriMesh {
+            indices: Indices::U16(indices[3..].iter().map(|i| *i as u16).collect()),
+            ..sphere.clone()
+        };
+        assert!(!mesh.is_watertight());
+        assert_eq!(mesh.fill_holes(2), 0);
+        assert_eq!(mesh.fill_holes(3), 1);
+        assert!(mesh.is_watertight());
+        assert_eq!(mesh.triangle_count(), sphere.triangle_count());
+        assert_eq!(mesh.vertex_count(), sphere.vertex_count());
+        assert!(matches!(mesh.indices, Indices::U16(_)));
+
+        // Remove the fan around the north pole
+        let mut mesh = TriMesh {
+            indices: Indices::U16(indices[3 * 16..].iter().map(|i| *i as u16).collect()),
+            ..sphere.clone()
+        };
+        assert_eq!(mesh.boundary_edges().len(), 16);
+        assert_eq!(mesh.fill_holes(15), 0);
+        assert_eq!(mesh.fill_holes(16), 1);
+        assert!(mesh.is_watertight());
+        assert_eq!(mesh.triangle_count(), sphere.triangle_count());
+        assert_eq!(mesh.vertex_count(), sphere.vertex_count() + 1);
+        mesh.validate().unwrap();
+        let normal = *mesh.normals.as_ref().unwrap().last().unwrap();
+        assert!((normal - vec3(0.0, 0.0, 1.0)).magnitude() < 0.001);
+        let center = *mesh.positions.to_f64().last().unwrap();
+        assert!((center - vec3(0.0, 0.0, (std::f64::consts::PI / 8.0).cos())).magnitude() < 0.001);
+
+        // The triangles of a mesh without indices are not holes
+        let mut mesh = TriMesh::cube();
+        assert_eq!(mesh.fill_holes(3), 0);
+        assert_eq!(mesh.triangle_count(), 12);
+        assert!(!mesh.is_watertight());
+
+        // The outer border of an open surface is a hole
+        let mut mesh = TriMesh::square();
+        assert_eq!(mesh.fill_holes(4), 1);
+        assert_eq!(mesh.triangle_count(), 6);
+        assert!(mesh.is_watertight());
+    }
+
+    #[test]
+    pub fn split_connected_components() {
+        let sphere = TriMesh::sphere(8);
+        let mut moved = sphere.clone();
+        moved
+            .transform(Mat4::from_translation(vec3(3.0, 0.0, 0.0)))
+            .unwrap();
+        let offset = sphere.vertex_count() as u32 + 1;
+        let mut indices = sphere.indices.to_u32().unwrap();
+        indices.extend(moved.indices.to_u32().unwrap().iter().map(|i| i + offset));
+        let mut positions = sphere.positions.to_f32();
+        positions.push(vec3(100.0, 0.0, 0.0));
+        positions.extend(moved.positions.to_f32());
+        let mut normals = sphere.normals.clone().unwrap();
+        normals.push(vec3(1.0, 0.0, 0.0));
+        normals.extend(moved.normals.clone().unwrap());
+        let mesh = TriMesh {
+            positions: Positions::F32(positions),
+            indices: Indices::U32(indices),
+            normals: Some(normals),
+            ..Default::default()
+        };
+
+        let components = mesh.split_connected_components();
+        assert_eq!(components.len(), 2);
+        for (component, expected) in components.iter().zip([&sphere, &moved]) {
+            assert_eq!(component.triangle_count(), expected.triangle_count());
+            assert_eq!(component.vertex_count(), expected.vertex_count());
+            assert!(matches!(component.indices, Indices::U32(_)));
+            assert!(component.is_watertight());
+            assert_eq!(
+                component.compute_aabb().min(),
+                expected.compute_aabb().min()
+            );
+            assert_eq!(
+                component.compute_aabb().max(),
+                expected.compute_aabb().max()
+            );
+        }
+
+        let mut compacted = mesh.clone();
+        compacted.remove_unused_vertices();
+        assert_eq!(compacted.vertex_count(), mesh.vertex_count() - 1);
+        assert_eq!(compacted.triangle_count(), mesh.triangle_count());
+        assert_eq!(compacted.compute_aabb().max(), vec3(4.0, 1.0, 1.0));
+
+        let cube = TriMesh::cube();
+        assert_eq!(
+            cube.split_connected_components().len(),
+            cube.triangle_count()
+        );
+    }
+
+    #[test]
+    pub fn compute_curvature() {
+        let sphere = TriMesh::sphere(32);
+        let curvature = sphere.compute_curvature();
+        assert_eq!(curvature.len(), sphere.vertex_count());
+        for c in curvature {
+            assert!((c - 1.0).abs() < 0.01, "{}", c);
+        }
+
+        let mut sphere = TriMesh::sphere(32);
+        sphere.transform(Mat4::from_scale(2.0)).unwrap();
+        for c in sphere.compute_curvature() {
+            assert!((c - 0.5).abs() < 0.005, "{}", c);
+        }
+
+        let plane = grid(4);
+        let curvature = plane.compute_curvature();
+        assert_eq!(curvature[0], 0.0);
+        for c in curvature {
+            assert!(c.abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    pub fn from_heightmap() {
+        let height_map = Texture2D {
+            data: TextureData::RU8(vec![0, 0, 0, 0, 255, 0]),
+            width: 3,
+            height: 2,
+            ..Default::default()
+        };
+        let mesh = TriMesh::from_heightmap(&height_map, 4.0, 2.0, 3.0);
+        mesh.validate().unwrap();
+        assert_eq!(mesh.triangle_count(), 4);
+        let positions = mesh.positions.to_f32();
+        assert_eq!(positions[0], vec3(-2.0, 0.0, -1.0));
+        assert_eq!(positions[4], vec3(0.0, 3.0, 1.0));
+        assert_eq!(mesh.uvs.as_ref().unwrap()[5], vec2(1.0, 1.0));
+        assert!(mesh.normals.unwrap().iter().all(|n| n.y > 0.0));
+    }
+
+    #[test]
+    pub fn sample_points() {
+        let mut mesh = TriMesh::square();
+        mesh.colors = Some(vec![Srgba::new_opaque(100, 0, 200); 4]);
+        let mut big = TriMesh::square();
+        big.transform(Mat4::from_translation(vec3(10.0, 0.0, 0.0)) * Mat4::from_scale(2.0))
+            .unwrap();
+        big.colors = Some(vec![Srgba::new_opaque(100, 0, 200); 4]);
+        let mut merged = mesh.clone();
+        let offset = merged.positions.len() as u32;
+        merged.positions =
+            Positions::F32([mesh.positions.to_f32(), big.positions.to_f32()].concat());
+        merged.normals = Some([mesh.normals.clone().unwrap(), big.normals.unwrap()].concat());
+        merged.colors = Some(vec![Srgba::new_opaque(100, 0, 200); 8]);
+        let mut indices = mesh.indices.to_u32().unwrap();
+        indices.extend(big.indices.to_u32().unwrap().iter().map(|i| i + offset));
+        merged.indices = Indices::U32(indices);
+
+        let points = merged.sample_points(1000, 7);
+        assert_eq!(points, merged.sample_points(1000, 7));
+        assert_ne!(points, merged.sample_points(1000, 8));
+        let positions = points.positions.to_f32();
+        assert_eq!(positions.len(), 1000);
+        // The big square has four times the area, so it should get roughly 80% of the points
+        let in_big = positions.iter().filter(|p| p.x > 5.0).count();
+        assert!(in_big > 750 && in_big < 850);
+        for p in positions {
+            assert!(p.z.abs() < 0.0001);
+            assert!(p.x.abs() <= 1.0 || (p.x - 10.0).abs() <= 2.0);
+        }
+        assert!(points
+            .colors
+            .unwrap()
+            .iter()
+            .all(|c| *c == Srgba::new_opaque(100, 0, 200)));
+        assert!(points
+            .normals
+            .unwrap()
+            .iter()
+            .all(|n| (n - vec3(0.0, 0.0, 1.0)).magnitude() < 0.0001));
+    }
+
+    fn grid(size: u32) -> TriMesh {
+        let mut positions = Vec::new();
+        for y in 0..=size {
+            for x in 0..=size {
+                positions.push(vec3(x as f32, y as f32, 0.0));
+            }
+        }
+        // Triangles in column order which is bad for the cache
+        let mut indices = Vec::new();
+        for x in 0..size {
+            for y in 0..size {
+                let i = y * (size + 1) + x;
+                indices.extend([i, i + 1, i + size + 1, i + 1, i + size + 2, i + size + 1]);
+            }
+        }
+        TriMesh {
+            positions: Positions::F32(positions),
+            indices: Indices::U32(indices),
+            ..Default::default()
+        }
+    }
+
+    fn cache_misses(mesh: &TriMesh, cache_size: usize) -> usize {
+        let mut cache = std::collections::VecDeque::new();
+        let mut misses = 0;
+        for i in mesh.indices.to_u32().unwrap() {
+            if !cache.contains(&i) {
+                misses += 1;
+                cache.push_back(i);
+                if cache.len() > cache_size {
+                    cache.pop_front();
+                }
+            }
+        }
+        misses
+    }
+
+    fn triangles(mesh: &TriMesh) -> Vec<[[u32; 3]; 3]> {
+        let positions = mesh.positions.to_f32();
+        let mut triangles = Vec::new();
+        mesh.for_each_triangle(|a, b, c| {
+            let mut t = [a, b, c].map(|i| positions[i].map(|v| v as u32).into());
+            // Rotate such that the triangle starts with the smallest vertex without changing the orientation
+            let min = (0..3).min_by_key(|i| t[*i]).unwrap();
+            t.rotate_left(min);
+            triangles.push(t);
+        });
+        triangles.sort();
+        triangles
+    }
+
+    #[test]
+    pub fn optimize_vertex_cache() {
+        let mut mesh = grid(50);
+        let misses = cache_misses(&mesh, 32);
+        let triangles = triangles(&mesh);
+        mesh.optimize_vertex_cache();
+        mesh.validate().unwrap();
+        assert_eq!(triangles, super::test::triangles(&mesh));
+        assert!(cache_misses(&mesh, 32) < misses * 3 / 4);
+    }
+
+    #[test]
+    pub fn optimize_vertex_fetch() {
+        let mut mesh = grid(10);
+        mesh.indices = Indices::U16(
+            mesh.indices
+                .to_u32()
+                .unwrap()
+                .into_iter()
+                .rev()
+                .map(|i| i as u16)
+                .collect(),
+        );
+        let triangles = triangles(&mesh);
+        mesh.optimize_vertex_fetch();
+        mesh.validate().unwrap();
+        assert_eq!(triangles, super::test::triangles(&mesh));
+        let mut next = 0;
+        for i in mesh.indices.to_u32().unwrap() {
+            assert!(i <= next);
+            next = next.max(i + 1);
+        }
+    }
 }
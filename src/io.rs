@@ -33,6 +33,14 @@
 //! save(&assets).unwrap();
 //! ```
 //!
+//! ## OBJ/MTL materials
+//!
+//! MTL materials are converted into dielectric [PbrMaterial](crate::PbrMaterial)s, which means some of the MTL values are not supported:
+//! - The specular color (`Ks`) and specular map (`map_Ks`) are ignored, so the specular strength does not affect the material.
+//!   Only the specular exponent (`Ns`, `map_Ns`) is used, which is converted into roughness.
+//! - The emissive map (`map_Ke`) is not read by the MTL parser and is therefore ignored. The emissive color (`Ke`) is supported.
+//! - The ambient color (`Ka`), ambient map (`map_Ka`), displacement map (`disp`) and decal map (`decal`) are ignored.
+//!
 
 mod loader;
 pub use loader::*;
@@ -63,6 +71,9 @@ mod vol;
 #[cfg(feature = "pcd")]
 mod pcd;
 
+#[cfg(feature = "las")]
+mod las;
+
 ///
 /// Deserialize a single file from raw bytes.
 ///
@@ -88,7 +99,27 @@ pub fn load_and_deserialize<T: Deserialize>(path: impl AsRef<std::path::Path>) -
 pub async fn load_and_deserialize_async<T: Deserialize>(
     path: impl AsRef<std::path::Path>,
 ) -> crate::Result<T> {
-    load_async(&[&path]).await?.deserialize(path)
+    load_async(&[&path]).await?.deserialize_async(path).await
+}
+
+///
+/// Yields control to the event loop of the browser, so it can render and handle events before the future is polled again.
+/// On native, this does nothing since the work is usually done on another thread than the one handling events.
+///
+pub(crate) async fn yield_now() {
+    #[cfg(target_arch = "wasm32")]
+    {
+        // A zero timeout is a task on the event loop and not a microtask, which would run before rendering.
+        let promise = js_sys::Promise::new(&mut |resolve, _| match web_sys::window() {
+            Some(window) => {
+                let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, 0);
+            }
+            None => {
+                let _ = resolve.call0(&wasm_bindgen::JsValue::NULL);
+            }
+        });
+        let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+    }
 }
 
 ///
@@ -113,6 +144,30 @@ pub trait Deserialize: Sized {
         path: impl AsRef<std::path::Path>,
         raw_assets: &mut RawAssets,
     ) -> crate::Result<Self>;
+
+    ///
+    /// See [RawAssets::deserialize_with].
+    /// The default implementation ignores the options and calls [Deserialize::deserialize].
+    ///
+    fn deserialize_with(
+        path: impl AsRef<std::path::Path>,
+        raw_assets: &mut RawAssets,
+        options: &ImportOptions,
+    ) -> crate::Result<Self> {
+        let _ = options;
+        Self::deserialize(path, raw_assets)
+    }
+
+    ///
+    /// See [RawAssets::deserialize_async].
+    /// The default implementation calls [Deserialize::deserialize].
+    ///
+    fn deserialize_async(
+        path: impl AsRef<std::path::Path>,
+        raw_assets: &mut RawAssets,
+    ) -> impl std::future::Future<Output = crate::Result<Self>> {
+        async move { Self::deserialize(path, raw_assets) }
+    }
 }
 
 ///
@@ -126,10 +181,133 @@ pub trait Serialize: Sized {
     fn serialize(&self, path: impl AsRef<std::path::Path>) -> crate::Result<RawAssets>;
 }
 
+use crate::prelude::{CoordinateSystem, LengthUnit, Mat4, SquareMatrix};
 use crate::{Error, Geometry, Result};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+///
+/// Options used when deserializing a scene, see [RawAssets::deserialize_with].
+/// The default is to use the asset as authored without any conversion.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct ImportOptions {
+    /// The coordinate system the asset is authored in or `None` if the asset should not be converted.
+    /// Most formats, except glTF which is always Y up and right-handed, do not specify the coordinate system, so it has to be provided by the user.
+    pub source_coordinate_system: Option<CoordinateSystem>,
+    /// The coordinate system the asset is converted into when [ImportOptions::source_coordinate_system] is specified.
+    pub target_coordinate_system: CoordinateSystem,
+    /// The unit of the positions in the asset which overrides the [Scene::unit](crate::Scene::unit) specified by the file format.
+    /// This is needed to scale assets in formats without a unit, like OBJ and STL.
+    pub source_unit: Option<LengthUnit>,
+    /// The unit the asset is scaled into if the unit of the asset is known, either from the file format or from [ImportOptions::source_unit].
+    /// If `None`, the asset is not scaled.
+    pub target_unit: Option<LengthUnit>,
+}
+
+impl ImportOptions {
+    ///
+    /// Returns the transformation which is applied to the root of a deserialized scene with the given unit, see [Scene::unit](crate::Scene::unit).
+    ///
+    pub fn transformation(&self, unit: Option<LengthUnit>) -> Mat4 {
+        let scale = self
+            .source_unit
+            .or(unit)
+            .zip(self.target_unit)
+            .map(|(source, target)| source.conversion_to(target))
+            .unwrap_or(1.0);
+        let conversion = self
+            .source_coordinate_system
+            .map(|source| source.conversion_to(self.target_coordinate_system))
+            .unwrap_or_else(Mat4::identity);
+        Mat4::from_scale(scale) * conversion
+    }
+
+    ///
+    /// Applies [ImportOptions::transformation] to the scene by moving the children of the scene into a new root node with that transformation
+    /// and updates the unit of the scene.
+    ///
+    fn apply(&self, scene: &mut crate::Scene) {
+        let transformation = self.transformation(scene.unit);
+        if transformation != Mat4::identity() {
+            let root = crate::Node {
+                name: scene.name.clone(),
+                transformation,
+                children: std::mem::take(&mut scene.children),
+                ..Default::default()
+            };
+            scene.children.push(root);
+        }
+        scene.unit = self.source_unit.or(scene.unit);
+        if scene.unit.is_some() && self.target_unit.is_some() {
+            scene.unit = self.target_unit;
+        }
+    }
+}
+
+///
+/// The compression level used when serializing a PNG image, see [ImageSerializeOptions].
+/// A higher compression level gives a smaller file, but takes longer to serialize.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum PngCompression {
+    /// No compression.
+    Uncompressed,
+    /// Fast, minimal compression.
+    #[default]
+    Fast,
+    /// A balance between compression and speed.
+    Default,
+    /// The highest compression level.
+    Best,
+}
+
+///
+/// Options used when serializing a [Texture2D](crate::Texture2D) into an image file, see [crate::Texture2D::serialize_with_options].
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ImageSerializeOptions {
+    /// The quality of a JPEG image in the range `1..=100` where 100 is the best quality and the largest file.
+    pub jpeg_quality: u8,
+    /// The compression level of a PNG image.
+    pub png_compression: PngCompression,
+}
+
+impl Default for ImageSerializeOptions {
+    fn default() -> Self {
+        Self {
+            jpeg_quality: 75,
+            png_compression: PngCompression::Fast,
+        }
+    }
+}
+
+impl crate::Texture2D {
+    ///
+    /// Serialize the texture into an image file using the given options, otherwise the same as [Serialize::serialize]
+    /// which uses the default [ImageSerializeOptions].
+    ///
+    #[allow(unused_variables)]
+    pub fn serialize_with_options(
+        &self,
+        path: impl AsRef<Path>,
+        options: ImageSerializeOptions,
+    ) -> Result<RawAssets> {
+        let path = path.as_ref();
+
+        #[cfg(not(feature = "image"))]
+        return Err(Error::FeatureMissing(
+            path.extension()
+                .map(|e| e.to_str().unwrap())
+                .unwrap_or("image")
+                .to_string(),
+        ));
+
+        #[cfg(feature = "image")]
+        img::serialize_img(self, path, options)
+    }
+}
+
 impl Deserialize for crate::Texture2D {
     fn deserialize(path: impl AsRef<std::path::Path>, raw_assets: &mut RawAssets) -> Result<Self> {
         let path = raw_assets.match_path(path.as_ref())?;
@@ -160,18 +338,7 @@ impl Deserialize for crate::Texture2D {
 
 impl Serialize for crate::Texture2D {
     fn serialize(&self, path: impl AsRef<Path>) -> Result<RawAssets> {
-        let path = path.as_ref();
-
-        #[cfg(not(feature = "image"))]
-        return Err(Error::FeatureMissing(
-            path.extension()
-                .map(|e| e.to_str().unwrap())
-                .unwrap_or("image")
-                .to_string(),
-        ));
-
-        #[cfg(feature = "image")]
-        img::serialize_img(self, path)
+        self.serialize_with_options(path, ImageSerializeOptions::default())
     }
 }
 
@@ -207,9 +374,121 @@ impl Deserialize for crate::Scene {
                 #[cfg(feature = "pcd")]
                 pcd::deserialize_pcd(raw_assets, &path)
             }
+            "las" => {
+                #[cfg(not(feature = "las"))]
+                return Err(Error::FeatureMissing("las".to_string()));
+
+                #[cfg(feature = "las")]
+                las::deserialize_las(raw_assets, &path)
+            }
             _ => Err(Error::FailedDeserialize(path.to_str().unwrap().to_string())),
         }
     }
+
+    fn deserialize_with(
+        path: impl AsRef<Path>,
+        raw_assets: &mut RawAssets,
+        options: &ImportOptions,
+    ) -> Result<Self> {
+        let mut scene = Self::deserialize(path, raw_assets)?;
+        options.apply(&mut scene);
+        Ok(scene)
+    }
+
+    async fn deserialize_async(path: impl AsRef<Path>, raw_assets: &mut RawAssets) -> Result<Self> {
+        let path = raw_assets.match_path(path.as_ref())?;
+        match path.extension().map(|e| e.to_str().unwrap()).unwrap_or("") {
+            #[cfg(feature = "gltf")]
+            "gltf" | "glb" => gltf::deserialize_gltf_async(raw_assets, &path).await,
+            _ => {
+                yield_now().await;
+                Self::deserialize(path, raw_assets)
+            }
+        }
+    }
+}
+
+impl Serialize for crate::Scene {
+    fn serialize(&self, path: impl AsRef<Path>) -> Result<RawAssets> {
+        let path = path.as_ref();
+        match path.extension().map(|e| e.to_str().unwrap()).unwrap_or("") {
+            "glb" => {
+                let mut raw_assets = RawAssets::new();
+                raw_assets.insert(path, self.to_glb_bytes()?);
+                Ok(raw_assets)
+            }
+            _ => Err(Error::FailedSerialize(path.to_str().unwrap().to_string())),
+        }
+    }
+}
+
+impl crate::Scene {
+    ///
+    /// Serializes the scene into the bytes of a single self-contained .glb file which has no external dependencies,
+    /// for example to hand directly to a web viewer.
+    /// All of the vertex data is embedded in the binary chunk and all of the textures are embedded as PNG images,
+    /// so the `png` feature is needed if the scene has textures.
+    ///
+    /// The node hierarchy, the geometry and the materials, including the transmission, index of refraction,
+    /// emissive strength, clear coat and texture transform extensions, are serialized.
+    /// The [Node::animations](crate::Node::animations) are not serialized and textures with floating point data result in an error.
+    ///
+    /// ```
+    /// # use three_d_asset::{io::*, Scene};
+    /// # #[cfg(feature = "stl")]
+    /// # {
+    /// let scene: Scene = load_and_deserialize("test_data/cube.stl").unwrap();
+    /// # #[cfg(feature = "gltf")]
+    /// let glb = scene.to_glb_bytes().unwrap();
+    /// # }
+    /// ```
+    ///
+    pub fn to_glb_bytes(&self) -> Result<Vec<u8>> {
+        #[cfg(not(feature = "gltf"))]
+        return Err(Error::FeatureMissing("gltf".to_string()));
+
+        #[cfg(feature = "gltf")]
+        gltf::serialize_glb(self)
+    }
+}
+
+impl Deserialize for Vec<crate::Scene> {
+    fn deserialize(path: impl AsRef<Path>, raw_assets: &mut RawAssets) -> Result<Self> {
+        let path = raw_assets.match_path(path.as_ref())?;
+        match path.extension().map(|e| e.to_str().unwrap()).unwrap_or("") {
+            "gltf" | "glb" => {
+                #[cfg(not(feature = "gltf"))]
+                return Err(Error::FeatureMissing("gltf".to_string()));
+
+                #[cfg(feature = "gltf")]
+                gltf::deserialize_gltf_all_scenes(raw_assets, &path)
+            }
+            _ => Ok(vec![crate::Scene::deserialize(path, raw_assets)?]),
+        }
+    }
+
+    fn deserialize_with(
+        path: impl AsRef<Path>,
+        raw_assets: &mut RawAssets,
+        options: &ImportOptions,
+    ) -> Result<Self> {
+        let mut scenes = Self::deserialize(path, raw_assets)?;
+        for scene in scenes.iter_mut() {
+            options.apply(scene);
+        }
+        Ok(scenes)
+    }
+
+    async fn deserialize_async(path: impl AsRef<Path>, raw_assets: &mut RawAssets) -> Result<Self> {
+        let path = raw_assets.match_path(path.as_ref())?;
+        match path.extension().map(|e| e.to_str().unwrap()).unwrap_or("") {
+            #[cfg(feature = "gltf")]
+            "gltf" | "glb" => gltf::deserialize_gltf_all_scenes_async(raw_assets, &path).await,
+            _ => Ok(vec![
+                crate::Scene::deserialize_async(path, raw_assets).await?,
+            ]),
+        }
+    }
 }
 
 impl Deserialize for crate::Model {
@@ -217,6 +496,20 @@ impl Deserialize for crate::Model {
         let scene = crate::Scene::deserialize(path, raw_assets)?;
         Ok(scene.into())
     }
+
+    fn deserialize_with(
+        path: impl AsRef<Path>,
+        raw_assets: &mut RawAssets,
+        options: &ImportOptions,
+    ) -> Result<Self> {
+        let scene = crate::Scene::deserialize_with(path, raw_assets, options)?;
+        Ok(scene.into())
+    }
+
+    async fn deserialize_async(path: impl AsRef<Path>, raw_assets: &mut RawAssets) -> Result<Self> {
+        let scene = crate::Scene::deserialize_async(path, raw_assets).await?;
+        Ok(scene.into())
+    }
 }
 
 impl Deserialize for crate::VoxelGrid {
@@ -235,6 +528,38 @@ impl Deserialize for crate::VoxelGrid {
     }
 }
 
+impl Serialize for crate::VoxelGrid {
+    fn serialize(&self, path: impl AsRef<Path>) -> Result<RawAssets> {
+        let path = path.as_ref();
+        match path.extension().map(|e| e.to_str().unwrap()).unwrap_or("") {
+            "vol" => {
+                #[cfg(not(feature = "vol"))]
+                return Err(Error::FeatureMissing("vol".to_string()));
+
+                #[cfg(feature = "vol")]
+                vol::serialize_vol(&self.voxels, self.size, path)
+            }
+            _ => Err(Error::FailedSerialize(path.to_str().unwrap().to_string())),
+        }
+    }
+}
+
+impl Serialize for crate::Texture3D {
+    fn serialize(&self, path: impl AsRef<Path>) -> Result<RawAssets> {
+        let path = path.as_ref();
+        match path.extension().map(|e| e.to_str().unwrap()).unwrap_or("") {
+            "vol" => {
+                #[cfg(not(feature = "vol"))]
+                return Err(Error::FeatureMissing("vol".to_string()));
+
+                #[cfg(feature = "vol")]
+                vol::serialize_vol(self, crate::VoxelGrid::default().size, path)
+            }
+            _ => Err(Error::FailedSerialize(path.to_str().unwrap().to_string())),
+        }
+    }
+}
+
 impl Deserialize for crate::Texture3D {
     fn deserialize(path: impl AsRef<Path>, raw_assets: &mut RawAssets) -> Result<Self> {
         let path = raw_assets.match_path(path.as_ref())?;
@@ -245,9 +570,18 @@ impl Deserialize for crate::Texture3D {
 
 impl Deserialize for crate::TriMesh {
     fn deserialize(path: impl AsRef<Path>, raw_assets: &mut RawAssets) -> Result<Self> {
+        Self::deserialize_with(path, raw_assets, &ImportOptions::default())
+    }
+
+    fn deserialize_with(
+        path: impl AsRef<Path>,
+        raw_assets: &mut RawAssets,
+        options: &ImportOptions,
+    ) -> Result<Self> {
         let path = path.as_ref();
-        let model = crate::Model::deserialize(path, raw_assets)?;
-        model
+        let scene = crate::Scene::deserialize(path, raw_assets)?;
+        let transformation = options.transformation(scene.unit);
+        let mut mesh = crate::Model::from(scene)
             .geometries
             .into_iter()
             .find_map(|p| {
@@ -262,15 +596,28 @@ impl Deserialize for crate::TriMesh {
                     "a triangle mesh".to_owned(),
                     path.to_str().unwrap().to_owned(),
                 )
-            })
+            })?;
+        if transformation != Mat4::identity() {
+            mesh.transform(transformation)?;
+        }
+        Ok(mesh)
     }
 }
 
 impl Deserialize for crate::PointCloud {
     fn deserialize(path: impl AsRef<Path>, raw_assets: &mut RawAssets) -> Result<Self> {
+        Self::deserialize_with(path, raw_assets, &ImportOptions::default())
+    }
+
+    fn deserialize_with(
+        path: impl AsRef<Path>,
+        raw_assets: &mut RawAssets,
+        options: &ImportOptions,
+    ) -> Result<Self> {
         let path = path.as_ref();
-        let model = crate::Model::deserialize(path, raw_assets)?;
-        model
+        let scene = crate::Scene::deserialize(path, raw_assets)?;
+        let transformation = options.transformation(scene.unit);
+        let mut point_cloud = crate::Model::from(scene)
             .geometries
             .into_iter()
             .find_map(|p| {
@@ -285,10 +632,42 @@ impl Deserialize for crate::PointCloud {
                     "a point cloud".to_owned(),
                     path.to_str().unwrap().to_owned(),
                 )
-            })
+            })?;
+        if transformation != Mat4::identity() {
+            point_cloud.transform(transformation)?;
+        }
+        Ok(point_cloud)
     }
 }
 
+impl Serialize for crate::PointCloud {
+    fn serialize(&self, path: impl AsRef<Path>) -> Result<RawAssets> {
+        let path = path.as_ref();
+        match path.extension().map(|e| e.to_str().unwrap()).unwrap_or("") {
+            "pcd" => {
+                #[cfg(not(feature = "pcd"))]
+                return Err(Error::FeatureMissing("pcd".to_string()));
+
+                #[cfg(feature = "pcd")]
+                pcd::serialize_pcd(self, path, false)
+            }
+            _ => Err(Error::FailedSerialize(path.to_str().unwrap().to_string())),
+        }
+    }
+}
+
+///
+/// Serialize a point cloud into the ascii variant of the .pcd format.
+/// Serializing using [Serialize] results in the more compact binary variant.
+///
+#[cfg(feature = "pcd")]
+pub fn serialize_pcd_ascii(
+    point_cloud: &crate::PointCloud,
+    path: impl AsRef<Path>,
+) -> Result<RawAssets> {
+    pcd::serialize_pcd(point_cloud, path.as_ref(), true)
+}
+
 fn get_dependencies(raw_assets: &RawAssets) -> Vec<PathBuf> {
     #[allow(unused_mut)]
     let mut dependencies = HashSet::new();
@@ -1,6 +1,7 @@
 use crate::{animation::*, geometry::*, io::*, material::*, Error, Node, Result, Scene};
-use ::gltf::Gltf;
-use std::collections::HashSet;
+use ::gltf::json::validation::{Checked::Valid, USize64};
+use ::gltf::{json, Gltf};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 pub fn dependencies(raw_assets: &RawAssets, path: &PathBuf) -> HashSet<PathBuf> {
@@ -38,9 +39,98 @@ pub fn dependencies(raw_assets: &RawAssets, path: &PathBuf) -> HashSet<PathBuf>
 }
 
 pub fn deserialize_gltf(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<Scene> {
-    let Gltf { document, mut blob } = Gltf::from_slice(&raw_assets.remove(path)?)?;
+    let (mut scenes, default_scene) = deserialize_scenes(raw_assets, path)?;
+    if scenes.is_empty() {
+        Err(Error::GltfMissingData(path.to_str().unwrap().to_owned()))?;
+    }
+    Ok(scenes.swap_remove(default_scene.unwrap_or(0)))
+}
+
+pub fn deserialize_gltf_all_scenes(
+    raw_assets: &mut RawAssets,
+    path: &PathBuf,
+) -> Result<Vec<Scene>> {
+    Ok(deserialize_scenes(raw_assets, path)?.0)
+}
+
+pub async fn deserialize_gltf_async(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<Scene> {
+    let (mut scenes, default_scene) = deserialize_scenes_async(raw_assets, path).await?;
+    if scenes.is_empty() {
+        Err(Error::GltfMissingData(path.to_str().unwrap().to_owned()))?;
+    }
+    Ok(scenes.swap_remove(default_scene.unwrap_or(0)))
+}
+
+pub async fn deserialize_gltf_all_scenes_async(
+    raw_assets: &mut RawAssets,
+    path: &PathBuf,
+) -> Result<Vec<Scene>> {
+    Ok(deserialize_scenes_async(raw_assets, path).await?.0)
+}
+
+///
+/// Deserializes all scenes and returns them together with the index of the default scene.
+///
+fn deserialize_scenes(
+    raw_assets: &mut RawAssets,
+    path: &PathBuf,
+) -> Result<(Vec<Scene>, Option<usize>)> {
+    let (document, buffers) = parse_buffers(raw_assets, path)?;
+    build_scenes(raw_assets, path, &document, buffers, HashMap::new())
+}
+
+///
+/// Deserializes all scenes like [deserialize_scenes], except that all of the images are decoded up front
+/// and control is yielded to the event loop before decoding each of them, since that is the most expensive part.
+///
+async fn deserialize_scenes_async(
+    raw_assets: &mut RawAssets,
+    path: &PathBuf,
+) -> Result<(Vec<Scene>, Option<usize>)> {
+    let (document, buffers) = parse_buffers(raw_assets, path)?;
+    let base_path = path.parent().unwrap_or(Path::new(""));
+    let mut textures = HashMap::new();
+    for image in document.images() {
+        yield_now().await;
+        let key = image_key(base_path, &image.source());
+        // An image that fails to decode is decoded again, and the error reported, if a material uses it
+        if let Ok(tex) = decode_image(raw_assets, &buffers, &key, image.source()) {
+            textures.insert(key, tex);
+        }
+    }
+    yield_now().await;
+    build_scenes(raw_assets, path, &document, buffers, textures)
+}
+
+///
+/// Parses the document and returns it together with the data of all of the buffers.
+///
+/// To keep the peak memory usage close to the size of the input, buffer data is moved rather than copied:
+/// The binary chunk of a .glb file is moved to the front of the allocation holding the file, which is then used as the buffer,
+/// and external buffers are moved out of the [RawAssets]. Padding a buffer to a multiple of four bytes
+/// never reallocates the binary chunk and at most reallocates an external buffer once.
+///
+fn parse_buffers(
+    raw_assets: &mut RawAssets,
+    path: &PathBuf,
+) -> Result<(::gltf::Document, Vec<::gltf::buffer::Data>)> {
+    let (document, mut blob) = parse_document(raw_assets.remove(path)?)?;
     let base_path = path.parent().unwrap_or(Path::new(""));
 
+    // A self-contained file has no external files, so the raw assets are not used again.
+    // Otherwise, report all of the missing files at once instead of failing on the first one.
+    let missing_files = external_files(&document, base_path)
+        .into_iter()
+        .filter(|p| matches!(raw_assets.match_path(p), Err(Error::NotLoaded(_))))
+        .map(|p| p.to_str().unwrap().to_owned())
+        .collect::<Vec<_>>();
+    if !missing_files.is_empty() {
+        Err(Error::GltfMissingFiles(
+            path.to_str().unwrap().to_owned(),
+            missing_files,
+        ))?;
+    }
+
     let mut buffers = Vec::new();
     for buffer in document.buffers() {
         let mut data = match buffer.source() {
@@ -51,30 +141,47 @@ pub fn deserialize_gltf(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<Sc
                     raw_assets.remove(base_path.join(uri))?
                 }
             }
-            ::gltf::buffer::Source::Bin => blob.take().ok_or(Error::GltfMissingData)?,
+            ::gltf::buffer::Source::Bin => blob
+                .take()
+                .ok_or_else(|| Error::GltfMissingData(path.to_str().unwrap().to_owned()))?,
         };
         if data.len() < buffer.length() {
-            Err(Error::GltfCorruptData)?;
-        }
-        while data.len() % 4 != 0 {
-            data.push(0);
+            Err(Error::GltfCorruptData(path.to_str().unwrap().to_owned()))?;
         }
+        data.resize(data.len().next_multiple_of(4), 0);
         buffers.push(::gltf::buffer::Data(data));
     }
+    Ok((document, buffers))
+}
 
+///
+/// Builds all of the scenes in the document. The given textures, which are keyed by [image_key], are used instead of decoding the images again.
+///
+fn build_scenes(
+    raw_assets: &mut RawAssets,
+    path: &Path,
+    document: &::gltf::Document,
+    mut buffers: Vec<::gltf::buffer::Data>,
+    textures: HashMap<String, Texture2D>,
+) -> Result<(Vec<Scene>, Option<usize>)> {
+    let base_path = path.parent().unwrap_or(Path::new(""));
+    let mut textures = TextureCache::new(document, base_path, textures);
     let mut materials = Vec::new();
     for material in document.materials() {
         if let Some(_) = material.index() {
             materials.push(parse_material(
                 raw_assets,
                 &base_path,
+                document,
                 &mut buffers,
+                &mut textures,
                 &material,
             )?);
         }
     }
 
     let mut nodes = Vec::new();
+    let mut warnings = Vec::new();
     for gltf_node in document.nodes() {
         let transformation = parse_transform(gltf_node.transform());
         // glTF say that if the scale is all zeroes, the node should be ignored.
@@ -84,7 +191,7 @@ pub fn deserialize_gltf(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<Sc
                 .map(|s| s.to_string())
                 .unwrap_or(format!("index {}", gltf_node.index()));
             let children = if let Some(mesh) = gltf_node.mesh() {
-                parse_model(&mesh, &buffers)?
+                parse_model(&mesh, &buffers, &mut warnings)?
             } else {
                 Vec::new()
             };
@@ -105,9 +212,11 @@ pub fn deserialize_gltf(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<Sc
         for channel in animation.channels() {
             let reader = channel.reader(|buffer| Some(&buffers[buffer.index()]));
             let interpolation = match channel.sampler().interpolation() {
-                ::gltf::animation::Interpolation::Step => Interpolation::Nearest,
-                ::gltf::animation::Interpolation::Linear => Interpolation::Linear,
-                ::gltf::animation::Interpolation::CubicSpline => Interpolation::CubicSpline,
+                ::gltf::animation::Interpolation::Step => AnimationInterpolation::Step,
+                ::gltf::animation::Interpolation::Linear => AnimationInterpolation::Linear,
+                ::gltf::animation::Interpolation::CubicSpline => {
+                    AnimationInterpolation::CubicSpline
+                }
             };
             let target_node = channel.target().node().index();
             let key = (
@@ -178,22 +287,60 @@ pub fn deserialize_gltf(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<Sc
         }
     }
 
-    let gltf_scene = document.scenes().nth(0).unwrap();
-    let mut scene = Scene {
-        name: gltf_scene
-            .name()
-            .unwrap_or(&format!("Scene {}", gltf_scene.index()))
-            .to_owned(),
-        materials,
-        children: Vec::new(),
-    };
-    for c in gltf_scene.nodes() {
-        if let Some(mut node) = nodes[c.index()].take() {
-            visit(c, &mut nodes, &mut node.children);
-            scene.children.push(node);
+    let scene_count = document.scenes().len();
+    let mut scenes = Vec::new();
+    for gltf_scene in document.scenes() {
+        // Nodes and materials can be shared between scenes, so only the last scene takes them.
+        let (mut scene_nodes, materials) = if gltf_scene.index() + 1 < scene_count {
+            (nodes.clone(), materials.clone())
+        } else {
+            (std::mem::take(&mut nodes), std::mem::take(&mut materials))
+        };
+        let mut scene = Scene {
+            name: gltf_scene
+                .name()
+                .unwrap_or(&format!("Scene {}", gltf_scene.index()))
+                .to_owned(),
+            materials,
+            children: Vec::new(),
+            unit: Some(LengthUnit::Meter),
+            warnings: warnings.clone(),
+        };
+        for c in gltf_scene.nodes() {
+            if let Some(mut node) = scene_nodes[c.index()].take() {
+                visit(c, &mut scene_nodes, &mut node.children);
+                scene.children.push(node);
+            }
         }
+        scenes.push(scene);
     }
-    Ok(scene)
+    Ok((scenes, document.default_scene().map(|s| s.index())))
+}
+
+///
+/// Returns the paths of the buffers and images which are neither embedded in a .glb file nor in a data url.
+///
+fn external_files(document: &::gltf::Document, base_path: &Path) -> Vec<PathBuf> {
+    let buffer_uris = document
+        .buffers()
+        .filter_map(|buffer| match buffer.source() {
+            ::gltf::buffer::Source::Uri(uri) => Some(uri),
+            ::gltf::buffer::Source::Bin => None,
+        });
+    let image_uris = document
+        .textures()
+        .filter_map(|texture| match texture.source().source() {
+            ::gltf::image::Source::Uri { uri, .. } => Some(uri),
+            ::gltf::image::Source::View { .. } => None,
+        });
+    let mut files = Vec::new();
+    for uri in buffer_uris.chain(image_uris) {
+        let file = base_path.join(uri);
+        if !uri.starts_with("data:") && !files.contains(&file) {
+            files.push(file);
+        }
+    }
+    files
 }
 
 fn visit(gltf_node: ::gltf::Node, nodes: &mut Vec<Option<Node>>, children: &mut Vec<Node>) {
@@ -205,11 +352,82 @@ fn visit(gltf_node: ::gltf::Node, nodes: &mut Vec<Option<Node>>, children: &mut
     }
 }
 
-fn parse_model(mesh: &::gltf::mesh::Mesh, buffers: &[::gltf::buffer::Data]) -> Result<Vec<Node>> {
+///
+/// Parses the glTF document from either a .gltf or a .glb file and returns it together with the binary chunk of a .glb file, if any.
+/// The format is detected from the content, so a file with the wrong extension is still parsed correctly.
+/// The binary chunk reuses the allocation of the given bytes instead of being copied.
+///
+fn parse_document(mut bytes: Vec<u8>) -> Result<(::gltf::Document, Option<Vec<u8>>)> {
+    let (root, blob_range) = if bytes.starts_with(b"glTF") {
+        let glb = ::gltf::binary::Glb::from_slice(&bytes)?;
+        let root: ::gltf::json::Root =
+            ::gltf::json::deserialize::from_slice(&glb.json).map_err(::gltf::Error::Deserialize)?;
+        // The chunks borrow from the bytes, so the offset of the binary chunk is the distance between the pointers
+        let blob_range = glb.bin.as_ref().map(|bin| {
+            let start = bin.as_ptr() as usize - bytes.as_ptr() as usize;
+            start..start + bin.len()
+        });
+        (root, blob_range)
+    } else {
+        let root =
+            ::gltf::json::deserialize::from_slice(&bytes).map_err(::gltf::Error::Deserialize)?;
+        (root, None)
+    };
+    let blob = blob_range.map(|range| {
+        bytes.truncate(range.end);
+        bytes.drain(..range.start);
+        bytes
+    });
+
+    // Draco compressed accessors have no buffer view which fails validation,
+    // so report the missing extension support instead of the validation error.
+    let uses_draco = root
+        .extensions_used
+        .iter()
+        .any(|e| e == "KHR_draco_mesh_compression");
+    match ::gltf::Document::from_json(root) {
+        Ok(document) => Ok((document, blob)),
+        Err(_) if uses_draco => Err(Error::GltfUnsupportedExtension(
+            "KHR_draco_mesh_compression".to_owned(),
+        )),
+        Err(e) => Err(e)?,
+    }
+}
+
+///
+/// Parses the primitives of the mesh into nodes.
+/// Primitives which cannot be imported are skipped and a description of why is added to the warnings.
+///
+fn parse_model(
+    mesh: &::gltf::mesh::Mesh,
+    buffers: &[::gltf::buffer::Data],
+    warnings: &mut Vec<String>,
+) -> Result<Vec<Node>> {
     let mut children = Vec::new();
     for primitive in mesh.primitives() {
         let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
-        if let Some(read_positions) = reader.read_positions() {
+        let mut skip = |reason: &str| {
+            let mesh_name = mesh
+                .name()
+                .map(|s| s.to_string())
+                .unwrap_or(format!("index {}", mesh.index()));
+            warnings.push(format!(
+                "skipped primitive {} of mesh {}: {}",
+                primitive.index(),
+                mesh_name,
+                reason
+            ));
+        };
+        let mode = primitive.mode();
+        if !matches!(
+            mode,
+            ::gltf::mesh::Mode::Triangles
+                | ::gltf::mesh::Mode::TriangleStrip
+                | ::gltf::mesh::Mode::TriangleFan
+                | ::gltf::mesh::Mode::Points
+        ) {
+            skip(&format!("unsupported mode {:?}", mode));
+        } else if let Some(read_positions) = reader.read_positions() {
             let positions: Vec<_> = read_positions.map(|p| p.into()).collect();
 
             let normals = reader
@@ -240,23 +458,73 @@ fn parse_model(mesh: &::gltf::mesh::Mesh, buffers: &[::gltf::buffer::Data]) -> R
                 .read_tex_coords(0)
                 .map(|values| values.into_f32().map(|uv| uv.into()).collect());
 
+            let geometry = match mode {
+                ::gltf::mesh::Mode::Points => {
+                    // Point clouds are not indexed, so the indexed points are copied
+                    let points = indices.into_u32();
+                    if let Some(max) = points.as_ref().and_then(|p| p.iter().max()) {
+                        if *max as usize >= positions.len() {
+                            Err(Error::InvalidIndices(*max as usize, positions.len()))?;
+                        }
+                    }
+                    let points = points.as_deref();
+                    Geometry::Points(PointCloud {
+                        positions: Positions::F32(select_points(positions, points)),
+                        colors: colors.map(|c| select_points(c, points)),
+                        normals: normals.map(|n| select_points(n, points)),
+                    })
+                }
+                _ => {
+                    let indices = if mode == ::gltf::mesh::Mode::Triangles {
+                        indices
+                    } else {
+                        let list = indices
+                            .into_u32()
+                            .unwrap_or_else(|| (0..positions.len() as u32).collect());
+                        if mode == ::gltf::mesh::Mode::TriangleStrip {
+                            Indices::from_triangle_strip(&list)
+                        } else {
+                            Indices::from_triangle_fan(&list)
+                        }
+                    };
+                    Geometry::Triangles(TriMesh {
+                        positions: Positions::F32(positions),
+                        normals,
+                        tangents,
+                        indices,
+                        colors,
+                        uvs,
+                    })
+                }
+            };
             children.push(Node {
-                geometry: Some(Geometry::Triangles(TriMesh {
-                    positions: Positions::F32(positions),
-                    normals,
-                    tangents,
-                    indices,
-                    colors,
-                    uvs,
-                })),
+                geometry: Some(geometry),
                 material_index: primitive.material().index(),
                 ..Default::default()
             });
+        } else if primitive
+            .extension_value("KHR_draco_mesh_compression")
+            .is_some()
+        {
+            // The vertex data is only available in compressed form, so rather than silently
+            // returning empty geometry, report that the compression is not supported.
+            Err(Error::GltfUnsupportedExtension(
+                "KHR_draco_mesh_compression".to_owned(),
+            ))?;
+        } else {
+            skip("no POSITION attribute");
         }
     }
     Ok(children)
 }
 
+fn select_points<T: Copy>(values: Vec<T>, points: Option<&[u32]>) -> Vec<T> {
+    match points {
+        Some(points) => points.iter().map(|i| values[*i as usize]).collect(),
+        None => values,
+    }
+}
+
 fn material_name(material: &::gltf::material::Material) -> String {
     material.name().map(|s| s.to_string()).unwrap_or(
         material
@@ -269,24 +537,47 @@ fn material_name(material: &::gltf::material::Material) -> String {
 fn parse_material(
     raw_assets: &mut RawAssets,
     path: &Path,
+    document: &::gltf::Document,
     buffers: &[::gltf::buffer::Data],
+    textures: &mut TextureCache,
     material: &::gltf::material::Material,
 ) -> Result<PbrMaterial> {
     let pbr = material.pbr_metallic_roughness();
     let color = pbr.base_color_factor();
     let albedo_texture = if let Some(info) = pbr.base_color_texture() {
-        Some(parse_texture(raw_assets, path, buffers, info.texture())?)
+        Some(parse_texture(
+            raw_assets,
+            path,
+            buffers,
+            textures,
+            info.texture(),
+            info.extension_value("KHR_texture_transform"),
+        )?)
     } else {
         None
     };
     let metallic_roughness_texture = if let Some(info) = pbr.metallic_roughness_texture() {
-        Some(parse_texture(raw_assets, path, buffers, info.texture())?)
+        Some(parse_texture(
+            raw_assets,
+            path,
+            buffers,
+            textures,
+            info.texture(),
+            info.extension_value("KHR_texture_transform"),
+        )?)
     } else {
         None
     };
     let (normal_texture, normal_scale) = if let Some(normal) = material.normal_texture() {
         (
-            Some(parse_texture(raw_assets, path, buffers, normal.texture())?),
+            Some(parse_texture(
+                raw_assets,
+                path,
+                buffers,
+                textures,
+                normal.texture(),
+                normal.extension_value("KHR_texture_transform"),
+            )?),
             normal.scale(),
         )
     } else {
@@ -299,7 +590,9 @@ fn parse_material(
                     raw_assets,
                     path,
                     buffers,
+                    textures,
                     occlusion.texture(),
+                    occlusion.extension_value("KHR_texture_transform"),
                 )?),
                 occlusion.strength(),
             )
@@ -307,22 +600,115 @@ fn parse_material(
             (None, 1.0)
         };
     let emissive_texture = if let Some(info) = material.emissive_texture() {
-        Some(parse_texture(raw_assets, path, buffers, info.texture())?)
+        Some(parse_texture(
+            raw_assets,
+            path,
+            buffers,
+            textures,
+            info.texture(),
+            info.extension_value("KHR_texture_transform"),
+        )?)
     } else {
         None
     };
     let transmission_texture =
         if let Some(Some(info)) = material.transmission().map(|t| t.transmission_texture()) {
-            Some(parse_texture(raw_assets, path, buffers, info.texture())?)
+            Some(parse_texture(
+                raw_assets,
+                path,
+                buffers,
+                textures,
+                info.texture(),
+                info.extension_value("KHR_texture_transform"),
+            )?)
         } else {
             None
         };
+    let (alpha_mode, alpha_cutout) = match material.alpha_mode() {
+        ::gltf::material::AlphaMode::Opaque => (AlphaMode::Opaque, None),
+        ::gltf::material::AlphaMode::Mask => (
+            AlphaMode::Mask,
+            Some(material.alpha_cutoff().unwrap_or(0.5)),
+        ),
+        ::gltf::material::AlphaMode::Blend => (AlphaMode::Blend, None),
+    };
+    let clearcoat = material.extension_value("KHR_materials_clearcoat");
+    let clearcoat_factor = |key: &str| {
+        clearcoat
+            .and_then(|c| c.get(key))
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0) as f32
+    };
+    // Textures referenced from extensions are plain JSON texture info objects
+    let mut parse_extension_texture =
+        |info: Option<&::gltf::json::Value>| -> Result<Option<Texture2D>> {
+            if let Some(index) = info.and_then(|t| t.get("index")).and_then(|i| i.as_u64()) {
+                let texture = document
+                    .textures()
+                    .nth(index as usize)
+                    .ok_or_else(|| Error::GltfCorruptData(path.to_str().unwrap().to_owned()))?;
+                let transform = info
+                    .and_then(|t| t.get("extensions"))
+                    .and_then(|e| e.get("KHR_texture_transform"));
+                Ok(Some(parse_texture(
+                    raw_assets, path, buffers, textures, texture, transform,
+                )?))
+            } else {
+                Ok(None)
+            }
+        };
+    let clearcoat_texture_info = |key: &str| clearcoat.and_then(|c| c.get(key));
+    let clearcoat_texture = parse_extension_texture(clearcoat_texture_info("clearcoatTexture"))?;
+    let clearcoat_roughness_texture =
+        parse_extension_texture(clearcoat_texture_info("clearcoatRoughnessTexture"))?;
+    let clearcoat_normal_texture =
+        parse_extension_texture(clearcoat_texture_info("clearcoatNormalTexture"))?;
+
+    let mut albedo = color;
+    let mut albedo_texture = albedo_texture;
+    let mut metallic = pbr.metallic_factor();
+    let mut roughness = pbr.roughness_factor();
+    let mut metallic_roughness_texture = metallic_roughness_texture;
+    if let Some(specular_glossiness) =
+        material.extension_value("KHR_materials_pbrSpecularGlossiness")
+    {
+        let factors = |key: &str, default: &[f32]| -> Vec<f32> {
+            specular_glossiness
+                .get(key)
+                .and_then(|v| v.as_array())
+                .map(|a| a.iter().map(|v| v.as_f64().unwrap_or(0.0) as f32).collect())
+                .filter(|a: &Vec<f32>| a.len() == default.len())
+                .unwrap_or(default.to_vec())
+        };
+        let diffuse = factors("diffuseFactor", &[1.0; 4]);
+        let diffuse = vec4(diffuse[0], diffuse[1], diffuse[2], diffuse[3]);
+        let specular = factors("specularFactor", &[1.0; 3]);
+        let specular = vec3(specular[0], specular[1], specular[2]);
+        let glossiness = specular_glossiness
+            .get("glossinessFactor")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(1.0) as f32;
+        let (base_color, m) = specular_glossiness_to_metallic(diffuse, specular);
+        albedo = base_color.into();
+        metallic = m;
+        roughness = 1.0 - glossiness;
+        albedo_texture = parse_extension_texture(specular_glossiness.get("diffuseTexture"))?;
+        metallic_roughness_texture = parse_extension_texture(
+            specular_glossiness.get("specularGlossinessTexture"),
+        )?
+        .map(|mut texture| {
+            convert_specular_glossiness_texture(&mut texture, diffuse, specular, glossiness);
+            (metallic, roughness) = (1.0, 1.0);
+            texture
+        });
+    }
+
     Ok(PbrMaterial {
         name: material_name(material),
-        albedo: color.into(),
+        albedo: albedo.into(),
         albedo_texture,
-        metallic: pbr.metallic_factor(),
-        roughness: pbr.roughness_factor(),
+        metallic,
+        roughness,
         metallic_roughness_texture,
         normal_texture,
         normal_scale,
@@ -331,13 +717,21 @@ fn parse_material(
         occlusion_metallic_roughness_texture: None,
         emissive: material.emissive_factor().into(),
         emissive_texture,
+        emissive_strength: material.emissive_strength().unwrap_or(1.0),
         transmission: material
             .transmission()
             .map(|t| t.transmission_factor())
             .unwrap_or(0.0),
         transmission_texture,
         index_of_refraction: material.ior().unwrap_or(1.5),
-        alpha_cutout: material.alpha_cutoff(),
+        clearcoat: clearcoat_factor("clearcoatFactor"),
+        clearcoat_texture,
+        clearcoat_roughness: clearcoat_factor("clearcoatRoughnessFactor"),
+        clearcoat_roughness_texture,
+        clearcoat_normal_texture,
+        alpha_mode,
+        alpha_cutout,
+        double_sided: material.double_sided(),
         lighting_model: LightingModel::Cook(
             NormalDistributionFunction::TrowbridgeReitzGGX,
             GeometryFunction::SmithSchlickGGX,
@@ -355,34 +749,266 @@ impl Into<Wrapping> for ::gltf::texture::WrappingMode {
     }
 }
 
-fn parse_texture<'a>(
+///
+/// Converts the diffuse and specular color of the specular-glossiness workflow to the base color and metallic value of the metallic-roughness workflow.
+/// Uses the approximate conversion from the glTF sample viewer, which solves for the metallic value that gives the same
+/// perceived brightness of the diffuse and specular reflection, assuming a specular reflectance of 0.04 for dielectrics.
+///
+fn specular_glossiness_to_metallic(diffuse: Vec4, specular: Vec3) -> (Vec4, f32) {
+    const DIELECTRIC_SPECULAR: f32 = 0.04;
+    const EPSILON: f32 = 1e-6;
+    let brightness = |c: Vec3| (0.299 * c.x * c.x + 0.587 * c.y * c.y + 0.114 * c.z * c.z).sqrt();
+    let one_minus_specular_strength = 1.0 - specular.x.max(specular.y).max(specular.z);
+    let diffuse_brightness = brightness(diffuse.truncate());
+    let specular_brightness = brightness(specular);
+
+    let metallic = if specular_brightness < DIELECTRIC_SPECULAR {
+        0.0
+    } else {
+        let a = DIELECTRIC_SPECULAR;
+        let b = diffuse_brightness * one_minus_specular_strength / (1.0 - a) + specular_brightness
+            - 2.0 * a;
+        let c = a - specular_brightness;
+        let d = (b * b - 4.0 * a * c).max(0.0);
+        ((-b + d.sqrt()) / (2.0 * a)).clamp(0.0, 1.0)
+    };
+
+    let from_diffuse = diffuse.truncate() * one_minus_specular_strength
+        / (1.0 - DIELECTRIC_SPECULAR)
+        / (1.0 - metallic).max(EPSILON);
+    let from_specular = (specular - vec3(1.0, 1.0, 1.0) * DIELECTRIC_SPECULAR * (1.0 - metallic))
+        / metallic.max(EPSILON);
+    let base_color = from_diffuse.lerp(from_specular, metallic * metallic);
+    (
+        base_color.map(|c| c.clamp(0.0, 1.0)).extend(diffuse.w),
+        metallic,
+    )
+}
+
+///
+/// Converts a specular-glossiness texture, with the specular color in sRGB in the red, green and blue channels and the glossiness in the alpha channel,
+/// into a metallic-roughness texture with the metallic value in the blue channel and the roughness in the green channel.
+/// The factors are applied to each texel, so the metallic and roughness factors of the material should be one.
+/// The metallic value is computed from the diffuse factor, since the diffuse texture might not have the same size.
+///
+fn convert_specular_glossiness_texture(
+    texture: &mut Texture2D,
+    diffuse: Vec4,
+    specular: Vec3,
+    glossiness: f32,
+) {
+    let convert = |texel: [u8; 4]| -> [u8; 4] {
+        let linear = Srgba::from(texel).to_linear_srgb();
+        let texel_specular = vec3(
+            linear.x * specular.x,
+            linear.y * specular.y,
+            linear.z * specular.z,
+        );
+        let (_, metallic) = specular_glossiness_to_metallic(diffuse, texel_specular);
+        let roughness = 1.0 - linear.w * glossiness;
+        [
+            0,
+            (roughness * 255.0).round() as u8,
+            (metallic * 255.0).round() as u8,
+            255,
+        ]
+    };
+    texture.data = match &texture.data {
+        TextureData::RgbaU8(data) => {
+            TextureData::RgbaU8(data.iter().map(|t| convert(*t)).collect())
+        }
+        TextureData::RgbU8(data) => TextureData::RgbaU8(
+            data.iter()
+                .map(|t| convert([t[0], t[1], t[2], 255]))
+                .collect(),
+        ),
+        _ => return,
+    };
+}
+
+///
+/// Parses the `KHR_texture_transform` extension of a texture reference, missing values default to the identity transform.
+/// The optional `texCoord` override is not supported.
+///
+fn parse_texture_transform(transform: Option<&::gltf::json::Value>) -> TextureTransform {
+    let Some(transform) = transform else {
+        return TextureTransform::default();
+    };
+    let vector = |key: &str, default: Vec2| {
+        transform
+            .get(key)
+            .and_then(|v| v.as_array())
+            .filter(|a| a.len() == 2)
+            .map(|a| {
+                vec2(
+                    a[0].as_f64().unwrap_or(default.x as f64) as f32,
+                    a[1].as_f64().unwrap_or(default.y as f64) as f32,
+                )
+            })
+            .unwrap_or(default)
+    };
+    TextureTransform {
+        offset: vector("offset", vec2(0.0, 0.0)),
+        scale: vector("scale", vec2(1.0, 1.0)),
+        rotation: transform
+            .get("rotation")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0) as f32,
+    }
+}
+
+///
+/// The decoded images keyed by [image_key]. Images are often used by several textures, so each image is only decoded once.
+///
+/// To avoid keeping a copy of every image until all materials are built, the number of uses of each image is counted up front.
+/// All but the last use get a copy of the cached image and the last use moves it out of the cache,
+/// so an image shared by several textures is copied once for each additional use, and an image used once is never copied.
+///
+struct TextureCache {
+    textures: HashMap<String, Texture2D>,
+    uses: HashMap<String, usize>,
+}
+
+impl TextureCache {
+    fn new(
+        document: &::gltf::Document,
+        path: &Path,
+        mut textures: HashMap<String, Texture2D>,
+    ) -> Self {
+        let mut uses = HashMap::new();
+        for material in document.materials() {
+            for index in material_texture_indices(&material) {
+                if let Some(texture) = document.textures().nth(index) {
+                    *uses
+                        .entry(image_key(path, &texture.source().source()))
+                        .or_insert(0) += 1;
+                }
+            }
+        }
+        textures.retain(|key, _| uses.contains_key(key));
+        Self { textures, uses }
+    }
+
+    ///
+    /// Returns the image with the given key, which is decoded using the given function if it is not already cached.
+    ///
+    fn get(&mut self, key: &str, decode: impl FnOnce() -> Result<Texture2D>) -> Result<Texture2D> {
+        let last_use = match self.uses.get_mut(key) {
+            Some(uses) if *uses > 1 => {
+                *uses -= 1;
+                false
+            }
+            Some(_) => {
+                self.uses.remove(key);
+                true
+            }
+            // Not counted, so keep it in case it is used again
+            None => false,
+        };
+        if last_use {
+            self.textures.remove(key).map_or_else(decode, Ok)
+        } else if let Some(texture) = self.textures.get(key) {
+            Ok(texture.clone())
+        } else {
+            let texture = decode()?;
+            self.textures.insert(key.to_owned(), texture.clone());
+            Ok(texture)
+        }
+    }
+}
+
+///
+/// Returns the indices of all of the textures used by the material, including textures referenced from extensions,
+/// with an index for each use. This must match the textures parsed in [parse_material].
+///
+fn material_texture_indices(material: &::gltf::material::Material) -> Vec<usize> {
+    let pbr = material.pbr_metallic_roughness();
+    let mut indices = Vec::new();
+    indices.extend(pbr.base_color_texture().map(|t| t.texture().index()));
+    indices.extend(
+        pbr.metallic_roughness_texture()
+            .map(|t| t.texture().index()),
+    );
+    indices.extend(material.normal_texture().map(|t| t.texture().index()));
+    indices.extend(material.occlusion_texture().map(|t| t.texture().index()));
+    indices.extend(material.emissive_texture().map(|t| t.texture().index()));
+    indices.extend(
+        material
+            .transmission()
+            .and_then(|t| t.transmission_texture())
+            .map(|t| t.texture().index()),
+    );
+    for (extension, keys) in [
+        (
+            "KHR_materials_clearcoat",
+            &[
+                "clearcoatTexture",
+                "clearcoatRoughnessTexture",
+                "clearcoatNormalTexture",
+            ][..],
+        ),
+        (
+            "KHR_materials_pbrSpecularGlossiness",
+            &["diffuseTexture", "specularGlossinessTexture"][..],
+        ),
+    ] {
+        if let Some(value) = material.extension_value(extension) {
+            indices.extend(keys.iter().filter_map(|key| {
+                value
+                    .get(key)
+                    .and_then(|t| t.get("index"))
+                    .and_then(|i| i.as_u64())
+                    .map(|i| i as usize)
+            }));
+        }
+    }
+    indices
+}
+
+///
+/// Returns a key which identifies the image source within the document.
+///
+fn image_key(path: &Path, source: &::gltf::image::Source) -> String {
+    match source {
+        ::gltf::image::Source::Uri { uri, .. } if uri.starts_with("data:") => (*uri).to_owned(),
+        ::gltf::image::Source::Uri { uri, .. } => path.join(uri).to_str().unwrap().to_owned(),
+        ::gltf::image::Source::View { view, .. } => format!("buffer view {}", view.index()),
+    }
+}
+
+fn decode_image(
     raw_assets: &mut RawAssets,
-    path: &Path,
     buffers: &[::gltf::buffer::Data],
-    gltf_texture: ::gltf::texture::Texture,
+    key: &str,
+    source: ::gltf::image::Source,
 ) -> Result<Texture2D> {
-    let gltf_image = gltf_texture.source();
-    let gltf_source = gltf_image.source();
-    let mut tex: Texture2D = match gltf_source {
-        ::gltf::image::Source::Uri { uri, .. } => {
-            if uri.starts_with("data:") {
-                raw_assets.deserialize(uri)?
-            } else {
-                raw_assets.deserialize(path.join(uri))?
-            }
-        }
+    match source {
+        ::gltf::image::Source::Uri { .. } => raw_assets.deserialize(key),
         ::gltf::image::Source::View { view, .. } => {
-            if view.stride() != None {
-                unimplemented!();
-            }
+            // The byte stride only applies to vertex attributes, an encoded image is always tightly packed.
             #[allow(unused_variables)]
             let buffer = &buffers[view.buffer().index()];
             #[cfg(not(feature = "image"))]
             return Err(Error::FeatureMissing("image".to_string()));
             #[cfg(feature = "image")]
-            super::img::deserialize_img("", &buffer[view.offset()..view.offset() + view.length()])?
+            super::img::deserialize_img("", &buffer[view.offset()..view.offset() + view.length()])
         }
-    };
+    }
+}
+
+fn parse_texture<'a>(
+    raw_assets: &mut RawAssets,
+    path: &Path,
+    buffers: &[::gltf::buffer::Data],
+    textures: &mut TextureCache,
+    gltf_texture: ::gltf::texture::Texture,
+    transform: Option<&::gltf::json::Value>,
+) -> Result<Texture2D> {
+    let gltf_source = gltf_texture.source().source();
+    let key = image_key(path, &gltf_source);
+    let mut tex = textures.get(&key, || {
+        decode_image(raw_assets, buffers, &key, gltf_source)
+    })?;
 
     let sampler = gltf_texture.sampler();
     tex.mag_filter = match sampler.mag_filter() {
@@ -425,6 +1051,7 @@ fn parse_texture<'a>(
     };
     tex.wrap_s = sampler.wrap_s().into();
     tex.wrap_t = sampler.wrap_t().into();
+    tex.uv_transform = parse_texture_transform(transform);
 
     Ok(tex)
 }
@@ -434,6 +1061,489 @@ fn parse_transform(transform: ::gltf::scene::Transform) -> Mat4 {
     Mat4::from_cols(c0.into(), c1.into(), c2.into(), c3.into())
 }
 
+///
+/// Serializes the scene into a single self-contained .glb file. The vertex data is stored in the binary chunk
+/// and the textures are encoded as PNG images which are stored in buffer views of the binary chunk.
+/// Textures which are equal are only stored once.
+///
+/// The [Node::animations] are not serialized and textures with floating point data result in an error, since they cannot be encoded as PNG.
+///
+pub fn serialize_glb(scene: &Scene) -> Result<Vec<u8>> {
+    let mut writer = GlbWriter::default();
+    writer.root.asset.generator = Some("three-d-asset".to_owned());
+    for material in scene.materials.iter() {
+        let material = writer.material(material)?;
+        writer.root.push(material);
+    }
+    let mut nodes = Vec::new();
+    for node in scene.children.iter() {
+        nodes.push(writer.node(node, None)?);
+    }
+    let gltf_scene = writer.root.push(json::Scene {
+        extensions: None,
+        extras: Default::default(),
+        name: Some(scene.name.clone()),
+        nodes,
+    });
+    writer.root.scene = Some(gltf_scene);
+    writer.finish()
+}
+
+#[derive(Default)]
+struct GlbWriter<'a> {
+    root: json::Root,
+    bin: Vec<u8>,
+    textures: Vec<(&'a Texture2D, json::Index<json::Texture>)>,
+    images: Vec<(&'a Texture2D, json::Index<json::Image>)>,
+}
+
+impl<'a> GlbWriter<'a> {
+    fn finish(mut self) -> Result<Vec<u8>> {
+        if !self.bin.is_empty() {
+            self.bin.resize(self.bin.len().next_multiple_of(4), 0);
+            self.root.push(json::Buffer {
+                byte_length: USize64::from(self.bin.len()),
+                name: None,
+                uri: None,
+                extensions: None,
+                extras: Default::default(),
+            });
+        }
+        let json = json::serialize::to_vec(&self.root)
+            .map_err(|_| Error::FailedSerialize("glb".to_owned()))?;
+        // The length of the file is stored as 32 bits
+        if 28 + json.len().next_multiple_of(4) + self.bin.len() > u32::MAX as usize {
+            Err(Error::FailedSerialize("glb".to_owned()))?;
+        }
+        let glb = ::gltf::binary::Glb {
+            header: ::gltf::binary::Header {
+                magic: *b"glTF",
+                version: 2,
+                length: 0,
+            },
+            json: json.into(),
+            bin: (!self.bin.is_empty()).then_some(self.bin.into()),
+        };
+        Ok(glb.to_vec()?)
+    }
+
+    fn use_extension(&mut self, name: &str) {
+        if !self.root.extensions_used.iter().any(|e| e == name) {
+            self.root.extensions_used.push(name.to_owned());
+        }
+    }
+
+    fn node(
+        &mut self,
+        node: &'a Node,
+        material_index: Option<usize>,
+    ) -> Result<json::Index<json::Node>> {
+        // The material applies to all of the geometry below the node
+        let material_index = node.material_index.or(material_index);
+        let mesh = match node.geometry {
+            Some(ref geometry) => self.mesh(geometry, material_index)?,
+            None => None,
+        };
+        let mut children = Vec::new();
+        for child in node.children.iter() {
+            children.push(self.node(child, material_index)?);
+        }
+        let matrix: [[f32; 4]; 4] = node.transformation.into();
+        Ok(self.root.push(json::Node {
+            children: (!children.is_empty()).then_some(children),
+            matrix: (node.transformation != Mat4::identity())
+                .then(|| matrix.concat().try_into().unwrap()),
+            mesh,
+            name: Some(node.name.clone()),
+            ..Default::default()
+        }))
+    }
+
+    fn mesh(
+        &mut self,
+        geometry: &Geometry,
+        material_index: Option<usize>,
+    ) -> Result<Option<json::Index<json::Mesh>>> {
+        use json::accessor::{ComponentType, Type};
+        use json::buffer::Target;
+        use json::mesh::Semantic;
+        let material = material_index
+            .map(|i| {
+                if i < self.root.materials.len() {
+                    Ok(json::Index::new(i as u32))
+                } else {
+                    Err(Error::IndexOutOfRange(i, self.root.materials.len()))
+                }
+            })
+            .transpose()?;
+        let (positions, normals, colors) = match geometry {
+            Geometry::Triangles(mesh) => (&mesh.positions, &mesh.normals, &mesh.colors),
+            Geometry::Points(point_cloud) => (
+                &point_cloud.positions,
+                &point_cloud.normals,
+                &point_cloud.colors,
+            ),
+        };
+        // An accessor must contain at least one element
+        if positions.is_empty() {
+            return Ok(None);
+        }
+
+        let mut attributes = std::collections::BTreeMap::new();
+        let positions: Vec<[f32; 3]> = positions.to_f32().into_iter().map(|p| p.into()).collect();
+        let (min, max) = positions
+            .iter()
+            .fold(([f32::MAX; 3], [f32::MIN; 3]), |(min, max), p| {
+                (
+                    [0, 1, 2].map(|i| min[i].min(p[i])),
+                    [0, 1, 2].map(|i| max[i].max(p[i])),
+                )
+            });
+        let mut accessor = self.accessor(
+            &positions,
+            ComponentType::F32,
+            Type::Vec3,
+            Target::ArrayBuffer,
+        );
+        accessor.min = Some(json::Value::from(min.to_vec()));
+        accessor.max = Some(json::Value::from(max.to_vec()));
+        attributes.insert(Valid(Semantic::Positions), self.root.push(accessor));
+        if let Some(normals) = normals {
+            let normals: Vec<[f32; 3]> = normals.iter().map(|n| (*n).into()).collect();
+            let accessor = self.accessor(
+                &normals,
+                ComponentType::F32,
+                Type::Vec3,
+                Target::ArrayBuffer,
+            );
+            attributes.insert(Valid(Semantic::Normals), self.root.push(accessor));
+        }
+        if let Some(colors) = colors {
+            let colors: Vec<[u8; 4]> = colors.iter().map(|c| (*c).into()).collect();
+            let mut accessor =
+                self.accessor(&colors, ComponentType::U8, Type::Vec4, Target::ArrayBuffer);
+            accessor.normalized = true;
+            attributes.insert(Valid(Semantic::Colors(0)), self.root.push(accessor));
+        }
+
+        let (mode, indices) = match geometry {
+            Geometry::Triangles(mesh) => {
+                if let Some(tangents) = &mesh.tangents {
+                    let tangents: Vec<[f32; 4]> = tangents.iter().map(|t| (*t).into()).collect();
+                    let accessor = self.accessor(
+                        &tangents,
+                        ComponentType::F32,
+                        Type::Vec4,
+                        Target::ArrayBuffer,
+                    );
+                    attributes.insert(Valid(Semantic::Tangents), self.root.push(accessor));
+                }
+                if let Some(uvs) = &mesh.uvs {
+                    let uvs: Vec<[f32; 2]> = uvs.iter().map(|uv| (*uv).into()).collect();
+                    let accessor =
+                        self.accessor(&uvs, ComponentType::F32, Type::Vec2, Target::ArrayBuffer);
+                    attributes.insert(Valid(Semantic::TexCoords(0)), self.root.push(accessor));
+                }
+                let target = Target::ElementArrayBuffer;
+                let indices = match &mesh.indices {
+                    Indices::None => None,
+                    Indices::U8(indices) => {
+                        Some(self.accessor(indices, ComponentType::U8, Type::Scalar, target))
+                    }
+                    Indices::U16(indices) => {
+                        Some(self.accessor(indices, ComponentType::U16, Type::Scalar, target))
+                    }
+                    Indices::U32(indices) => {
+                        Some(self.accessor(indices, ComponentType::U32, Type::Scalar, target))
+                    }
+                };
+                (
+                    json::mesh::Mode::Triangles,
+                    indices.map(|accessor| self.root.push(accessor)),
+                )
+            }
+            Geometry::Points(_) => (json::mesh::Mode::Points, None),
+        };
+
+        Ok(Some(self.root.push(json::Mesh {
+            extensions: None,
+            extras: Default::default(),
+            name: None,
+            primitives: vec![json::mesh::Primitive {
+                attributes,
+                extensions: None,
+                extras: Default::default(),
+                indices,
+                material,
+                mode: Valid(mode),
+                targets: None,
+            }],
+            weights: None,
+        })))
+    }
+
+    ///
+    /// Stores the values in a new buffer view and returns an accessor for them.
+    ///
+    fn accessor<T: bytemuck::Pod>(
+        &mut self,
+        values: &[T],
+        component_type: json::accessor::ComponentType,
+        type_: json::accessor::Type,
+        target: json::buffer::Target,
+    ) -> json::Accessor {
+        let buffer_view = self.buffer_view(bytemuck::cast_slice(values), Some(target));
+        json::Accessor {
+            buffer_view: Some(buffer_view),
+            byte_offset: None,
+            count: USize64::from(values.len()),
+            component_type: Valid(json::accessor::GenericComponentType(component_type)),
+            extensions: None,
+            extras: Default::default(),
+            type_: Valid(type_),
+            min: None,
+            max: None,
+            name: None,
+            normalized: false,
+            sparse: None,
+        }
+    }
+
+    fn buffer_view(
+        &mut self,
+        bytes: &[u8],
+        target: Option<json::buffer::Target>,
+    ) -> json::Index<json::buffer::View> {
+        // All of the data is aligned to four bytes, which is enough for any component type
+        self.bin.resize(self.bin.len().next_multiple_of(4), 0);
+        let view = json::buffer::View {
+            buffer: json::Index::new(0),
+            byte_length: USize64::from(bytes.len()),
+            byte_offset: Some(USize64::from(self.bin.len())),
+            byte_stride: None,
+            name: None,
+            target: target.map(Valid),
+            extensions: None,
+            extras: Default::default(),
+        };
+        self.bin.extend_from_slice(bytes);
+        self.root.push(view)
+    }
+
+    fn material(&mut self, material: &'a PbrMaterial) -> Result<json::Material> {
+        let combined_texture = material.occlusion_metallic_roughness_texture.as_ref();
+        let metallic_roughness_texture = material
+            .metallic_roughness_texture
+            .as_ref()
+            .or(combined_texture);
+        let occlusion_texture = material.occlusion_texture.as_ref().or(combined_texture);
+
+        let mut extensions = json::extensions::material::Material::default();
+        let mut has_extensions = false;
+        if material.transmission > 0.0 || material.transmission_texture.is_some() {
+            self.use_extension("KHR_materials_transmission");
+            has_extensions = true;
+            extensions.transmission = Some(json::extensions::material::Transmission {
+                transmission_factor: json::extensions::material::TransmissionFactor(
+                    material.transmission,
+                ),
+                transmission_texture: self.texture_info(material.transmission_texture.as_ref())?,
+                extras: Default::default(),
+            });
+        }
+        if material.index_of_refraction != 1.5 {
+            self.use_extension("KHR_materials_ior");
+            has_extensions = true;
+            extensions.ior = Some(json::extensions::material::Ior {
+                ior: json::extensions::material::IndexOfRefraction(material.index_of_refraction),
+                extras: Default::default(),
+            });
+        }
+        if material.emissive_strength != 1.0 {
+            self.use_extension("KHR_materials_emissive_strength");
+            has_extensions = true;
+            extensions.emissive_strength = Some(json::extensions::material::EmissiveStrength {
+                emissive_strength: json::extensions::material::EmissiveStrengthFactor(
+                    material.emissive_strength,
+                ),
+            });
+        }
+        let clearcoat_textures = [
+            ("clearcoatTexture", &material.clearcoat_texture),
+            (
+                "clearcoatRoughnessTexture",
+                &material.clearcoat_roughness_texture,
+            ),
+            ("clearcoatNormalTexture", &material.clearcoat_normal_texture),
+        ];
+        if material.clearcoat > 0.0 || clearcoat_textures.iter().any(|(_, t)| t.is_some()) {
+            self.use_extension("KHR_materials_clearcoat");
+            has_extensions = true;
+            // Clear coat is not part of the typed extensions, so the JSON is written directly
+            let mut clearcoat = json::Value::Object(Default::default());
+            clearcoat["clearcoatFactor"] = json::Value::from(material.clearcoat);
+            clearcoat["clearcoatRoughnessFactor"] = json::Value::from(material.clearcoat_roughness);
+            for (key, texture) in clearcoat_textures {
+                if let Some(info) = self.texture_info(texture.as_ref())? {
+                    clearcoat[key] = json::serialize::to_value(info)
+                        .map_err(|_| Error::FailedSerialize(material.name.clone()))?;
+                }
+            }
+            extensions
+                .others
+                .insert("KHR_materials_clearcoat".to_owned(), clearcoat);
+        }
+
+        Ok(json::Material {
+            alpha_cutoff: material
+                .alpha_cutout
+                .filter(|_| material.alpha_mode == AlphaMode::Mask)
+                .map(json::material::AlphaCutoff),
+            alpha_mode: Valid(match material.alpha_mode {
+                AlphaMode::Opaque => json::material::AlphaMode::Opaque,
+                AlphaMode::Mask => json::material::AlphaMode::Mask,
+                AlphaMode::Blend => json::material::AlphaMode::Blend,
+            }),
+            double_sided: material.double_sided,
+            name: Some(material.name.clone()),
+            pbr_metallic_roughness: json::material::PbrMetallicRoughness {
+                base_color_factor: json::material::PbrBaseColorFactor(material.albedo.into()),
+                base_color_texture: self.texture_info(material.albedo_texture.as_ref())?,
+                metallic_factor: json::material::StrengthFactor(material.metallic),
+                roughness_factor: json::material::StrengthFactor(material.roughness),
+                metallic_roughness_texture: self.texture_info(metallic_roughness_texture)?,
+                extensions: None,
+                extras: Default::default(),
+            },
+            normal_texture: self
+                .texture_info(material.normal_texture.as_ref())?
+                .map(|info| json::material::NormalTexture {
+                    index: info.index,
+                    scale: material.normal_scale,
+                    tex_coord: 0,
+                    extensions: None,
+                    extras: Default::default(),
+                }),
+            occlusion_texture: self.texture_info(occlusion_texture)?.map(|info| {
+                json::material::OcclusionTexture {
+                    index: info.index,
+                    strength: json::material::StrengthFactor(material.occlusion_strength),
+                    tex_coord: 0,
+                    extensions: None,
+                    extras: Default::default(),
+                }
+            }),
+            emissive_texture: self.texture_info(material.emissive_texture.as_ref())?,
+            emissive_factor: json::material::EmissiveFactor(material.emissive.into()),
+            extensions: has_extensions.then_some(extensions),
+            ..Default::default()
+        })
+    }
+
+    fn texture_info(
+        &mut self,
+        texture: Option<&'a Texture2D>,
+    ) -> Result<Option<json::texture::Info>> {
+        let Some(texture) = texture else {
+            return Ok(None);
+        };
+        let index = match self.textures.iter().find(|(t, _)| t.content_eq(texture)) {
+            Some((_, index)) => *index,
+            None => {
+                let source = self.image(texture)?;
+                let sampler = self.root.push(sampler(texture));
+                let index = self.root.push(json::Texture {
+                    name: Some(texture.name.clone()),
+                    sampler: Some(sampler),
+                    source,
+                    extensions: None,
+                    extras: Default::default(),
+                });
+                self.textures.push((texture, index));
+                index
+            }
+        };
+        let extensions = if texture.uv_transform.is_identity() {
+            None
+        } else {
+            self.use_extension("KHR_texture_transform");
+            let t = &texture.uv_transform;
+            let mut transform = json::Value::Object(Default::default());
+            transform["offset"] = json::Value::from(vec![t.offset.x, t.offset.y]);
+            transform["scale"] = json::Value::from(vec![t.scale.x, t.scale.y]);
+            transform["rotation"] = json::Value::from(t.rotation);
+            let mut extensions = json::extensions::texture::Info::default();
+            extensions
+                .others
+                .insert("KHR_texture_transform".to_owned(), transform);
+            Some(extensions)
+        };
+        Ok(Some(json::texture::Info {
+            index,
+            tex_coord: 0,
+            extensions,
+            extras: Default::default(),
+        }))
+    }
+
+    fn image(&mut self, texture: &'a Texture2D) -> Result<json::Index<json::Image>> {
+        if let Some((_, index)) = self.images.iter().find(|(t, _)| {
+            t.width == texture.width && t.height == texture.height && t.data == texture.data
+        }) {
+            return Ok(*index);
+        }
+        let png = match texture.data {
+            TextureData::RU8(_)
+            | TextureData::RgU8(_)
+            | TextureData::RgbU8(_)
+            | TextureData::RgbaU8(_) => texture.serialize("image.png")?.remove("image.png")?,
+            _ => Err(Error::FailedSerialize(texture.name.clone()))?,
+        };
+        let buffer_view = self.buffer_view(&png, None);
+        let index = self.root.push(json::Image {
+            buffer_view: Some(buffer_view),
+            mime_type: Some(json::image::MimeType("image/png".to_owned())),
+            name: Some(texture.name.clone()),
+            uri: None,
+            extensions: None,
+            extras: Default::default(),
+        });
+        self.images.push((texture, index));
+        Ok(index)
+    }
+}
+
+fn sampler(texture: &Texture2D) -> json::texture::Sampler {
+    use json::texture::{MagFilter, MinFilter, WrappingMode};
+    let wrapping = |wrapping: Wrapping| {
+        Valid(match wrapping {
+            Wrapping::Repeat => WrappingMode::Repeat,
+            Wrapping::MirroredRepeat => WrappingMode::MirroredRepeat,
+            Wrapping::ClampToEdge => WrappingMode::ClampToEdge,
+        })
+    };
+    let mipmap_filter = texture.mipmap.as_ref().map(|m| m.filter);
+    json::texture::Sampler {
+        mag_filter: Some(Valid(match texture.mag_filter {
+            Interpolation::Nearest => MagFilter::Nearest,
+            Interpolation::Linear => MagFilter::Linear,
+        })),
+        min_filter: Some(Valid(match (texture.min_filter, mipmap_filter) {
+            (Interpolation::Nearest, None) => MinFilter::Nearest,
+            (Interpolation::Linear, None) => MinFilter::Linear,
+            (Interpolation::Nearest, Some(Interpolation::Nearest)) => {
+                MinFilter::NearestMipmapNearest
+            }
+            (Interpolation::Linear, Some(Interpolation::Nearest)) => MinFilter::LinearMipmapNearest,
+            (Interpolation::Nearest, Some(Interpolation::Linear)) => MinFilter::NearestMipmapLinear,
+            (Interpolation::Linear, Some(Interpolation::Linear)) => MinFilter::LinearMipmapLinear,
+        })),
+        wrap_s: wrapping(texture.wrap_s),
+        wrap_t: wrapping(texture.wrap_t),
+        ..Default::default()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -500,11 +1610,556 @@ mod test {
         );
     }
 
+    ///
+    /// Packs Cube.gltf and all of its files into a single .glb file.
+    ///
+    fn cube_glb() -> Vec<u8> {
+        let mut json: serde_json::Value =
+            serde_json::from_slice(include_bytes!("../../test_data/Cube.gltf")).unwrap();
+        let mut bin = include_bytes!("../../test_data/Cube.bin").to_vec();
+        json["buffers"][0].as_object_mut().unwrap().remove("uri");
+        for (i, image) in [
+            include_bytes!("../../test_data/Cube_BaseColor.png").as_slice(),
+            include_bytes!("../../test_data/Cube_MetallicRoughness.png").as_slice(),
+        ]
+        .iter()
+        .enumerate()
+        {
+            let views = json["bufferViews"].as_array_mut().unwrap();
+            views.push(serde_json::json!({
+                "buffer": 0, "byteOffset": bin.len(), "byteLength": image.len()
+            }));
+            json["images"][i] = serde_json::json!({
+                "bufferView": views.len() - 1, "mimeType": "image/png"
+            });
+            bin.extend_from_slice(image);
+            bin.resize(bin.len().next_multiple_of(4), 0);
+        }
+        json["buffers"][0]["byteLength"] = bin.len().into();
+        let mut json = serde_json::to_vec(&json).unwrap();
+        json.resize(json.len().next_multiple_of(4), b' ');
+
+        let mut glb = Vec::new();
+        glb.extend_from_slice(b"glTF");
+        glb.extend_from_slice(&2u32.to_le_bytes());
+        glb.extend_from_slice(&(12 + 8 + json.len() as u32 + 8 + bin.len() as u32).to_le_bytes());
+        glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"JSON");
+        glb.extend_from_slice(&json);
+        glb.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"BIN\0");
+        glb.extend_from_slice(&bin);
+        glb
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    pub fn deserialize_self_contained_glb() {
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("model.glb", cube_glb());
+        // Files with the same names as those in Cube.gltf are never used
+        raw_assets.insert("Cube.bin", Vec::new());
+        raw_assets.insert("other/Cube.bin", Vec::new());
+        let model: Model = raw_assets.deserialize("model.glb").unwrap();
+        assert_eq!(model.geometries.len(), 1);
+        assert_eq!(model.materials.len(), 1);
+        assert!(model.materials[0].albedo_texture.is_some());
+        assert!(model.materials[0].metallic_roughness_texture.is_some());
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    pub fn deserialize_gltf_async() {
+        use std::future::Future;
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("model.glb", cube_glb());
+        raw_assets.insert("copy.glb", cube_glb());
+        let scene: Scene = raw_assets.deserialize("model.glb").unwrap();
+
+        // The future only yields on web, so it completes on the first poll
+        let future = std::pin::pin!(raw_assets.deserialize_async::<Scene>("copy.glb"));
+        let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+        let std::task::Poll::Ready(async_scene) = future.poll(&mut context) else {
+            panic!("deserializing should complete immediately on native");
+        };
+        let async_scene = async_scene.unwrap();
+        assert!(async_scene.materials[0].albedo_texture.is_some());
+        assert_eq!(format!("{:?}", async_scene), format!("{:?}", scene));
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    pub fn serialize_glb() {
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("model.glb", cube_glb());
+        let mut scene: Scene = raw_assets.deserialize("model.glb").unwrap();
+        scene.materials[0]
+            .albedo_texture
+            .as_mut()
+            .unwrap()
+            .uv_transform = TextureTransform {
+            offset: vec2(0.5, 0.25),
+            scale: vec2(2.0, 3.0),
+            rotation: 0.5,
+        };
+
+        let glb = scene.to_glb_bytes().unwrap();
+        assert!(::gltf::Gltf::from_slice(&glb)
+            .unwrap()
+            .document
+            .buffers()
+            .all(|b| matches!(b.source(), ::gltf::buffer::Source::Bin)));
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("copy.glb", glb);
+        let copy: Scene = raw_assets.deserialize("copy.glb").unwrap();
+
+        let model = Model::from(scene);
+        let copy = Model::from(copy);
+        assert_eq!(copy.geometries.len(), model.geometries.len());
+        for (a, b) in copy.geometries.iter().zip(model.geometries.iter()) {
+            assert_eq!(a.transformation, b.transformation);
+            assert_eq!(a.material_index, b.material_index);
+            assert_eq!(format!("{:?}", a.geometry), format!("{:?}", b.geometry));
+        }
+        let (a, b) = (&copy.materials[0], &model.materials[0]);
+        assert_eq!(a.name, b.name);
+        assert_eq!(a.albedo, b.albedo);
+        assert_eq!(a.metallic, b.metallic);
+        assert_eq!(a.roughness, b.roughness);
+        let texture_eq = |a: &Option<Texture2D>, b: &Option<Texture2D>| {
+            a.as_ref().unwrap().content_eq(b.as_ref().unwrap())
+        };
+        assert!(texture_eq(&a.albedo_texture, &b.albedo_texture));
+        assert!(texture_eq(
+            &a.metallic_roughness_texture,
+            &b.metallic_roughness_texture
+        ));
+    }
+
+    #[test]
+    pub fn serialize_glb_without_textures() {
+        let mut mesh = TriMesh::cube();
+        mesh.colors = Some(vec![Srgba::new(10, 20, 30, 40); mesh.vertex_count()]);
+        mesh.indices = Indices::U8((0..mesh.vertex_count() as u8).collect());
+        let points = PointCloud {
+            positions: Positions::F32(vec![vec3(1.0, 2.0, 3.0), vec3(-1.0, 0.0, 1.0)]),
+            ..Default::default()
+        };
+        let transformation = Mat4::from_translation(vec3(1.0, 2.0, 3.0)) * Mat4::from_scale(2.0);
+        let scene = Scene {
+            name: "exported".to_owned(),
+            children: vec![Node {
+                name: "parent".to_owned(),
+                transformation,
+                material_index: Some(0),
+                children: vec![
+                    Node {
+                        geometry: Some(Geometry::Triangles(mesh.clone())),
+                        ..Default::default()
+                    },
+                    Node {
+                        geometry: Some(Geometry::Points(points.clone())),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+            materials: vec![PbrMaterial {
+                name: "material".to_owned(),
+                albedo: Srgba::new(255, 0, 0, 102),
+                metallic: 0.25,
+                roughness: 0.75,
+                alpha_mode: AlphaMode::Mask,
+                alpha_cutout: Some(0.5),
+                double_sided: true,
+                transmission: 0.5,
+                index_of_refraction: 1.33,
+                emissive_strength: 2.0,
+                clearcoat: 0.8,
+                clearcoat_roughness: 0.1,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mut raw_assets = scene.serialize("exported.glb").unwrap();
+        let copy: Scene = raw_assets.deserialize("exported.glb").unwrap();
+        assert_eq!(copy.name, "exported");
+        assert!(copy.warnings.is_empty());
+        let material = &copy.materials[0];
+        assert_eq!(material.name, "material");
+        assert_eq!(material.albedo, Srgba::new(255, 0, 0, 102));
+        assert_eq!(material.metallic, 0.25);
+        assert_eq!(material.roughness, 0.75);
+        assert_eq!(material.alpha_mode, AlphaMode::Mask);
+        assert_eq!(material.alpha_cutout, Some(0.5));
+        assert!(material.double_sided);
+        assert_eq!(material.transmission, 0.5);
+        assert_eq!(material.index_of_refraction, 1.33);
+        assert_eq!(material.emissive_strength, 2.0);
+        assert_eq!(material.clearcoat, 0.8);
+        assert_eq!(material.clearcoat_roughness, 0.1);
+
+        let nodes = copy
+            .iter_nodes()
+            .filter_map(|(node, transformation)| {
+                node.geometry
+                    .as_ref()
+                    .map(|g| (g, transformation, node.material_index))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(nodes.len(), 2);
+        let Geometry::Triangles(copied_mesh) = nodes[0].0 else {
+            panic!("expected a triangle mesh");
+        };
+        assert_eq!(copied_mesh.positions.to_f32(), mesh.positions.to_f32());
+        assert_eq!(copied_mesh.normals, mesh.normals);
+        assert_eq!(copied_mesh.tangents, mesh.tangents);
+        assert_eq!(copied_mesh.uvs, mesh.uvs);
+        assert_eq!(copied_mesh.colors, mesh.colors);
+        assert_eq!(copied_mesh.indices, mesh.indices);
+        let Geometry::Points(copied_points) = nodes[1].0 else {
+            panic!("expected a point cloud");
+        };
+        assert_eq!(copied_points.positions.to_f32(), points.positions.to_f32());
+        for (_, t, material_index) in nodes {
+            assert_eq!(t, transformation);
+            assert_eq!(material_index, Some(0));
+        }
+    }
+
+    #[test]
+    pub fn serialize_glb_with_invalid_material_index() {
+        let scene = Scene {
+            children: vec![Node {
+                geometry: Some(Geometry::Triangles(TriMesh::square())),
+                material_index: Some(1),
+                ..Default::default()
+            }],
+            materials: vec![PbrMaterial::default()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            scene.to_glb_bytes(),
+            Err(Error::IndexOutOfRange(1, 1))
+        ));
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    pub fn deserialize_gltf_with_wrong_extension() {
+        // Binary glTF with a .gltf extension
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("model.gltf", cube_glb());
+        let model: Model = raw_assets.deserialize("model.gltf").unwrap();
+        assert_eq!(model.geometries.len(), 1);
+
+        // Text glTF with a .glb extension
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert(
+            "Cube.glb",
+            include_bytes!("../../test_data/Cube.gltf").to_vec(),
+        );
+        let dependencies = dependencies(&raw_assets, &PathBuf::from("Cube.glb"));
+        assert!(dependencies.contains(&PathBuf::from("Cube.bin")));
+        raw_assets.insert(
+            "Cube.bin",
+            include_bytes!("../../test_data/Cube.bin").to_vec(),
+        );
+        raw_assets.insert(
+            "Cube_BaseColor.png",
+            include_bytes!("../../test_data/Cube_BaseColor.png").to_vec(),
+        );
+        raw_assets.insert(
+            "Cube_MetallicRoughness.png",
+            include_bytes!("../../test_data/Cube_MetallicRoughness.png").to_vec(),
+        );
+        let model: Model = raw_assets.deserialize("Cube.glb").unwrap();
+        assert_eq!(model.geometries.len(), 1);
+        assert!(model.materials[0].albedo_texture.is_some());
+    }
+
+    #[test]
+    pub fn deserialize_gltf_with_skipped_primitives() {
+        let mut json: serde_json::Value =
+            serde_json::from_slice(include_bytes!("../../test_data/Cube.gltf")).unwrap();
+        for key in ["materials", "textures", "images", "samplers"] {
+            json.as_object_mut().unwrap().remove(key);
+        }
+        let mut primitive = json["meshes"][0]["primitives"][0].clone();
+        primitive.as_object_mut().unwrap().remove("material");
+        let mut lines = primitive.clone();
+        lines["mode"] = 1.into();
+        json["meshes"][0]["primitives"] = serde_json::json!([primitive, lines]);
+
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("Cube.gltf", serde_json::to_vec(&json).unwrap());
+        raw_assets.insert(
+            "Cube.bin",
+            include_bytes!("../../test_data/Cube.bin").to_vec(),
+        );
+        let scene: Scene = raw_assets.deserialize("Cube.gltf").unwrap();
+        assert_eq!(scene.children[0].children.len(), 1);
+        assert_eq!(
+            scene.warnings,
+            vec!["skipped primitive 1 of mesh Cube: unsupported mode Lines".to_owned()]
+        );
+
+        let scene: Scene = crate::io::load_and_deserialize("test_data/Cube.gltf").unwrap();
+        assert!(scene.warnings.is_empty());
+    }
+
+    #[test]
+    pub fn deserialize_gltf_with_primitive_modes() {
+        let mut json: serde_json::Value =
+            serde_json::from_slice(include_bytes!("../../test_data/Cube.gltf")).unwrap();
+        for key in ["materials", "textures", "images", "samplers"] {
+            json.as_object_mut().unwrap().remove(key);
+        }
+        let mut primitive = json["meshes"][0]["primitives"][0].clone();
+        primitive.as_object_mut().unwrap().remove("material");
+        let mut primitives = Vec::new();
+        for mode in [4, 5, 6, 0] {
+            let mut p = primitive.clone();
+            p["mode"] = mode.into();
+            primitives.push(p);
+        }
+        // A non-indexed triangle strip
+        primitive.as_object_mut().unwrap().remove("indices");
+        primitive["mode"] = 5.into();
+        primitives.push(primitive);
+        json["meshes"][0]["primitives"] = primitives.into();
+
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("Cube.gltf", serde_json::to_vec(&json).unwrap());
+        raw_assets.insert(
+            "Cube.bin",
+            include_bytes!("../../test_data/Cube.bin").to_vec(),
+        );
+        let scene: Scene = raw_assets.deserialize("Cube.gltf").unwrap();
+        assert!(scene.warnings.is_empty());
+        let geometries = scene.children[0]
+            .children
+            .iter()
+            .map(|n| n.geometry.as_ref().unwrap())
+            .collect::<Vec<_>>();
+        let Geometry::Triangles(list) = geometries[0] else {
+            panic!("expected triangles")
+        };
+        let list_indices = list.indices.to_u32().unwrap();
+        let Geometry::Triangles(strip) = geometries[1] else {
+            panic!("expected triangles")
+        };
+        assert_eq!(strip.indices, Indices::from_triangle_strip(&list_indices));
+        let Geometry::Triangles(fan) = geometries[2] else {
+            panic!("expected triangles")
+        };
+        assert_eq!(fan.indices, Indices::from_triangle_fan(&list_indices));
+        let Geometry::Points(points) = geometries[3] else {
+            panic!("expected points")
+        };
+        assert_eq!(points.positions.len(), list_indices.len());
+        assert_eq!(
+            points.positions.to_f32()[5],
+            list.positions.to_f32()[list_indices[5] as usize]
+        );
+        assert_eq!(points.normals.as_ref().unwrap().len(), list_indices.len());
+        let Geometry::Triangles(strip) = geometries[4] else {
+            panic!("expected triangles")
+        };
+        assert_eq!(strip.triangle_count(), list.vertex_count() - 2);
+        strip.validate().unwrap();
+    }
+
+    #[test]
+    pub fn deserialize_gltf_with_missing_files() {
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert(
+            "data/Cube.gltf",
+            include_bytes!("../../test_data/Cube.gltf").to_vec(),
+        );
+        raw_assets.insert(
+            "data/Cube_BaseColor.png",
+            include_bytes!("../../test_data/Cube_BaseColor.png").to_vec(),
+        );
+        let error = raw_assets.deserialize::<Model>("Cube.gltf").unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("data/Cube.bin, data/Cube_MetallicRoughness.png"));
+        assert!(matches!(
+            error,
+            Error::GltfMissingFiles(path, files)
+                if path == "data/Cube.gltf" && files == ["data/Cube.bin", "data/Cube_MetallicRoughness.png"]
+        ));
+    }
+
     #[test]
     pub fn deserialize_gltf_with_data_url() {
         let model: Model = crate::io::load_and_deserialize("test_data/data_url.gltf").unwrap();
         assert_eq!(model.geometries.len(), 1);
         assert_eq!(model.materials.len(), 1);
+        assert!(model.materials[0].double_sided);
+    }
+
+    #[test]
+    pub fn deserialize_gltf_with_emissive_strength() {
+        let model: Model =
+            crate::io::load_and_deserialize("test_data/EmissiveStrength.gltf").unwrap();
+        assert_eq!(model.materials.len(), 1);
+        assert_eq!(model.materials[0].emissive_strength, 4.0);
+
+        let model: Model = crate::io::load_and_deserialize("test_data/Cube.gltf").unwrap();
+        assert_eq!(model.materials[0].emissive_strength, 1.0);
+    }
+
+    #[test]
+    pub fn deserialize_gltf_with_clearcoat() {
+        let model: Model = crate::io::load_and_deserialize("test_data/Clearcoat.gltf").unwrap();
+        assert_eq!(model.materials.len(), 1);
+        assert_eq!(model.materials[0].clearcoat, 1.0);
+        assert_eq!(model.materials[0].clearcoat_roughness, 0.25);
+        assert!(model.materials[0].clearcoat_texture.is_none());
+
+        let model: Model = crate::io::load_and_deserialize("test_data/Cube.gltf").unwrap();
+        assert_eq!(model.materials[0].clearcoat, 0.0);
+    }
+
+    #[test]
+    pub fn deserialize_gltf_with_specular_glossiness() {
+        let model: Model =
+            crate::io::load_and_deserialize("test_data/SpecularGlossiness.gltf").unwrap();
+        assert_eq!(model.materials.len(), 3);
+        let plastic = &model.materials[0];
+        assert_eq!(plastic.metallic, 0.0);
+        assert_eq!(plastic.roughness, 0.25);
+        assert_eq!(plastic.albedo, Srgba::new(204, 0, 0, 255));
+        let gold = &model.materials[1];
+        assert!(gold.metallic > 0.99);
+        assert_eq!(gold.roughness, 0.0);
+        assert_eq!(gold.albedo, Srgba::new(254, 195, 85, 255));
+        let textured = &model.materials[2];
+        assert!(textured.albedo_texture.is_some());
+        assert_eq!((textured.metallic, textured.roughness), (1.0, 1.0));
+        let texture = textured.metallic_roughness_texture.as_ref().unwrap();
+        let TextureData::RgbaU8(data) = &texture.data else {
+            panic!("expected RgbaU8 data");
+        };
+        // The black texel is a dielectric and the saturated texels are metals, all with a glossiness of 0.5
+        assert_eq!(data[0], [0, 128, 0, 255]);
+        assert_eq!(data[1], [0, 128, 255, 255]);
+    }
+
+    #[test]
+    pub fn deserialize_gltf_with_texture_transform() {
+        let model: Model =
+            crate::io::load_and_deserialize("test_data/TextureTransform.gltf").unwrap();
+        let material = &model.materials[0];
+        let transform = material.albedo_texture.as_ref().unwrap().uv_transform;
+        assert_eq!(transform.offset, vec2(0.5, 0.0));
+        assert_eq!(transform.scale, vec2(2.0, 2.0));
+        assert_eq!(transform.rotation, 1.5);
+        assert!(material
+            .normal_texture
+            .as_ref()
+            .unwrap()
+            .uv_transform
+            .is_identity());
+    }
+
+    #[test]
+    pub fn deserialize_gltf_with_shared_texture() {
+        let model: Model = crate::io::load_and_deserialize("test_data/SharedTexture.gltf").unwrap();
+        assert_eq!(model.materials.len(), 2);
+        let nearest = model.materials[0].albedo_texture.as_ref().unwrap();
+        let linear = model.materials[1].albedo_texture.as_ref().unwrap();
+        let emissive = model.materials[1].emissive_texture.as_ref().unwrap();
+        assert_eq!(nearest.data, linear.data);
+        assert_eq!(linear, emissive);
+        assert_eq!(nearest.mag_filter, Interpolation::Nearest);
+        assert_eq!(linear.mag_filter, Interpolation::Linear);
+    }
+
+    #[test]
+    pub fn texture_cache() {
+        let mut raw_assets = crate::io::load(&["test_data/SharedTexture.gltf"]).unwrap();
+        let (document, _) =
+            parse_document(raw_assets.remove("SharedTexture.gltf").unwrap()).unwrap();
+        let mut cache = TextureCache::new(&document, Path::new("test_data"), HashMap::new());
+        let key = "test_data/test.png";
+        assert_eq!(cache.uses.get(key), Some(&3));
+
+        let mut decode_count = 0;
+        let mut decode = || {
+            decode_count += 1;
+            Ok(Texture2D::default())
+        };
+        cache.get(key, &mut decode).unwrap();
+        cache.get(key, &mut decode).unwrap();
+        assert!(cache.textures.contains_key(key));
+        // The last use moves the image out of the cache instead of copying it
+        cache.get(key, &mut decode).unwrap();
+        assert!(cache.textures.is_empty());
+        assert!(cache.uses.is_empty());
+        assert_eq!(decode_count, 1);
+    }
+
+    #[test]
+    pub fn deserialize_gltf_with_alpha_modes() {
+        let model: Model = crate::io::load_and_deserialize("test_data/AlphaModes.gltf").unwrap();
+        assert_eq!(model.materials.len(), 3);
+        assert_eq!(model.materials[0].alpha_mode, AlphaMode::Opaque);
+        assert_eq!(model.materials[0].alpha_cutout, None);
+        assert!(!model.materials[0].double_sided);
+        assert_eq!(model.materials[1].alpha_mode, AlphaMode::Mask);
+        assert_eq!(model.materials[1].alpha_cutout, Some(0.3));
+        assert_eq!(model.materials[2].alpha_mode, AlphaMode::Blend);
+        assert_eq!(model.materials[2].alpha_cutout, None);
+    }
+
+    #[test]
+    pub fn deserialize_gltf_with_draco_compression() {
+        let result: crate::Result<Model> = crate::io::load_and_deserialize("test_data/Draco.gltf");
+        assert!(matches!(
+            result,
+            Err(crate::Error::GltfUnsupportedExtension(_))
+        ));
+    }
+
+    #[test]
+    pub fn deserialize_gltf_with_multiple_scenes() {
+        let scene: Scene =
+            crate::io::load_and_deserialize("test_data/MultipleScenes.gltf").unwrap();
+        assert_eq!(scene.name, "second");
+        assert_eq!(scene.children.len(), 2);
+
+        let scenes: Vec<Scene> =
+            crate::io::load_and_deserialize("test_data/MultipleScenes.gltf").unwrap();
+        assert_eq!(scenes.len(), 2);
+        assert_eq!(scenes[0].name, "first");
+        assert_eq!(scenes[0].children.len(), 1);
+        assert_eq!(scenes[0].children[0].name, "a");
+        assert_eq!(scenes[1].name, "second");
+        assert_eq!(scenes[1].children[1].name, "c");
+    }
+
+    #[test]
+    pub fn deserialize_gltf_with_interleaved_buffer() {
+        let model: Model =
+            crate::io::load_and_deserialize("test_data/InterleavedTriangle.gltf").unwrap();
+        assert_eq!(model.geometries.len(), 1);
+        if let crate::geometry::Geometry::Triangles(mesh) = &model.geometries[0].geometry {
+            assert_eq!(
+                mesh.positions.to_f32(),
+                vec![
+                    vec3(0.0, 0.0, 0.0),
+                    vec3(1.0, 0.0, 0.0),
+                    vec3(0.0, 1.0, 0.0)
+                ]
+            );
+            assert_eq!(mesh.normals, Some(vec![vec3(0.0, 0.0, 1.0); 3]));
+        } else {
+            panic!("expected a triangle mesh");
+        }
     }
 
     #[test]
@@ -515,6 +2170,10 @@ mod test {
         assert_eq!(model.materials.len(), 0);
         assert_eq!(model.geometries[0].animations.len(), 1);
         let animation = &model.geometries[0].animations[0];
+        assert_eq!(
+            animation.key_frames[0].1.interpolation,
+            AnimationInterpolation::Linear
+        );
         assert_eq!(animation.transformation(0.0), Mat4::identity());
         assert_eq!(
             animation.transformation(0.25),
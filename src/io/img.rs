@@ -1,4 +1,8 @@
-use crate::{io::RawAssets, texture::*, Error, Result};
+use crate::{
+    io::{ImageSerializeOptions, RawAssets},
+    texture::*,
+    Error, Result,
+};
 use image::*;
 use std::io::Cursor;
 use std::path::Path;
@@ -15,9 +19,14 @@ pub fn deserialize_img(path: impl AsRef<Path>, bytes: &[u8]) -> Result<Texture2D
         .expect("Cursor io never fails");
 
     if reader.format().is_none() {
-        reader.set_format(ImageFormat::from_path(path)?);
+        reader.set_format(
+            ImageFormat::from_path(path.as_ref())
+                .map_err(|e| Error::Image(path.as_ref().to_str().unwrap().to_owned(), e))?,
+        );
     }
-    let img: DynamicImage = reader.decode()?;
+    let img: DynamicImage = reader
+        .decode()
+        .map_err(|e| Error::Image(path.as_ref().to_str().unwrap().to_owned(), e))?;
     let width = img.width();
     let height = img.height();
     let data = match img {
@@ -40,12 +49,53 @@ pub fn deserialize_img(path: impl AsRef<Path>, bytes: &[u8]) -> Result<Texture2D
                 .map(|c| [c[0], c[1], c[2], c[3]])
                 .collect::<Vec<_>>(),
         ),
+        // 16 bit images are normalized to the [0..1] range and stored as half floats, which have 11 bits of precision.
+        DynamicImage::ImageLuma16(img) => {
+            TextureData::RF16(img.into_raw().into_iter().map(normalize_u16).collect())
+        }
+        DynamicImage::ImageLumaA16(img) => TextureData::RgF16(
+            img.into_raw()
+                .chunks(2)
+                .map(|c| [normalize_u16(c[0]), normalize_u16(c[1])])
+                .collect::<Vec<_>>(),
+        ),
+        DynamicImage::ImageRgb16(img) => TextureData::RgbF16(
+            img.into_raw()
+                .chunks(3)
+                .map(|c| {
+                    [
+                        normalize_u16(c[0]),
+                        normalize_u16(c[1]),
+                        normalize_u16(c[2]),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        ),
+        DynamicImage::ImageRgba16(img) => TextureData::RgbaF16(
+            img.into_raw()
+                .chunks(4)
+                .map(|c| {
+                    [
+                        normalize_u16(c[0]),
+                        normalize_u16(c[1]),
+                        normalize_u16(c[2]),
+                        normalize_u16(c[3]),
+                    ]
+                })
+                .collect::<Vec<_>>(),
+        ),
         DynamicImage::ImageRgb32F(img) => TextureData::RgbF32(
             img.into_raw()
                 .chunks(3)
                 .map(|c| [c[0], c[1], c[2]])
                 .collect::<Vec<_>>(),
         ),
+        DynamicImage::ImageRgba32F(img) => TextureData::RgbaF32(
+            img.into_raw()
+                .chunks(4)
+                .map(|c| [c[0], c[1], c[2], c[3]])
+                .collect::<Vec<_>>(),
+        ),
         _ => unimplemented!(),
     };
     Ok(Texture2D {
@@ -57,6 +107,10 @@ pub fn deserialize_img(path: impl AsRef<Path>, bytes: &[u8]) -> Result<Texture2D
     })
 }
 
+fn normalize_u16(value: u16) -> half::f16 {
+    half::f16::from_f32(value as f32 / u16::MAX as f32)
+}
+
 #[cfg(feature = "svg")]
 pub fn deserialize_svg(path: impl AsRef<Path>, bytes: &[u8]) -> Result<Texture2D> {
     use cgmath::num_traits::ToPrimitive;
@@ -96,7 +150,11 @@ pub fn deserialize_svg(path: impl AsRef<Path>, bytes: &[u8]) -> Result<Texture2D
     })
 }
 
-pub fn serialize_img(tex: &Texture2D, path: &Path) -> Result<RawAssets> {
+pub fn serialize_img(
+    tex: &Texture2D,
+    path: &Path,
+    options: ImageSerializeOptions,
+) -> Result<RawAssets> {
     #![allow(unreachable_code)]
     #![allow(unused_variables)]
     let format: ImageFormat = match path.extension().unwrap().to_str().unwrap() {
@@ -184,7 +242,31 @@ pub fn serialize_img(tex: &Texture2D, path: &Path) -> Result<RawAssets> {
         _ => unimplemented!(),
     };
     let mut bytes: Vec<u8> = Vec::new();
-    img.write_to(&mut Cursor::new(&mut bytes), format)?;
+    match format {
+        #[cfg(feature = "jpeg")]
+        ImageFormat::Jpeg => img.write_with_encoder(codecs::jpeg::JpegEncoder::new_with_quality(
+            &mut bytes,
+            options.jpeg_quality.clamp(1, 100),
+        )),
+        #[cfg(feature = "png")]
+        ImageFormat::Png => {
+            use crate::io::PngCompression;
+            use codecs::png::{CompressionType, FilterType, PngEncoder};
+            let compression = match options.png_compression {
+                PngCompression::Uncompressed => CompressionType::Uncompressed,
+                PngCompression::Fast => CompressionType::Fast,
+                PngCompression::Default => CompressionType::Default,
+                PngCompression::Best => CompressionType::Best,
+            };
+            img.write_with_encoder(PngEncoder::new_with_quality(
+                &mut bytes,
+                compression,
+                FilterType::Adaptive,
+            ))
+        }
+        _ => img.write_to(&mut Cursor::new(&mut bytes), format),
+    }
+    .map_err(|e| Error::Image(path.to_str().unwrap().to_owned(), e))?;
     let mut raw_assets = RawAssets::new();
     raw_assets.insert(path, bytes);
     Ok(raw_assets)
@@ -258,6 +340,86 @@ mod test {
         test_deserialize("png");
     }
 
+    #[cfg(feature = "png")]
+    #[test]
+    pub fn png_16_bit() {
+        use half::f16;
+        use image::{ImageBuffer, Luma, Rgba};
+        let mut bytes = Vec::new();
+        ImageBuffer::<Rgba<u16>, _>::from_raw(2, 1, vec![0, 65535, 32768, 65535, 65535, 0, 0, 0])
+            .unwrap()
+            .write_to(
+                &mut std::io::Cursor::new(&mut bytes),
+                image::ImageFormat::Png,
+            )
+            .unwrap();
+        let tex = super::deserialize_img("test.png", &bytes).unwrap();
+        assert_eq!((tex.width, tex.height), (2, 1));
+        assert!(
+            tex.data
+                == crate::TextureData::RgbaF16(vec![
+                    [f16::ZERO, f16::ONE, f16::from_f32(0.5), f16::ONE],
+                    [f16::ONE, f16::ZERO, f16::ZERO, f16::ZERO],
+                ])
+        );
+
+        let mut bytes = Vec::new();
+        ImageBuffer::<Luma<u16>, _>::from_raw(1, 1, vec![16384])
+            .unwrap()
+            .write_to(
+                &mut std::io::Cursor::new(&mut bytes),
+                image::ImageFormat::Png,
+            )
+            .unwrap();
+        let tex = super::deserialize_img("test.png", &bytes).unwrap();
+        assert!(tex.data == crate::TextureData::RF16(vec![f16::from_f32(0.25)]));
+    }
+
+    #[cfg(all(feature = "jpeg", feature = "png"))]
+    #[test]
+    pub fn serialize_with_options() {
+        use crate::io::{ImageSerializeOptions, PngCompression};
+        let tex = crate::Texture2D {
+            data: crate::TextureData::RgbaU8(
+                (0..64 * 64)
+                    .map(|i| [(i % 64) as u8 * 4, (i / 64) as u8 * 4, 128, 255])
+                    .collect(),
+            ),
+            width: 64,
+            height: 64,
+            ..Default::default()
+        };
+        let size = |path: &str, options| {
+            tex.serialize_with_options(path, options)
+                .unwrap()
+                .get(path)
+                .unwrap()
+                .len()
+        };
+        let low = ImageSerializeOptions {
+            jpeg_quality: 10,
+            ..Default::default()
+        };
+        let high = ImageSerializeOptions {
+            jpeg_quality: 100,
+            ..Default::default()
+        };
+        assert!(size("test.jpg", low) < size("test.jpg", high));
+
+        let uncompressed = ImageSerializeOptions {
+            png_compression: PngCompression::Uncompressed,
+            ..Default::default()
+        };
+        let best = ImageSerializeOptions {
+            png_compression: PngCompression::Best,
+            ..Default::default()
+        };
+        assert!(size("test.png", best) < size("test.png", uncompressed));
+        let mut raw_assets = tex.serialize_with_options("test.png", best).unwrap();
+        let loaded: crate::Texture2D = raw_assets.deserialize("test.png").unwrap();
+        assert_eq!(loaded.data, tex.data);
+    }
+
     #[cfg(feature = "jpeg")]
     #[test]
     pub fn jpeg() {
@@ -0,0 +1,239 @@
+use crate::geometry::{Geometry, PointCloud, Positions};
+use crate::prelude::*;
+use crate::{io::RawAssets, Error, Node, Result, Scene};
+use std::path::PathBuf;
+
+///
+/// Deserialize a loaded .las file into a [Scene] with a single [PointCloud].
+///
+/// The scale and offset specified in the header are applied to the points, so the positions are in the
+/// coordinate system of the file, which is often a geographic coordinate system with large coordinates.
+/// To avoid loss of precision, the positions are therefore returned as [Positions::F64].
+///
+/// The colors are read from the RGB values if the point format contains them, otherwise the colors
+/// are based on the classification of the points (ground is brown, vegetation green, buildings red etc.).
+///
+/// **Note:** Compressed .laz files are not supported.
+///
+pub fn deserialize_las(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<Scene> {
+    let name = path.to_str().unwrap().to_string();
+    let bytes = raw_assets.remove(path)?;
+    if bytes.len() < 227 || &bytes[0..4] != b"LASF" {
+        Err(Error::LasCorruptData(name.clone()))?;
+    }
+    let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+    let u32_at = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+    let i32_at = |i: usize| i32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+    let f64_at = |i: usize| f64::from_le_bytes(bytes[i..i + 8].try_into().unwrap());
+
+    let version_minor = bytes[25];
+    let header_size = u16_at(94) as usize;
+    let point_offset = u32_at(96) as usize;
+    let point_format = bytes[104] & 0x3f;
+    let record_length = u16_at(105) as usize;
+    let mut point_count = u32_at(107) as u64;
+    if point_count == 0 && version_minor >= 4 && header_size >= 255 {
+        if bytes.len() < 255 {
+            Err(Error::LasCorruptData(name.clone()))?;
+        }
+        point_count = u64::from_le_bytes(bytes[247..255].try_into().unwrap());
+    }
+    let scale = vec3(f64_at(131), f64_at(139), f64_at(147));
+    let offset = vec3(f64_at(155), f64_at(163), f64_at(171));
+
+    let (classification_offset, rgb_offset) = match point_format {
+        0 | 1 | 4 => (15, None),
+        2 => (15, Some(20)),
+        3 | 5 => (15, Some(28)),
+        6 | 9 => (16, None),
+        7 | 8 | 10 => (16, Some(30)),
+        _ => Err(Error::LasCorruptData(name.clone()))?,
+    };
+    let min_record_length = rgb_offset
+        .map(|o| o + 6)
+        .unwrap_or(classification_offset + 1);
+    let end = point_count
+        .checked_mul(record_length as u64)
+        .and_then(|size| size.checked_add(point_offset as u64))
+        .unwrap_or(u64::MAX);
+    if record_length < min_record_length || end > bytes.len() as u64 {
+        Err(Error::LasCorruptData(name.clone()))?;
+    }
+    let point_count = point_count as usize;
+
+    let records = (0..point_count).map(|i| point_offset + i * record_length);
+    let positions = records
+        .clone()
+        .map(|r| {
+            vec3(
+                i32_at(r) as f64 * scale.x + offset.x,
+                i32_at(r + 4) as f64 * scale.y + offset.y,
+                i32_at(r + 8) as f64 * scale.z + offset.z,
+            )
+        })
+        .collect();
+
+    let colors = if let Some(rgb_offset) = rgb_offset {
+        let rgb = records
+            .map(|r| {
+                [
+                    u16_at(r + rgb_offset),
+                    u16_at(r + rgb_offset + 2),
+                    u16_at(r + rgb_offset + 4),
+                ]
+            })
+            .collect::<Vec<_>>();
+        // The RGB values should be 16 bit, but some writers store 8 bit values.
+        let shift = if rgb.iter().flatten().any(|c| *c > 255) {
+            8
+        } else {
+            0
+        };
+        rgb.into_iter()
+            .map(|c| {
+                Srgba::new_opaque(
+                    (c[0] >> shift) as u8,
+                    (c[1] >> shift) as u8,
+                    (c[2] >> shift) as u8,
+                )
+            })
+            .collect()
+    } else {
+        records
+            .map(|r| {
+                let classification = if point_format < 6 {
+                    bytes[r + classification_offset] & 0x1f
+                } else {
+                    bytes[r + classification_offset]
+                };
+                classification_color(classification)
+            })
+            .collect()
+    };
+
+    Ok(Scene {
+        name,
+        children: vec![Node {
+            geometry: Some(Geometry::Points(PointCloud {
+                positions: Positions::F64(positions),
+                colors: Some(colors),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }],
+        ..Default::default()
+    })
+}
+
+fn classification_color(classification: u8) -> Srgba {
+    match classification {
+        2 => Srgba::new_opaque(139, 90, 43),
+        3 => Srgba::new_opaque(144, 238, 144),
+        4 => Srgba::new_opaque(34, 139, 34),
+        5 => Srgba::new_opaque(0, 100, 0),
+        6 => Srgba::new_opaque(200, 50, 50),
+        7 | 18 => Srgba::new_opaque(255, 0, 255),
+        9 => Srgba::new_opaque(30, 144, 255),
+        _ => Srgba::new_opaque(128, 128, 128),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    pub fn deserialize_las_with_rgb() {
+        let point_cloud: crate::PointCloud =
+            crate::io::load_and_deserialize("test_data/rgb.las").unwrap();
+        assert!(matches!(point_cloud.positions, crate::Positions::F64(_)));
+        assert_eq!(
+            point_cloud.positions.to_f64(),
+            vec![
+                vec3(500000.0, 6000000.0, 10.0),
+                vec3(500001.25, 6000002.5, 11.125),
+                vec3(499999.5, 5999999.0, 9.0),
+            ]
+        );
+        assert_eq!(
+            point_cloud.colors,
+            Some(vec![
+                Srgba::new_opaque(255, 0, 0),
+                Srgba::new_opaque(0, 255, 0),
+                Srgba::new_opaque(0, 0, 255),
+            ])
+        );
+    }
+
+    #[test]
+    pub fn deserialize_las_with_classification() {
+        let point_cloud: crate::PointCloud =
+            crate::io::load_and_deserialize("test_data/classified.las").unwrap();
+        assert_eq!(point_cloud.positions.len(), 3);
+        assert_eq!(
+            point_cloud.colors,
+            Some(vec![
+                Srgba::new_opaque(139, 90, 43),
+                Srgba::new_opaque(0, 100, 0),
+                Srgba::new_opaque(200, 50, 50),
+            ])
+        );
+    }
+
+    #[test]
+    pub fn deserialize_las_corrupt() {
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("corrupt.las", b"LASF".to_vec());
+        let error = raw_assets
+            .deserialize::<crate::PointCloud>("corrupt.las")
+            .unwrap_err();
+        assert!(matches!(error, crate::Error::LasCorruptData(_)));
+        assert!(error.to_string().contains("corrupt.las"));
+    }
+
+    fn las_header(version_minor: u8, header_size: u16, point_count: u64) -> Vec<u8> {
+        let mut bytes = vec![0u8; header_size as usize];
+        bytes[0..4].copy_from_slice(b"LASF");
+        bytes[24] = 1;
+        bytes[25] = version_minor;
+        bytes[94..96].copy_from_slice(&header_size.to_le_bytes());
+        bytes[96..100].copy_from_slice(&(header_size as u32).to_le_bytes());
+        bytes[105..107].copy_from_slice(&20u16.to_le_bytes());
+        if point_count <= u32::MAX as u64 {
+            bytes[107..111].copy_from_slice(&(point_count as u32).to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    pub fn deserialize_las_truncated_header() {
+        // A version 1.4 header claims to be 375 bytes, but the file ends before the 64 bit point count.
+        let mut bytes = las_header(4, 240, 0);
+        bytes[94..96].copy_from_slice(&375u16.to_le_bytes());
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("truncated.las", bytes);
+        let error = raw_assets
+            .deserialize::<crate::PointCloud>("truncated.las")
+            .unwrap_err();
+        assert!(matches!(error, crate::Error::LasCorruptData(_)));
+    }
+
+    #[test]
+    pub fn deserialize_las_huge_point_count() {
+        let mut bytes = las_header(4, 375, u64::MAX);
+        bytes[247..255].copy_from_slice(&u64::MAX.to_le_bytes());
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("huge.las", bytes);
+        let error = raw_assets
+            .deserialize::<crate::PointCloud>("huge.las")
+            .unwrap_err();
+        assert!(matches!(error, crate::Error::LasCorruptData(_)));
+
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("huge_legacy.las", las_header(2, 227, u32::MAX as u64));
+        let error = raw_assets
+            .deserialize::<crate::PointCloud>("huge_legacy.las")
+            .unwrap_err();
+        assert!(matches!(error, crate::Error::LasCorruptData(_)));
+    }
+}
@@ -17,18 +17,37 @@ use std::path::{Path, PathBuf};
 ///
 #[cfg(not(target_arch = "wasm32"))]
 pub fn load(paths: &[impl AsRef<Path>]) -> Result<RawAssets> {
-    let mut raw_assets = load_single(paths)?;
+    let (raw_assets, failures) = load_lenient(paths);
+    first_failure(failures)?;
+    Ok(raw_assets)
+}
+
+///
+/// Loads all of the resources in the given paths like [load], but instead of failing if one of the resources fails to load,
+/// the resources that loaded successfully are returned together with the path and error for each resource that failed.
+///
+/// ```
+/// # use three_d_asset::io::*;
+/// let (mut assets, failures) = load_lenient(&["test_data/test.png", "test_data/missing.png"]);
+/// assert!(assets.get("test.png").is_ok());
+/// assert_eq!(failures.len(), 1);
+/// ```
+///
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_lenient(paths: &[impl AsRef<Path>]) -> (RawAssets, Vec<(PathBuf, Error)>) {
+    let (mut raw_assets, mut failures) = load_single(paths);
     let mut dependencies = super::get_dependencies(&raw_assets);
     while !dependencies.is_empty() {
-        let deps = load_single(&dependencies)?;
+        let (deps, deps_failures) = load_single(&dependencies);
+        failures.extend(deps_failures);
         dependencies = super::get_dependencies(&deps);
         raw_assets.extend(deps);
     }
-    Ok(raw_assets)
+    (raw_assets, failures)
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-fn load_single(paths: &[impl AsRef<Path>]) -> Result<RawAssets> {
+fn load_single(paths: &[impl AsRef<Path>]) -> (RawAssets, Vec<(PathBuf, Error)>) {
     let mut data_urls = HashSet::new();
     let mut local_paths = HashSet::new();
     for path in paths.iter() {
@@ -40,9 +59,9 @@ fn load_single(paths: &[impl AsRef<Path>]) -> Result<RawAssets> {
         }
     }
     let mut raw_assets = RawAssets::new();
-    load_from_disk(local_paths, &mut raw_assets)?;
-    parse_data_urls(data_urls, &mut raw_assets)?;
-    Ok(raw_assets)
+    let mut failures = load_from_disk(local_paths, &mut raw_assets);
+    failures.extend(parse_data_urls(data_urls, &mut raw_assets));
+    (raw_assets, failures)
 }
 
 ///
@@ -54,18 +73,37 @@ fn load_single(paths: &[impl AsRef<Path>]) -> Result<RawAssets> {
 /// - *** Native only *** Loading from disk (relative and absolute paths)
 ///
 pub async fn load_async(paths: &[impl AsRef<Path>]) -> Result<RawAssets> {
-    let mut raw_assets = load_async_single(paths).await?;
+    let (raw_assets, failures) = load_async_lenient(paths).await;
+    first_failure(failures)?;
+    Ok(raw_assets)
+}
+
+///
+/// Async loads all of the resources in the given paths like [load_async], but instead of failing if one of the resources fails to load,
+/// the resources that loaded successfully are returned together with the path and error for each resource that failed.
+/// This is useful when loading many independent assets, for example in an import tool, where one corrupt or missing file should not prevent loading the rest.
+///
+pub async fn load_async_lenient(paths: &[impl AsRef<Path>]) -> (RawAssets, Vec<(PathBuf, Error)>) {
+    let (mut raw_assets, mut failures) = load_async_single(paths).await;
     let mut dependencies = super::get_dependencies(&raw_assets);
     while !dependencies.is_empty() {
-        let deps = load_async_single(&dependencies).await?;
+        let (deps, deps_failures) = load_async_single(&dependencies).await;
+        failures.extend(deps_failures);
         dependencies = super::get_dependencies(&deps);
         raw_assets.extend(deps);
     }
-    Ok(raw_assets)
+    (raw_assets, failures)
+}
+
+fn first_failure(failures: Vec<(PathBuf, Error)>) -> Result<()> {
+    match failures.into_iter().next() {
+        Some((_, error)) => Err(error),
+        None => Ok(()),
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
-async fn load_async_single(paths: &[impl AsRef<Path>]) -> Result<RawAssets> {
+async fn load_async_single(paths: &[impl AsRef<Path>]) -> (RawAssets, Vec<(PathBuf, Error)>) {
     let base_path = base_path();
     let mut urls = HashSet::new();
     let mut data_urls = HashSet::new();
@@ -80,13 +118,13 @@ async fn load_async_single(paths: &[impl AsRef<Path>]) -> Result<RawAssets> {
         }
     }
     let mut raw_assets = RawAssets::new();
-    load_urls(urls, &mut raw_assets).await?;
-    parse_data_urls(data_urls, &mut raw_assets)?;
-    Ok(raw_assets)
+    let mut failures = load_urls(urls, &mut raw_assets).await;
+    failures.extend(parse_data_urls(data_urls, &mut raw_assets));
+    (raw_assets, failures)
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-async fn load_async_single(paths: &[impl AsRef<Path>]) -> Result<RawAssets> {
+async fn load_async_single(paths: &[impl AsRef<Path>]) -> (RawAssets, Vec<(PathBuf, Error)>) {
     let mut urls = HashSet::new();
     let mut data_urls = HashSet::new();
     let mut local_paths = HashSet::new();
@@ -102,14 +140,14 @@ async fn load_async_single(paths: &[impl AsRef<Path>]) -> Result<RawAssets> {
     }
 
     let mut raw_assets = RawAssets::new();
-    load_urls(urls, &mut raw_assets).await?;
-    load_from_disk(local_paths, &mut raw_assets)?;
-    parse_data_urls(data_urls, &mut raw_assets)?;
-    Ok(raw_assets)
+    let mut failures = load_urls(urls, &mut raw_assets).await;
+    failures.extend(load_from_disk(local_paths, &mut raw_assets));
+    failures.extend(parse_data_urls(data_urls, &mut raw_assets));
+    (raw_assets, failures)
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-fn load_from_disk(paths: HashSet<PathBuf>, raw_assets: &mut RawAssets) -> Result<()> {
+fn load_from_disk(paths: HashSet<PathBuf>, raw_assets: &mut RawAssets) -> Vec<(PathBuf, Error)> {
     let mut handles = Vec::new();
     for path in paths {
         handles.push((
@@ -118,67 +156,85 @@ fn load_from_disk(paths: HashSet<PathBuf>, raw_assets: &mut RawAssets) -> Result
         ));
     }
 
+    let mut failures = Vec::new();
     for (path, handle) in handles.drain(..) {
-        let bytes = handle
-            .join()
-            .unwrap()
-            .map_err(|e| Error::FailedLoading(path.to_str().unwrap().to_string(), e))?;
-        raw_assets.insert(path, bytes);
+        match handle.join().unwrap() {
+            Ok(bytes) => {
+                raw_assets.insert(path, bytes);
+            }
+            Err(e) => {
+                let error = Error::FailedLoading(path.to_str().unwrap().to_string(), e);
+                failures.push((path, error));
+            }
+        }
     }
-    Ok(())
+    failures
 }
 
-#[allow(unused_variables)]
-async fn load_urls(paths: HashSet<PathBuf>, raw_assets: &mut RawAssets) -> Result<()> {
+#[allow(unused_mut, unused_variables)]
+async fn load_urls(paths: HashSet<PathBuf>, raw_assets: &mut RawAssets) -> Vec<(PathBuf, Error)> {
+    let mut failures = Vec::new();
     #[cfg(feature = "reqwest")]
-    if paths.len() > 0 {
-        let mut handles = Vec::new();
+    if !paths.is_empty() {
         let client = reqwest::Client::new();
         for path in paths {
-            let url = reqwest::Url::parse(path.to_str().unwrap())
-                .map_err(|_| Error::FailedParsingUrl(path.to_str().unwrap().to_string()))?;
-            handles.push((path, client.get(url).send().await));
-        }
-        for (path, handle) in handles.drain(..) {
-            let bytes = handle
-                .map_err(|e| {
-                    Error::FailedLoadingUrlWithReqwest(path.to_str().unwrap().to_string(), e)
-                })?
-                .bytes()
-                .await
-                .map_err(|e| {
-                    Error::FailedLoadingUrlWithReqwest(path.to_str().unwrap().to_string(), e)
-                })?
-                .to_vec();
-
-            #[cfg(target_arch = "wasm32")]
-            {
-                if std::str::from_utf8(&bytes[0..15])
-                    .map(|r| r.starts_with("<!DOCTYPE html>"))
-                    .unwrap_or(false)
-                {
-                    Err(Error::FailedLoadingUrl(
-                        path.to_str().unwrap().to_string(),
-                        std::str::from_utf8(&bytes).unwrap().to_string(),
-                    ))?;
+            match load_url(&client, &path).await {
+                Ok(bytes) => {
+                    raw_assets.insert(path, bytes);
                 }
+                Err(e) => failures.push((path, e)),
             }
-            raw_assets.insert(path, bytes);
         }
     }
     #[cfg(not(feature = "reqwest"))]
-    if !paths.is_empty() {
-        return Err(Error::FeatureMissing("reqwest".to_string()));
+    failures.extend(
+        paths
+            .into_iter()
+            .map(|path| (path, Error::FeatureMissing("reqwest".to_string()))),
+    );
+    failures
+}
+
+#[cfg(feature = "reqwest")]
+async fn load_url(client: &reqwest::Client, path: &Path) -> Result<Vec<u8>> {
+    let url = reqwest::Url::parse(path.to_str().unwrap())
+        .map_err(|_| Error::FailedParsingUrl(path.to_str().unwrap().to_string()))?;
+    let bytes = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| Error::FailedLoadingUrlWithReqwest(path.to_str().unwrap().to_string(), e))?
+        .bytes()
+        .await
+        .map_err(|e| Error::FailedLoadingUrlWithReqwest(path.to_str().unwrap().to_string(), e))?
+        .to_vec();
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        if std::str::from_utf8(&bytes[0..15])
+            .map(|r| r.starts_with("<!DOCTYPE html>"))
+            .unwrap_or(false)
+        {
+            Err(Error::FailedLoadingUrl(
+                path.to_str().unwrap().to_string(),
+                std::str::from_utf8(&bytes).unwrap().to_string(),
+            ))?;
+        }
     }
-    Ok(())
+    Ok(bytes)
 }
 
-fn parse_data_urls(paths: HashSet<PathBuf>, raw_assets: &mut RawAssets) -> Result<()> {
+fn parse_data_urls(paths: HashSet<PathBuf>, raw_assets: &mut RawAssets) -> Vec<(PathBuf, Error)> {
+    let mut failures = Vec::new();
     for path in paths {
-        let bytes = parse_data_url(path.to_str().unwrap())?;
-        raw_assets.insert(path, bytes);
+        match parse_data_url(path.to_str().unwrap()) {
+            Ok(bytes) => {
+                raw_assets.insert(path, bytes);
+            }
+            Err(e) => failures.push((path, e)),
+        }
     }
-    Ok(())
+    failures
 }
 
 #[allow(unused_variables)]
@@ -242,4 +298,39 @@ mod test {
 
         assert_eq!(loaded_data_url, loaded_image);
     }
+
+    #[test]
+    pub fn load_lenient() {
+        use super::*;
+        let (mut loaded, failures) = load_lenient(&["test_data/test.png", "test_data/missing.png"]);
+        assert!(loaded.remove("test_data/test.png").is_ok());
+        assert!(loaded.is_empty());
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, PathBuf::from("test_data/missing.png"));
+        assert!(matches!(failures[0].1, Error::FailedLoading(..)));
+
+        assert!(matches!(
+            load(&["test_data/test.png", "test_data/missing.png"]),
+            Err(Error::FailedLoading(..))
+        ));
+    }
+
+    #[test]
+    pub fn load_async_lenient() {
+        use super::*;
+        use std::future::Future;
+        // Loading from disk never waits, so the future completes on the first poll
+        let future = std::pin::pin!(load_async_lenient(&[
+            "test_data/test.png",
+            "test_data/missing.png",
+            "test_data/cube.stl",
+        ]));
+        let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+        let std::task::Poll::Ready((loaded, failures)) = future.poll(&mut context) else {
+            panic!("loading from disk should complete immediately");
+        };
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, PathBuf::from("test_data/missing.png"));
+    }
 }
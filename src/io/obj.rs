@@ -22,27 +22,16 @@ pub fn dependencies_mtl(raw_assets: &RawAssets, path: &PathBuf) -> HashSet<PathB
     {
         let base_path = path.parent().unwrap_or(Path::new(""));
         for material in materials.materials {
-            material
-                .ambient_map
-                .map(|p| dependencies.insert(base_path.join(p)));
+            // Only the maps which are used when deserializing the material are dependencies.
             material
                 .diffuse_map
                 .map(|p| dependencies.insert(base_path.join(p)));
-            material
-                .specular_map
-                .map(|p| dependencies.insert(base_path.join(p)));
             material
                 .specular_exponent_map
                 .map(|p| dependencies.insert(base_path.join(p)));
-            material
-                .displacement_map
-                .map(|p| dependencies.insert(base_path.join(p)));
             material
                 .dissolve_map
                 .map(|p| dependencies.insert(base_path.join(p)));
-            material
-                .decal_map
-                .map(|p| dependencies.insert(base_path.join(p)));
             material
                 .bump_map
                 .map(|p| dependencies.insert(base_path.join(p)));
@@ -60,54 +49,97 @@ pub fn deserialize_obj(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<Sce
     let mut materials = Vec::new();
     if let Some(material_library) = obj.material_library {
         let bytes = raw_assets.remove(p.join(material_library).to_str().unwrap())?;
+        // The MTL values are mapped to PBR values as follows:
+        // - The diffuse color (Kd) and map (map_Kd) is the albedo and the dissolve (d) is the alpha value of the albedo.
+        //   The dissolve map (map_d) is multiplied into the alpha channel of the albedo texture.
+        // - The specular exponent (Ns) is converted into roughness, see PbrMaterial::roughness_from_specular_exponent.
+        //   The specular exponent map (map_Ns) scales the exponent and is converted into the green channel of the metallic roughness texture.
+        // - The materials are dielectric, so the metallic value is zero.
+        //   The specular (Ks, map_Ks) and ambient (Ka, map_Ka) colors have no PBR equivalent and are ignored,
+        //   so the specular strength is lost, see the OBJ/MTL section of the io module documentation.
+        // - The emissive color (Ke) is the emissive color, where values above one are moved into the emissive strength.
+        //   The emissive map (map_Ke) is not exposed by wavefront_obj and is ignored.
+        // - The optical density (Ni) is the index of refraction.
         for material in wavefront_obj::mtl::parse(std::str::from_utf8(&bytes).unwrap())?.materials {
-            let color = if material.color_diffuse.r != material.color_diffuse.g
-                || material.color_diffuse.g != material.color_diffuse.b
-            {
-                material.color_diffuse
-            } else if material.color_specular.r != material.color_specular.g
-                || material.color_specular.g != material.color_specular.b
-            {
-                material.color_specular
-            } else if material.color_ambient.r != material.color_ambient.g
-                || material.color_ambient.g != material.color_ambient.b
-            {
-                material.color_ambient
-            } else {
-                material.color_diffuse
-            };
-
             let normal_texture = if let Some(ref texture_name) = material.bump_map {
                 Some(raw_assets.deserialize(p.join(texture_name))?)
             } else {
                 None
             };
-            let albedo_texture = if let Some(ref texture_name) = material.diffuse_map {
+            let mut albedo_texture = if let Some(ref texture_name) = material.diffuse_map {
                 Some(raw_assets.deserialize(p.join(texture_name))?)
             } else {
                 None
             };
+            let mut alpha_mode = if material.alpha < 1.0 {
+                AlphaMode::Blend
+            } else {
+                AlphaMode::Opaque
+            };
+            if let Some(ref texture_name) = material.dissolve_map {
+                let dissolve: Texture2D = raw_assets.deserialize(p.join(texture_name))?;
+                if let Some(texture) = apply_dissolve(albedo_texture.as_ref(), &dissolve) {
+                    albedo_texture = Some(texture);
+                    alpha_mode = AlphaMode::Blend;
+                }
+            }
+            let mut roughness =
+                PbrMaterial::roughness_from_specular_exponent(material.specular_coefficient as f32);
+            let mut metallic_roughness_texture = None;
+            if let Some(ref texture_name) = material.specular_exponent_map {
+                let mut texture: Texture2D = raw_assets.deserialize(p.join(texture_name))?;
+                if let Some(values) = scalar_values(&texture) {
+                    texture.data = TextureData::RgbaU8(
+                        values
+                            .into_iter()
+                            .map(|v| {
+                                let exponent = material.specular_coefficient as f32 * v;
+                                let roughness =
+                                    PbrMaterial::roughness_from_specular_exponent(exponent);
+                                [0, (roughness * 255.0).round() as u8, 0, 255]
+                            })
+                            .collect(),
+                    );
+                    metallic_roughness_texture = Some(texture);
+                    roughness = 1.0;
+                }
+            }
+            let (emissive, emissive_strength) = if let Some(color) = material.color_emissive {
+                let strength = color.r.max(color.g).max(color.b).max(1.0);
+                (
+                    [
+                        (color.r / strength) as f32,
+                        (color.g / strength) as f32,
+                        (color.b / strength) as f32,
+                    ]
+                    .into(),
+                    strength as f32,
+                )
+            } else {
+                (Srgba::BLACK, 1.0)
+            };
 
             materials.push(PbrMaterial {
                 name: material.name,
                 albedo: [
-                    color.r as f32,
-                    color.g as f32,
-                    color.b as f32,
+                    material.color_diffuse.r as f32,
+                    material.color_diffuse.g as f32,
+                    material.color_diffuse.b as f32,
                     material.alpha as f32,
                 ]
                 .into(),
                 albedo_texture,
-                metallic: ((material.color_specular.r
-                    + material.color_specular.g
-                    + material.color_specular.b)
-                    / 3.0) as f32,
-                roughness: if material.specular_coefficient > 0.1 {
-                    ((1.999 / material.specular_coefficient).sqrt() as f32).min(1.0)
-                } else {
-                    1.0
-                },
+                metallic: 0.0,
+                roughness,
+                metallic_roughness_texture,
                 normal_texture,
+                emissive,
+                emissive_strength,
+                index_of_refraction: material
+                    .optical_density
+                    .map(|n| n as f32)
+                    .unwrap_or(PbrMaterial::default().index_of_refraction),
+                alpha_mode,
                 lighting_model: LightingModel::Blinn,
                 ..Default::default()
             });
@@ -212,9 +244,57 @@ pub fn deserialize_obj(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<Sce
         name: path.to_str().unwrap_or("default").to_owned(),
         children: nodes,
         materials,
+        unit: None,
+        warnings: Vec::new(),
     })
 }
 
+///
+/// Returns the values of the first channel of a texture with 8 bit channels in the range `[0..1]`.
+///
+fn scalar_values(texture: &Texture2D) -> Option<Vec<f32>> {
+    let value = |v: u8| v as f32 / 255.0;
+    Some(match &texture.data {
+        TextureData::RU8(data) => data.iter().map(|v| value(*v)).collect(),
+        TextureData::RgU8(data) => data.iter().map(|v| value(v[0])).collect(),
+        TextureData::RgbU8(data) => data.iter().map(|v| value(v[0])).collect(),
+        TextureData::RgbaU8(data) => data.iter().map(|v| value(v[0])).collect(),
+        _ => None?,
+    })
+}
+
+///
+/// Returns the albedo texture, or a white texture if there is no albedo texture, with the dissolve texture multiplied into the alpha channel.
+/// The dissolve texture is sampled using nearest neighbour if the size is different from the size of the albedo texture.
+///
+fn apply_dissolve(albedo_texture: Option<&Texture2D>, dissolve: &Texture2D) -> Option<Texture2D> {
+    let values = scalar_values(dissolve)?;
+    let mut texture = albedo_texture.cloned().unwrap_or_else(|| Texture2D {
+        name: dissolve.name.clone(),
+        data: TextureData::RgbaU8(vec![[255; 4]; values.len()]),
+        width: dissolve.width,
+        height: dissolve.height,
+        ..Default::default()
+    });
+    let mut texels = match &texture.data {
+        TextureData::RgbaU8(data) => data.clone(),
+        TextureData::RgbU8(data) => data.iter().map(|t| [t[0], t[1], t[2], 255]).collect(),
+        _ => None?,
+    };
+    let (width, height) = (texture.width as usize, texture.height as usize);
+    let (dissolve_width, dissolve_height) = (dissolve.width as usize, dissolve.height as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let value = values
+                [(y * dissolve_height / height) * dissolve_width + x * dissolve_width / width];
+            let texel = &mut texels[y * width + x];
+            texel[3] = (texel[3] as f32 * value).round() as u8;
+        }
+    }
+    texture.data = TextureData::RgbaU8(texels);
+    Some(texture)
+}
+
 #[cfg(test)]
 mod test {
 
@@ -230,5 +310,96 @@ mod test {
         let model: crate::Model = crate::io::load_and_deserialize("test_data/suzanne.obj").unwrap();
         assert_eq!(model.geometries.len(), 1);
         assert_eq!(model.materials.len(), 1);
+        let material = &model.materials[0];
+        assert_eq!(material.albedo, crate::Srgba::new(163, 122, 81, 255));
+        assert_eq!(material.metallic, 0.0);
+        assert!((material.roughness - 0.378).abs() < 0.001);
+        assert_eq!(material.index_of_refraction, 1.0);
+        assert_eq!(material.emissive, crate::Srgba::BLACK);
+        assert_eq!(material.alpha_mode, crate::AlphaMode::Opaque);
+    }
+
+    #[test]
+    pub fn deserialize_obj_with_emissive_material() {
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert(
+            "triangle.obj",
+            b"mtllib emissive.mtl\no triangle\nv 0 0 0\nv 1 0 0\nv 0 1 0\nusemtl glowing\nf 1 2 3\n"
+                .to_vec(),
+        );
+        raw_assets.insert(
+            "emissive.mtl",
+            b"newmtl glowing\nNs 0\nKa 0 0 0\nKd 1 0 0\nKs 0 0 0\nKe 4 2 0\nNi 1.5\nd 0.5\nillum 2\n"
+                .to_vec(),
+        );
+        let model: crate::Model = raw_assets.deserialize("triangle.obj").unwrap();
+        let material = &model.materials[0];
+        assert_eq!(material.albedo, crate::Srgba::new(255, 0, 0, 127));
+        assert_eq!(material.roughness, 1.0);
+        assert_eq!(material.emissive, crate::Srgba::new(255, 127, 0, 255));
+        assert_eq!(material.emissive_strength, 4.0);
+        assert_eq!(material.index_of_refraction, 1.5);
+        assert_eq!(material.alpha_mode, crate::AlphaMode::Blend);
+    }
+
+    #[test]
+    pub fn mtl_dependencies() {
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert(
+            "models/maps.mtl",
+            b"newmtl mapped\nNs 98\nKa 0 0 0\nKd 1 1 1\nKs 0 0 0\nd 1\nillum 2\nmap_Ka ambient.png\nmap_Kd albedo.png\nmap_Ks specular.png\nmap_d dissolve.png\n"
+                .to_vec(),
+        );
+        let dependencies =
+            super::dependencies_mtl(&raw_assets, &std::path::PathBuf::from("models/maps.mtl"));
+        assert_eq!(
+            dependencies,
+            ["models/albedo.png", "models/dissolve.png"]
+                .into_iter()
+                .map(std::path::PathBuf::from)
+                .collect()
+        );
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    pub fn deserialize_obj_with_texture_maps() {
+        use crate::{io::Serialize, Texture2D, TextureData};
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert(
+            "triangle.obj",
+            b"mtllib maps.mtl\no triangle\nv 0 0 0\nv 1 0 0\nv 0 1 0\nusemtl mapped\nf 1 2 3\n"
+                .to_vec(),
+        );
+        raw_assets.insert(
+            "maps.mtl",
+            b"newmtl mapped\nNs 98\nKa 0 0 0\nKd 1 1 1\nKs 0 0 0\nd 1\nillum 2\nmap_Ns exponent.png\nmap_d dissolve.png\n"
+                .to_vec(),
+        );
+        for (name, data) in [
+            ("exponent.png", vec![[255; 4], [0, 0, 0, 255]]),
+            ("dissolve.png", vec![[255; 4], [128, 128, 128, 255]]),
+        ] {
+            let texture = Texture2D {
+                data: TextureData::RgbaU8(data),
+                width: 2,
+                height: 1,
+                ..Default::default()
+            };
+            raw_assets.extend(texture.serialize(name).unwrap());
+        }
+
+        let model: crate::Model = raw_assets.deserialize("triangle.obj").unwrap();
+        let material = &model.materials[0];
+        assert_eq!(material.roughness, 1.0);
+        assert!(
+            material.metallic_roughness_texture.as_ref().unwrap().data
+                == TextureData::RgbaU8(vec![[0, 96, 0, 255], [0, 255, 0, 255]])
+        );
+        assert!(
+            material.albedo_texture.as_ref().unwrap().data
+                == TextureData::RgbaU8(vec![[255, 255, 255, 255], [255, 255, 255, 128]])
+        );
+        assert_eq!(material.alpha_mode, crate::AlphaMode::Blend);
     }
 }
@@ -2,7 +2,7 @@ use crate::geometry::{Geometry, PointCloud, Positions};
 use crate::prelude::*;
 use crate::{io::RawAssets, Node, Result, Scene};
 use pcd_rs::DynReader;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub fn deserialize_pcd(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<Scene> {
     let name = path.to_str().unwrap().to_string();
@@ -12,6 +12,15 @@ pub fn deserialize_pcd(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<Sce
     let y_index = schema.iter().position(|f| f.name == "y").unwrap();
     let z_index = schema.iter().position(|f| f.name == "z").unwrap();
     let rgb_index = schema.iter().position(|f| f.name == "rgb");
+    let intensity_index = schema.iter().position(|f| f.name == "intensity");
+    let normal_indices = match (
+        schema.iter().position(|f| f.name == "normal_x"),
+        schema.iter().position(|f| f.name == "normal_y"),
+        schema.iter().position(|f| f.name == "normal_z"),
+    ) {
+        (Some(x), Some(y), Some(z)) => Some((x, y, z)),
+        _ => None,
+    };
 
     let points = reader.collect::<pcd_rs::Result<Vec<_>>>()?;
     let positions = points
@@ -43,12 +52,49 @@ pub fn deserialize_pcd(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<Sce
             })
             .collect()
     });
+
+    // Use the intensity as a grey scale color if no colors are specified.
+    let colors = colors.or_else(|| {
+        intensity_index.map(|i| {
+            let intensities = points
+                .iter()
+                .map(|p| p.0[i].to_value::<f32>().unwrap_or(0.0))
+                .collect::<Vec<_>>();
+            let max = intensities.iter().fold(0.0f32, |a, b| a.max(*b));
+            intensities
+                .into_iter()
+                .map(|intensity| {
+                    let v = if max > 0.0 {
+                        (255.0 * intensity / max) as u8
+                    } else {
+                        0
+                    };
+                    Srgba::new_opaque(v, v, v)
+                })
+                .collect()
+        })
+    });
+
+    let normals = normal_indices.map(|(x_index, y_index, z_index)| {
+        points
+            .iter()
+            .map(|p| {
+                vec3(
+                    p.0[x_index].to_value::<f32>().unwrap(),
+                    p.0[y_index].to_value::<f32>().unwrap(),
+                    p.0[z_index].to_value::<f32>().unwrap(),
+                )
+            })
+            .collect()
+    });
+
     Ok(Scene {
         name,
         children: vec![Node {
             geometry: Some(Geometry::Points(PointCloud {
                 positions: Positions::F32(positions),
                 colors,
+                normals,
             })),
             ..Default::default()
         }],
@@ -56,8 +102,108 @@ pub fn deserialize_pcd(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<Sce
     })
 }
 
+pub fn serialize_pcd(point_cloud: &PointCloud, path: &Path, ascii: bool) -> Result<RawAssets> {
+    let count = point_cloud.positions.len();
+    let position_size = match point_cloud.positions {
+        Positions::F32(_) => 4,
+        Positions::F64(_) => 8,
+    };
+    let mut fields = vec![
+        ("x", position_size, 'F'),
+        ("y", position_size, 'F'),
+        ("z", position_size, 'F'),
+    ];
+    if point_cloud.colors.is_some() {
+        fields.push(("rgb", 4, 'U'));
+    }
+    if point_cloud.normals.is_some() {
+        fields.extend([
+            ("normal_x", 4, 'F'),
+            ("normal_y", 4, 'F'),
+            ("normal_z", 4, 'F'),
+        ]);
+    }
+    let join = |f: &dyn Fn(&(&str, usize, char)) -> String| {
+        fields.iter().map(f).collect::<Vec<_>>().join(" ")
+    };
+
+    let mut bytes = format!(
+        "# .PCD v0.7 - Point Cloud Data file format\n\
+        VERSION 0.7\n\
+        FIELDS {}\n\
+        SIZE {}\n\
+        TYPE {}\n\
+        COUNT {}\n\
+        WIDTH {count}\n\
+        HEIGHT 1\n\
+        VIEWPOINT 0 0 0 1 0 0 0\n\
+        POINTS {count}\n\
+        DATA {}\n",
+        join(&|f| f.0.to_string()),
+        join(&|f| f.1.to_string()),
+        join(&|f| f.2.to_string()),
+        join(&|_| "1".to_string()),
+        if ascii { "ascii" } else { "binary" },
+    )
+    .into_bytes();
+
+    // The colors are packed into a single value as 0x00RRGGBB like in PCL.
+    let rgb = |c: &Srgba| ((c.r as u32) << 16) | ((c.g as u32) << 8) | c.b as u32;
+    for i in 0..count {
+        if ascii {
+            let mut values = match point_cloud.positions {
+                Positions::F32(ref p) => {
+                    vec![p[i].x.to_string(), p[i].y.to_string(), p[i].z.to_string()]
+                }
+                Positions::F64(ref p) => {
+                    vec![p[i].x.to_string(), p[i].y.to_string(), p[i].z.to_string()]
+                }
+            };
+            if let Some(ref colors) = point_cloud.colors {
+                values.push(rgb(&colors[i]).to_string());
+            }
+            if let Some(ref normals) = point_cloud.normals {
+                values.extend([
+                    normals[i].x.to_string(),
+                    normals[i].y.to_string(),
+                    normals[i].z.to_string(),
+                ]);
+            }
+            bytes.extend(values.join(" ").into_bytes());
+            bytes.push(b'\n');
+        } else {
+            match point_cloud.positions {
+                Positions::F32(ref p) => {
+                    bytes.extend(p[i].x.to_le_bytes());
+                    bytes.extend(p[i].y.to_le_bytes());
+                    bytes.extend(p[i].z.to_le_bytes());
+                }
+                Positions::F64(ref p) => {
+                    bytes.extend(p[i].x.to_le_bytes());
+                    bytes.extend(p[i].y.to_le_bytes());
+                    bytes.extend(p[i].z.to_le_bytes());
+                }
+            }
+            if let Some(ref colors) = point_cloud.colors {
+                bytes.extend(rgb(&colors[i]).to_le_bytes());
+            }
+            if let Some(ref normals) = point_cloud.normals {
+                bytes.extend(normals[i].x.to_le_bytes());
+                bytes.extend(normals[i].y.to_le_bytes());
+                bytes.extend(normals[i].z.to_le_bytes());
+            }
+        }
+    }
+
+    let mut raw_assets = RawAssets::new();
+    raw_assets.insert(path, bytes);
+    Ok(raw_assets)
+}
+
 #[cfg(test)]
 mod test {
+    use crate::io::{Deserialize, Serialize};
+    use crate::prelude::*;
 
     #[test]
     pub fn deserialize_pcd() {
@@ -82,4 +228,56 @@ mod test {
             .unwrap();
         assert_eq!(point_cloud.positions.len(), 28944);
     }
+
+    #[test]
+    pub fn deserialize_pcd_with_normals_and_intensity() {
+        let point_cloud: crate::PointCloud = crate::io::RawAssets::new()
+            .insert(
+                "test_data/normals.pcd",
+                include_bytes!("../../test_data/normals.pcd").to_vec(),
+            )
+            .deserialize("pcd")
+            .unwrap();
+        assert_eq!(point_cloud.positions.len(), 3);
+        let normals = point_cloud.normals.unwrap();
+        assert_eq!(normals[0], crate::prelude::vec3(0.0, 0.0, 1.0));
+        assert_eq!(normals[2], crate::prelude::vec3(1.0, 0.0, 0.0));
+        let colors = point_cloud.colors.unwrap();
+        assert_eq!(colors[0], crate::prelude::Srgba::new_opaque(255, 255, 255));
+        assert_eq!(colors[1], crate::prelude::Srgba::new_opaque(127, 127, 127));
+    }
+
+    fn point_cloud() -> crate::PointCloud {
+        crate::PointCloud {
+            positions: crate::Positions::F32(vec![
+                vec3(0.1, -2.5, 3.0),
+                vec3(1.0e-3, 4.25, -0.7),
+                vec3(100.5, 0.0, 1.0 / 3.0),
+            ]),
+            colors: Some(vec![
+                Srgba::new_opaque(255, 0, 0),
+                Srgba::new_opaque(12, 34, 56),
+                Srgba::new_opaque(0, 0, 255),
+            ]),
+            normals: None,
+        }
+    }
+
+    #[test]
+    pub fn serialize_binary_pcd() {
+        let point_cloud = point_cloud();
+        let mut raw_assets = point_cloud.serialize("test.pcd").unwrap();
+        let result = crate::PointCloud::deserialize("test.pcd", &mut raw_assets).unwrap();
+        assert_eq!(result.positions.to_f32(), point_cloud.positions.to_f32());
+        assert_eq!(result.colors, point_cloud.colors);
+    }
+
+    #[test]
+    pub fn serialize_ascii_pcd() {
+        let point_cloud = point_cloud();
+        let mut raw_assets = crate::io::serialize_pcd_ascii(&point_cloud, "test.pcd").unwrap();
+        let result = crate::PointCloud::deserialize("test.pcd", &mut raw_assets).unwrap();
+        assert_eq!(result.positions.to_f32(), point_cloud.positions.to_f32());
+        assert_eq!(result.colors, point_cloud.colors);
+    }
 }
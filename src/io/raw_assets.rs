@@ -1,4 +1,7 @@
-use crate::{io::Deserialize, Error, Result};
+use crate::{
+    io::{Deserialize, ImportOptions},
+    Error, Result,
+};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
@@ -29,7 +32,10 @@ impl RawAssets {
     /// let png_bytes = assets.remove("test.png").unwrap();
     /// ```
     ///
-    /// **Note:** If no asset has been inserted with the exact same path given as input, then the first asset which path that contains the path given as input is returned.
+    /// **Note:** If no asset has been inserted with the exact same path given as input, then the asset which path ends with the path given as input is returned.
+    /// If more than one asset matches, an [Error::AmbiguousPath] error is returned.
+    /// The path given as input must match entire file or directory names, so `a.png` matches `data/a.png` but not `data/other_a.png`.
+    /// It is also possible to only specify the extension, for example `.png`, or an empty path to match any asset.
     /// This means the above example can be reduced to:
     /// ```
     /// # use three_d_asset::io::*;
@@ -49,7 +55,10 @@ impl RawAssets {
     /// let png_bytes = assets.get("test.png").unwrap();
     /// ```
     ///
-    /// **Note:** If no asset has been inserted with the exact same path given as input, then the first asset which path that contains the path given as input is returned.
+    /// **Note:** If no asset has been inserted with the exact same path given as input, then the asset which path ends with the path given as input is returned.
+    /// If more than one asset matches, an [Error::AmbiguousPath] error is returned.
+    /// The path given as input must match entire file or directory names, so `a.png` matches `data/a.png` but not `data/other_a.png`.
+    /// It is also possible to only specify the extension, for example `.png`, or an empty path to match any asset.
     /// This means the above example can be reduced to:
     /// ```
     /// # use three_d_asset::io::*;
@@ -60,26 +69,87 @@ impl RawAssets {
         Ok(self.0.get(&self.match_path(path.as_ref())?).unwrap())
     }
 
+    ///
+    /// Returns true if an asset has been inserted with exactly the given path.
+    /// Unlike [RawAssets::get] and [RawAssets::remove], the path must match the entire path of the asset,
+    /// except that `\\` and `/` are considered to be the same separator.
+    ///
+    /// ```
+    /// # use three_d_asset::io::*;
+    /// let assets = load(&["test_data/test.png"]).unwrap();
+    /// assert!(assets.contains_key("test_data/test.png"));
+    /// assert!(!assets.contains_key("test.png"));
+    /// ```
+    ///
+    pub fn contains_key(&self, path: impl AsRef<Path>) -> bool {
+        let key: PathBuf = path.as_ref().to_str().unwrap().replace('\\', "/").into();
+        self.0.contains_key(&key)
+    }
+
     pub(crate) fn match_path(&self, path: &Path) -> Result<PathBuf> {
         if self.0.contains_key(path) {
             Ok(path.into())
         } else {
-            let p = path.to_str().unwrap().replace('\\', "/");
-            let p = if p.ends_with(".jpeg") {
-                p[0..p.len() - 2].to_string()
-            } else if p.ends_with(".jpg") {
-                p[0..p.len() - 1].to_string()
-            } else {
-                p
-            };
-            self.0
-                .iter()
-                .find(|(k, _)| k.to_str().unwrap().contains(&p))
-                .map(|(k, _)| k.clone())
-                .ok_or(Error::NotLoaded(path.to_str().unwrap().to_string()))
+            let p = normalize(path);
+            let mut candidates = self
+                .0
+                .keys()
+                .filter(|k| matches(&normalize(k), &p))
+                .collect::<Vec<_>>();
+            match candidates.len() {
+                0 => Err(Error::NotLoaded(path.to_str().unwrap().to_string())),
+                1 => Ok(candidates[0].clone()),
+                _ => {
+                    candidates.sort();
+                    Err(Error::AmbiguousPath(
+                        path.to_str().unwrap().to_string(),
+                        candidates
+                            .iter()
+                            .map(|k| k.to_str().unwrap())
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    ))
+                }
+            }
         }
     }
 
+    ///
+    /// Returns an iterator over the paths of all the raw assets in arbitrary order.
+    ///
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.0.keys().map(|k| k.as_path())
+    }
+
+    ///
+    /// Returns the sorted paths of all the raw assets with the given extension, for example `png` or `.png`.
+    /// The comparison is case insensitive and `jpg` and `jpeg` are considered to be the same extension.
+    ///
+    /// ```
+    /// # use three_d_asset::io::*;
+    /// # use three_d_asset::Texture2D;
+    /// let mut assets = load(&["test_data/test.png", "test_data/test.jpg"]).unwrap();
+    /// for path in assets.find_by_extension("png") {
+    ///     let texture: Texture2D = assets.deserialize(path).unwrap();
+    /// }
+    /// ```
+    ///
+    pub fn find_by_extension(&self, ext: &str) -> Vec<PathBuf> {
+        let normalize = |e: &str| match e.to_lowercase().as_str() {
+            "jpeg" => "jpg".to_string(),
+            e => e.to_string(),
+        };
+        let ext = normalize(ext.strip_prefix('.').unwrap_or(ext));
+        let mut paths = self
+            .0
+            .keys()
+            .filter(|k| k.extension().and_then(|e| e.to_str()).map(normalize) == Some(ext.clone()))
+            .cloned()
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths
+    }
+
     ///
     /// Inserts the given bytes into the set of raw assets.
     /// This is useful if you want to add data from an unsuported source and want to use either the [RawAssets::deserialize] functionality or [RawAssets::save] functionality.
@@ -119,7 +189,8 @@ impl RawAssets {
     /// let texture: Texture2D = assets.deserialize("test.png").unwrap();
     /// ```
     ///
-    /// **Note:** If no asset has been inserted with the exact same path given as input, then the first asset which path that contains the path given as input is deserialized.
+    /// **Note:** If no asset has been inserted with the exact same path given as input, then the asset which path ends with the path given as input is deserialized,
+    /// see [RawAssets::get] for the details.
     /// This means the above example can be reduced to:
     /// ```
     /// # use three_d_asset::io::*;
@@ -130,6 +201,111 @@ impl RawAssets {
         T::deserialize(path, self)
     }
 
+    ///
+    /// Deserialize the asset with the given path like [RawAssets::deserialize], but using the given [ImportOptions],
+    /// for example to convert a Z up asset into the Y up coordinate system used by glTF:
+    /// ```
+    /// # use three_d_asset::io::*;
+    /// # use three_d_asset::{prelude::*, TriMesh};
+    /// let options = ImportOptions {
+    ///     source_coordinate_system: Some(CoordinateSystem {
+    ///         up_axis: Axis::Z,
+    ///         handedness: Handedness::Right,
+    ///     }),
+    ///     ..Default::default()
+    /// };
+    /// # #[cfg(feature = "stl")]
+    /// let mesh: TriMesh = load(&["test_data/cube.stl"]).unwrap().deserialize_with("cube.stl", &options).unwrap();
+    /// ```
+    ///
+    /// The options are applied to the root of a [Scene](crate::Scene) or [Model](crate::Model) and directly to the geometry of a [TriMesh](crate::TriMesh) or [PointCloud](crate::PointCloud).
+    /// Other assets, like textures, ignore the options.
+    ///
+    pub fn deserialize_with<T: Deserialize>(
+        &mut self,
+        path: impl AsRef<Path>,
+        options: &ImportOptions,
+    ) -> Result<T> {
+        T::deserialize_with(path, self, options)
+    }
+
+    ///
+    /// Deserialize the asset with the given path like [RawAssets::deserialize], but yields to the event loop between the expensive steps,
+    /// for example before decoding each of the images of a glTF file, so that loading a large model does not freeze a web page.
+    ///
+    /// This is primarily a concern on web, since the deserialization otherwise blocks the main thread of the browser.
+    /// On native, the result is the same as [RawAssets::deserialize] and the future never yields.
+    ///
+    /// ```
+    /// # use three_d_asset::io::*;
+    /// # use three_d_asset::Texture2D;
+    /// # async fn example() -> three_d_asset::Result<()> {
+    /// let mut assets = load_async(&["test_data/test.png"]).await?;
+    /// let texture: Texture2D = assets.deserialize_async("test.png").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub async fn deserialize_async<T: Deserialize>(&mut self, path: impl AsRef<Path>) -> Result<T> {
+        T::deserialize_async(path, self).await
+    }
+
+    ///
+    /// Deserialize the asset with the given path like [RawAssets::deserialize], except that the format is detected from the content if the path does not have
+    /// the extension of a supported format, for example if the bytes were inserted with an empty path after receiving them over a network connection.
+    ///
+    /// The format is detected using the following heuristics:
+    /// - PNG, JPEG, GIF, BMP, TIFF, HDR, GLB, LAS and PCD files are detected from the signature in the first bytes of the file.
+    /// - glTF files are detected as JSON objects containing an `"asset"` property and SVG files as XML containing an `<svg` element.
+    /// - Binary STL files are detected when the file size matches the number of triangles specified in the header
+    ///   and ASCII STL files when the text starts with `solid` and contains a `facet`.
+    ///   Since the binary STL header can contain anything, it is checked after all of the signatures.
+    ///   Also, text that happens to start with `solid` and contain `facet` is detected as ASCII STL.
+    /// - ZIP archives, which includes 3MF files, are detected, but not supported, so they result in an error.
+    ///
+    /// The asset is moved to a path with the detected extension before it is deserialized.
+    /// If another asset already has that path, a number is added before the extension so the other asset is left untouched.
+    ///
+    /// ```
+    /// # use three_d_asset::io::*;
+    /// # use three_d_asset::Texture2D;
+    /// # let png_bytes = include_bytes!("../../test_data/test.png").to_vec();
+    /// let mut assets = RawAssets::new();
+    /// assets.insert("", png_bytes);
+    /// let texture: Texture2D = assets.deserialize_autodetect("").unwrap();
+    /// ```
+    ///
+    pub fn deserialize_autodetect<T: Deserialize>(&mut self, path: impl AsRef<Path>) -> Result<T> {
+        let path = self.match_path(path.as_ref())?;
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase());
+        if extension.is_some_and(|e| SUPPORTED_EXTENSIONS.contains(&e.as_str())) {
+            return T::deserialize(path, self);
+        }
+        let extension = detect_extension(self.get(&path)?)
+            .ok_or_else(|| Error::FailedDeserialize(path.to_str().unwrap().to_string()))?;
+        if extension == "zip" {
+            Err(Error::FailedDeserialize(path.to_str().unwrap().to_string()))?;
+        }
+        let base_path = if path.file_name().is_some() {
+            path.clone()
+        } else {
+            PathBuf::from("autodetected")
+        };
+        // Do not replace another asset which already has the detected path
+        let mut detected_path = base_path.with_extension(extension);
+        let mut i = 1;
+        while self.contains_key(&detected_path) {
+            detected_path = base_path.with_extension(format!("{}.{}", i, extension));
+            i += 1;
+        }
+        let bytes = self.remove(&path)?;
+        self.insert(&detected_path, bytes);
+        T::deserialize(detected_path, self)
+    }
+
     ///
     /// Saves all of the raw assets to files.
     ///
@@ -140,6 +316,88 @@ impl RawAssets {
     }
 }
 
+const SUPPORTED_EXTENSIONS: [&str; 17] = [
+    "png", "jpg", "jpeg", "gif", "bmp", "tif", "tiff", "tga", "hdr", "svg", "gltf", "glb", "obj",
+    "stl", "pcd", "las", "vol",
+];
+
+///
+/// Returns the extension of the format of the given file content, see [RawAssets::deserialize_autodetect].
+///
+fn detect_extension(bytes: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: [(&[u8], &str); 12] = [
+        (b"\x89PNG\r\n\x1a\n", "png"),
+        (b"\xff\xd8\xff", "jpg"),
+        (b"GIF8", "gif"),
+        (b"BM", "bmp"),
+        (b"II*\0", "tiff"),
+        (b"MM\0*", "tiff"),
+        (b"#?RADIANCE", "hdr"),
+        (b"#?RGBE", "hdr"),
+        (b"glTF", "glb"),
+        (b"LASF", "las"),
+        (b"PK\x03\x04", "zip"),
+        (b"# .PCD", "pcd"),
+    ];
+    if let Some((_, extension)) = SIGNATURES.iter().find(|(s, _)| bytes.starts_with(s)) {
+        return Some(extension);
+    }
+    if bytes.len() >= 84 {
+        let triangle_count = u32::from_le_bytes(bytes[80..84].try_into().unwrap()) as u64;
+        if bytes.len() as u64 == 84 + 50 * triangle_count {
+            return Some("stl");
+        }
+    }
+    let text = std::str::from_utf8(&bytes[..bytes.len().min(4096)])
+        .or_else(|e| std::str::from_utf8(&bytes[..e.valid_up_to()]))
+        .unwrap()
+        .trim_start();
+    if text.starts_with('{') && text.contains("\"asset\"") {
+        Some("gltf")
+    } else if text.starts_with('<') && text.contains("<svg") {
+        Some("svg")
+    } else if text.starts_with("solid") && text.contains("facet") {
+        Some("stl")
+    } else if text.starts_with("VERSION") {
+        Some("pcd")
+    } else {
+        None
+    }
+}
+
+///
+/// Returns the path as a string with forward slashes and the `.jpeg` extension replaced by `.jpg`, since they are the same format.
+///
+fn normalize(path: &Path) -> String {
+    let p = path.to_str().unwrap().replace('\\', "/");
+    match p.strip_suffix(".jpeg") {
+        Some(stem) => format!("{}.jpg", stem),
+        None => p,
+    }
+}
+
+///
+/// Returns whether the given key matches the given path, which is the case if
+/// - the path is empty, or
+/// - the path is an extension, with or without a leading dot, equal to the extension of the key, or
+/// - the path is equal to the last components of the key, for example `b/c.png` matches `a/b/c.png` but not `a/bb/c.png`.
+///
+fn matches(key: &str, path: &str) -> bool {
+    if path.is_empty() {
+        return true;
+    }
+    let extension = match path.strip_prefix('.').unwrap_or(path) {
+        "jpeg" => "jpg",
+        e => e,
+    };
+    if !extension.contains(['.', '/'])
+        && Path::new(key).extension().and_then(|e| e.to_str()) == Some(extension)
+    {
+        return true;
+    }
+    Path::new(key).ends_with(path)
+}
+
 impl std::ops::Deref for RawAssets {
     type Target = HashMap<PathBuf, Vec<u8>>;
 
@@ -158,3 +416,283 @@ impl std::fmt::Debug for RawAssets {
         d.finish()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    pub fn match_path() {
+        let mut assets = RawAssets::new();
+        assets.insert("data/other_a.png", vec![1]);
+        assets.insert("data/a.png.bak", vec![2]);
+        assets.insert("data/b.jpeg", vec![3]);
+        assets.insert("data\\sub\\c.gltf", vec![4]);
+
+        assert!(assets.get("a.png").is_err());
+        assert_eq!(assets.get("other_a.png").unwrap(), &[1]);
+        assert_eq!(assets.get("data/other_a.png").unwrap(), &[1]);
+        assert_eq!(assets.get(".bak").unwrap(), &[2]);
+        assert_eq!(assets.get("b.jpg").unwrap(), &[3]);
+        assert_eq!(assets.get("jpeg").unwrap(), &[3]);
+        assert_eq!(assets.get("sub/c.gltf").unwrap(), &[4]);
+        assert_eq!(assets.get(".gltf").unwrap(), &[4]);
+        assert!(assets.get("ub/c.gltf").is_err());
+        assert!(assets.get("other").is_err());
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    pub fn deserialize_autodetect_image() {
+        let mut assets = RawAssets::new();
+        assets.insert(
+            "texture",
+            include_bytes!("../../test_data/test.png").to_vec(),
+        );
+        let texture: crate::Texture2D = assets.deserialize_autodetect("texture").unwrap();
+        assert_eq!((texture.width, texture.height), (2, 2));
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    pub fn deserialize_autodetect_keeps_existing_asset() {
+        let mut assets = RawAssets::new();
+        let png_bytes = include_bytes!("../../test_data/test.png").to_vec();
+        assets.insert("texture", png_bytes.clone());
+        assets.insert("texture.png", vec![1, 2, 3]);
+        assets.insert("texture.1.png", vec![4, 5, 6]);
+        let texture: crate::Texture2D = assets.deserialize_autodetect("texture").unwrap();
+        assert_eq!((texture.width, texture.height), (2, 2));
+        assert_eq!(assets.get("texture.png").unwrap(), &[1, 2, 3]);
+        assert_eq!(assets.get("texture.1.png").unwrap(), &[4, 5, 6]);
+        assert!(!assets.contains_key("texture"));
+    }
+
+    #[cfg(feature = "stl")]
+    #[test]
+    pub fn deserialize_autodetect_stl() {
+        let mut assets = RawAssets::new();
+        assets.insert("", include_bytes!("../../test_data/cube.stl").to_vec());
+        let mesh: crate::TriMesh = assets.deserialize_autodetect("").unwrap();
+        assert_eq!(mesh.triangle_count(), 12);
+
+        let mut bytes = b"solid but actually binary".to_vec();
+        bytes.resize(80, 0);
+        bytes.extend(1u32.to_le_bytes());
+        bytes.extend([0; 50]);
+        assert_eq!(detect_extension(&bytes), Some("stl"));
+    }
+
+    #[test]
+    pub fn import_options() {
+        let z_up = CoordinateSystem {
+            up_axis: Axis::Z,
+            handedness: Handedness::Right,
+        };
+        let transformation = ImportOptions {
+            source_coordinate_system: Some(z_up),
+            ..Default::default()
+        }
+        .transformation(None);
+        assert_eq!(
+            (transformation * vec3(1.0, 2.0, 3.0).extend(1.0)).truncate(),
+            vec3(1.0, 3.0, -2.0)
+        );
+        assert_eq!(
+            ImportOptions::default().transformation(None),
+            Mat4::identity()
+        );
+
+        let z_up_left_handed = CoordinateSystem {
+            handedness: Handedness::Left,
+            ..z_up
+        };
+        let transformation = z_up_left_handed.conversion_to(CoordinateSystem::default());
+        assert_eq!(
+            (transformation * vec3(1.0, 2.0, 3.0).extend(1.0)).truncate(),
+            vec3(1.0, 3.0, 2.0)
+        );
+        let x_up = CoordinateSystem {
+            up_axis: Axis::X,
+            handedness: Handedness::Right,
+        };
+        for (a, b) in [
+            (z_up, x_up),
+            (x_up, z_up_left_handed),
+            (z_up_left_handed, z_up),
+        ] {
+            let roundtrip = b.conversion_to(a) * a.conversion_to(b);
+            assert_eq!(roundtrip, Mat4::identity());
+        }
+    }
+
+    #[cfg(feature = "stl")]
+    #[test]
+    pub fn deserialize_with_coordinate_system() {
+        let options = ImportOptions {
+            source_coordinate_system: Some(CoordinateSystem {
+                up_axis: Axis::Z,
+                handedness: Handedness::Right,
+            }),
+            ..Default::default()
+        };
+        let mut assets = RawAssets::new();
+        assets.insert(
+            "cube.stl",
+            include_bytes!("../../test_data/cube.stl").to_vec(),
+        );
+        let scene: crate::Scene = assets.deserialize_with("cube.stl", &options).unwrap();
+        assert_eq!(scene.children.len(), 1);
+        assert_eq!(
+            scene.children[0].transformation,
+            options.transformation(None)
+        );
+
+        let mut assets = RawAssets::new();
+        assets.insert(
+            "cube.stl",
+            include_bytes!("../../test_data/cube.stl").to_vec(),
+        );
+        let mesh: crate::TriMesh = assets.deserialize_with("cube.stl", &options).unwrap();
+        assert_eq!(mesh.triangle_count(), 12);
+    }
+
+    #[cfg(feature = "stl")]
+    #[test]
+    pub fn deserialize_with_left_handed_coordinate_system() {
+        let options = ImportOptions {
+            source_coordinate_system: Some(CoordinateSystem {
+                up_axis: Axis::Z,
+                handedness: Handedness::Left,
+            }),
+            ..Default::default()
+        };
+        for options in [ImportOptions::default(), options] {
+            let mut assets = RawAssets::new();
+            assets.insert(
+                "cube.stl",
+                include_bytes!("../../test_data/cube.stl").to_vec(),
+            );
+            let mesh: crate::TriMesh = assets.deserialize_with("cube.stl", &options).unwrap();
+            let center = mesh.compute_aabb().center();
+            for [a, b, c] in mesh.triangles() {
+                let face_normal = (b - a).cross(c - a);
+                assert!(face_normal.dot((a + b + c) / 3.0 - center) > 0.0);
+            }
+        }
+    }
+
+    #[cfg(feature = "stl")]
+    #[test]
+    pub fn deserialize_with_unit() {
+        let options = ImportOptions {
+            source_unit: Some(LengthUnit::Millimeter),
+            target_unit: Some(LengthUnit::Meter),
+            ..Default::default()
+        };
+        let bytes = include_bytes!("../../test_data/cube.stl");
+        let mut assets = RawAssets::new();
+        assets.insert("cube.stl", bytes.to_vec());
+        let mesh: crate::TriMesh = assets.deserialize("cube.stl").unwrap();
+        let size = mesh.compute_aabb().size();
+
+        assets.insert("cube.stl", bytes.to_vec());
+        let scene: crate::Scene = assets.deserialize_with("cube.stl", &options).unwrap();
+        assert_eq!(scene.unit, Some(LengthUnit::Meter));
+        assert!((scene.compute_aabb().size() - size * 0.001).magnitude() < 0.00001);
+
+        assets.insert("cube.stl", bytes.to_vec());
+        let mesh: crate::TriMesh = assets.deserialize_with("cube.stl", &options).unwrap();
+        assert!((mesh.compute_aabb().size() - size * 0.001).magnitude() < 0.00001);
+
+        assets.insert("cube.stl", bytes.to_vec());
+        let scene: crate::Scene = assets
+            .deserialize_with(
+                "cube.stl",
+                &ImportOptions {
+                    source_unit: None,
+                    ..options
+                },
+            )
+            .unwrap();
+        assert_eq!(scene.unit, None);
+        assert_eq!(scene.children[0].transformation, Mat4::identity());
+    }
+
+    #[cfg(feature = "gltf")]
+    #[test]
+    pub fn deserialize_gltf_with_unit() {
+        let mut assets = crate::io::load(&["test_data/Cube.gltf"]).unwrap();
+        let scene: crate::Scene = assets
+            .deserialize_with(
+                "Cube.gltf",
+                &ImportOptions {
+                    target_unit: Some(LengthUnit::Centimeter),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(scene.unit, Some(LengthUnit::Centimeter));
+        assert_eq!(scene.children.len(), 1);
+        assert_eq!(scene.children[0].transformation, Mat4::from_scale(100.0));
+    }
+
+    #[test]
+    pub fn deserialize_autodetect_unknown() {
+        let mut assets = RawAssets::new();
+        assets.insert("", b"unknown".to_vec());
+        assert!(assets.deserialize_autodetect::<crate::Model>("").is_err());
+        assets.insert("model.3mf", b"PK\x03\x04".to_vec());
+        assert!(assets
+            .deserialize_autodetect::<crate::Model>("model.3mf")
+            .is_err());
+    }
+
+    #[test]
+    pub fn find_by_extension() {
+        let mut assets = RawAssets::new();
+        assets.insert("b.png", vec![]);
+        assets.insert("data/a.PNG", vec![]);
+        assets.insert("c.jpeg", vec![]);
+        assets.insert("png", vec![]);
+        assert_eq!(
+            assets.find_by_extension(".png"),
+            vec![PathBuf::from("b.png"), PathBuf::from("data/a.PNG")]
+        );
+        assert_eq!(
+            assets.find_by_extension("jpg"),
+            vec![PathBuf::from("c.jpeg")]
+        );
+        assert_eq!(assets.paths().count(), 4);
+    }
+
+    #[test]
+    pub fn contains_key() {
+        let mut assets = RawAssets::new();
+        assets.insert("data\\sub\\a.png", vec![1]);
+        assert!(assets.contains_key("data/sub/a.png"));
+        assert!(assets.contains_key("data\\sub\\a.png"));
+        assert!(assets.contains_key(PathBuf::from("data/sub/a.png")));
+        assert!(!assets.contains_key("a.png"));
+        assert!(!assets.contains_key("sub/a.png"));
+        assert!(assets.get("a.png").is_ok());
+    }
+
+    #[test]
+    pub fn match_path_ambiguous() {
+        let mut assets = RawAssets::new();
+        assets.insert("data/a.png", vec![1]);
+        assets.insert("other/a.png", vec![2]);
+        assert!(matches!(
+            assets.get(".png"),
+            Err(Error::AmbiguousPath(_, candidates)) if candidates == "data/a.png, other/a.png"
+        ));
+        assert!(matches!(assets.get("a.png"), Err(Error::AmbiguousPath(..))));
+        assert_eq!(assets.get("other/a.png").unwrap(), &[2]);
+
+        // An exact match is never ambiguous
+        assets.insert("a.png", vec![3]);
+        assert_eq!(assets.get("a.png").unwrap(), &[3]);
+    }
+}
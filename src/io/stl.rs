@@ -1,14 +1,34 @@
 use std::path::PathBuf;
 
-use crate::{Node, Positions, Result, TriMesh};
+use crate::{Error, Node, Positions, Result, TriMesh};
 
 use crate::{io::RawAssets, Scene};
 
 use cgmath::Vector3;
 
 pub fn deserialize_stl(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<Scene> {
-    let stl_bytes = raw_assets.remove(path)?;
-    let mut stl_bytes = std::io::Cursor::new(stl_bytes.to_vec());
+    let mut stl_bytes = raw_assets.remove(path)?;
+    // A binary file consists of an 80 byte header, the number of triangles and then 50 bytes for each triangle.
+    // Some exporters write additional bytes after the triangles, so the file is allowed to be longer than that.
+    let is_binary = stl_bytes.len() >= 84
+        && stl_bytes.len() as u64
+            >= 84 + 50 * u32::from_le_bytes(stl_bytes[80..84].try_into().unwrap()) as u64;
+    if stl_bytes.starts_with(b"solid") {
+        // The header of a binary file can contain anything, including "solid", so look for ascii keywords as well.
+        let is_ascii = contains(&stl_bytes, b"facet") || contains(&stl_bytes, b"endsolid");
+        match (is_binary, is_ascii) {
+            (true, true) => Err(Error::StlAmbiguousFormat(path.to_str().unwrap().to_owned()))?,
+            (false, false) => Err(Error::StlCorruptData(path.to_str().unwrap().to_owned()))?,
+            (true, false) => {
+                // stl_io reads any file starting with "solid" as an ascii file
+                stl_bytes[..80].fill(0);
+            }
+            (false, true) => {}
+        }
+    } else if !is_binary {
+        Err(Error::StlCorruptData(path.to_str().unwrap().to_owned()))?;
+    }
+    let mut stl_bytes = std::io::Cursor::new(stl_bytes);
     let stl = stl_io::read_stl(&mut stl_bytes)?;
 
     let positions = stl
@@ -56,9 +76,15 @@ pub fn deserialize_stl(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<Sce
         name: path.to_str().unwrap_or("default").to_owned(),
         children: vec![node],
         materials: vec![],
+        unit: None,
+        warnings: Vec::new(),
     })
 }
 
+fn contains(bytes: &[u8], keyword: &[u8]) -> bool {
+    bytes.windows(keyword.len()).any(|w| w == keyword)
+}
+
 #[cfg(test)]
 mod test {
     #[test]
@@ -74,4 +100,83 @@ mod test {
         assert_eq!(model.geometries.len(), 1);
         assert_eq!(model.materials.len(), 0);
     }
+
+    fn binary_stl(header: &[u8]) -> Vec<u8> {
+        let mut bytes = header.to_vec();
+        bytes.resize(80, b' ');
+        bytes.extend(1u32.to_le_bytes());
+        for v in [
+            0.0f32, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0,
+        ] {
+            bytes.extend(v.to_le_bytes());
+        }
+        bytes.extend([0, 0]);
+        bytes
+    }
+
+    #[test]
+    pub fn deserialize_stl_binary_starting_with_solid() {
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("solid.stl", binary_stl(b"solid exported by some tool"));
+        let mesh: crate::TriMesh = raw_assets.deserialize("solid.stl").unwrap();
+        assert_eq!(mesh.triangle_count(), 1);
+    }
+
+    #[test]
+    pub fn deserialize_stl_ambiguous() {
+        let mut bytes = b"solid a".to_vec();
+        bytes.resize(80, b' ');
+        bytes.extend(1u32.to_le_bytes());
+        bytes.resize(124, b' ');
+        bytes.extend(b"endsolid a");
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("ambiguous.stl", bytes);
+        assert!(matches!(
+            raw_assets.deserialize::<crate::TriMesh>("ambiguous.stl"),
+            Err(crate::Error::StlAmbiguousFormat(_))
+        ));
+    }
+
+    #[test]
+    pub fn deserialize_stl_corrupt() {
+        let mut bytes = binary_stl(b"");
+        bytes.pop();
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("corrupt.stl", bytes);
+        assert!(matches!(
+            raw_assets.deserialize::<crate::TriMesh>("corrupt.stl"),
+            Err(crate::Error::StlCorruptData(_))
+        ));
+    }
+
+    #[test]
+    pub fn deserialize_stl_ascii_with_non_ascii_name() {
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert(
+            "würfel.stl",
+            "solid Würfel
+facet normal 0 0 1
+outer loop
+vertex 0 0 0
+vertex 1 0 0
+vertex 0 1 0
+endloop
+endfacet
+endsolid Würfel"
+                .as_bytes()
+                .to_vec(),
+        );
+        let mesh: crate::TriMesh = raw_assets.deserialize("würfel.stl").unwrap();
+        assert_eq!(mesh.triangle_count(), 1);
+    }
+
+    #[test]
+    pub fn deserialize_stl_binary_with_trailing_bytes() {
+        let mut bytes = binary_stl(b"solid exported by some tool");
+        bytes.extend([0; 16]);
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("trailing.stl", bytes);
+        let mesh: crate::TriMesh = raw_assets.deserialize("trailing.stl").unwrap();
+        assert_eq!(mesh.triangle_count(), 1);
+    }
 }
@@ -1,55 +1,107 @@
 use crate::{io::RawAssets, volume::*, Error, Result};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+///
+/// The value of the header word at byte offset 12 which marks that the lowest byte contains the data type of the voxels.
+/// The word is the border width in the original format, which is not supported, so this does not collide with existing files.
+///
+const FORMAT_TAG: u32 = u32::from_be_bytes([b'T', b'D', 0, 0]);
 
 ///
 /// Deserialize a loaded .vol file into a [VoxelGrid].
 ///
-/// **Note:** Border is not supported.
+/// A .vol file consists of a header of 28 bytes followed by the voxel data.
+/// The header contains the number of voxels in each of the three dimensions as big endian `u32` values,
+/// then a `u32` value with the data type of the voxels and finally the size of the volume in each dimension as big endian `f32` values.
+/// The data type is given by the lowest byte when the upper two bytes are `TD`, numbering the [TextureData] variants from 1 to 12,
+/// ie. 1 is [TextureData::RU8] and 12 is [TextureData::RgbaF32]. Float values are big endian.
+/// Any bytes after the voxel data are ignored.
+///
+/// Files written by other tools contain a border width instead of the data type, which is not supported.
+/// For those files, the data type is determined from the number of bytes for each voxel:
+/// - 1, 2, 3 or 4 bytes gives [TextureData::RU8], [TextureData::RgU8], [TextureData::RgbU8] or [TextureData::RgbaU8] respectively.
+/// - 8, 12 or 16 bytes gives [TextureData::RgF32], [TextureData::RgbF32] or [TextureData::RgbaF32] respectively.
 ///
 pub fn deserialize_vol(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<VoxelGrid> {
     let name = path.to_str().unwrap().to_string();
     let bytes = raw_assets.remove(path)?;
-    let width = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
-    let height = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
-    let depth = u32::from_be_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
-    let size = Vec3::new(
-        f32::from_be_bytes([bytes[16], bytes[17], bytes[18], bytes[19]]),
-        f32::from_be_bytes([bytes[20], bytes[21], bytes[22], bytes[23]]),
-        f32::from_be_bytes([bytes[24], bytes[25], bytes[26], bytes[27]]),
-    );
+    if bytes.len() < 28 {
+        Err(Error::VolCorruptData(
+            name.clone(),
+            format!(
+                "the header is 28 bytes, but the file is only {} bytes",
+                bytes.len()
+            ),
+        ))?;
+    }
+    let u32_at = |i: usize| u32::from_be_bytes(bytes[i..i + 4].try_into().unwrap());
+    let f32_at = |i: usize| f32::from_be_bytes(bytes[i..i + 4].try_into().unwrap());
+    let width = u32_at(0);
+    let height = u32_at(4);
+    let depth = u32_at(8);
+    let format = u32_at(12);
+    let size = Vec3::new(f32_at(16), f32_at(20), f32_at(24));
     let bytes = &bytes[28..];
-    let data = match bytes.len() as u32 / (width * height * depth) {
-        1 => {
-            let data = bytes.to_vec();
-            TextureData::RU8(flip(data, width as usize, height as usize, depth as usize))
-        }
-        2 => {
-            let mut data = Vec::new();
-            for i in 0..bytes.len() / 2 {
-                data.push([bytes[i * 2], bytes[i * 2 + 1]]);
-            }
-            TextureData::RgU8(flip(data, width as usize, height as usize, depth as usize))
-        }
-        3 => {
-            let mut data = Vec::new();
-            for i in 0..bytes.len() / 3 {
-                data.push([bytes[i * 3], bytes[i * 3 + 1], bytes[i * 3 + 2]]);
-            }
-            TextureData::RgbU8(flip(data, width as usize, height as usize, depth as usize))
+    let (w, h, d) = (width as usize, height as usize, depth as usize);
+    let voxel_count = w
+        .checked_mul(h)
+        .and_then(|c| c.checked_mul(d))
+        .ok_or_else(|| {
+            Error::VolCorruptData(
+                name.clone(),
+                format!(
+                    "the dimensions {}x{}x{} are too large",
+                    width, height, depth
+                ),
+            )
+        })?;
+
+    let format = if format & 0xffff_0000 == FORMAT_TAG {
+        let format = (format & 0xff) as u8;
+        let bytes_per_voxel = bytes_per_voxel(format).ok_or_else(|| {
+            Error::VolCorruptData(name.clone(), format!("unknown data type {}", format))
+        })?;
+        if bytes.len() < voxel_count.saturating_mul(bytes_per_voxel) {
+            Err(Error::VolCorruptData(
+                name.clone(),
+                format!(
+                    "expected {} bytes for each of the {}x{}x{} voxels, but the voxel data is {} bytes",
+                    bytes_per_voxel, width, height, depth, bytes.len()
+                ),
+            ))?;
         }
-        4 => {
-            let mut data = Vec::new();
-            for i in 0..bytes.len() / 4 {
-                data.push([
-                    bytes[i * 4],
-                    bytes[i * 4 + 1],
-                    bytes[i * 4 + 2],
-                    bytes[i * 4 + 3],
-                ]);
-            }
-            TextureData::RgbaU8(flip(data, width as usize, height as usize, depth as usize))
+        format
+    } else {
+        let bytes_per_voxel = bytes.len().checked_div(voxel_count).unwrap_or(0);
+        match bytes_per_voxel {
+            1..=4 => bytes_per_voxel as u8,
+            8 => 10,
+            12 => 11,
+            16 => 12,
+            _ => Err(Error::VolCorruptData(
+                name.clone(),
+                format!(
+                    "expected 1, 2, 3, 4, 8, 12 or 16 bytes for each of the {}x{}x{} voxels, ie. at least {} bytes, but the voxel data is {} bytes",
+                    width, height, depth, voxel_count, bytes.len()
+                ),
+            ))?,
         }
-        _ => Err(Error::VolCorruptData)?,
+    };
+    let bytes = &bytes[..voxel_count * bytes_per_voxel(format).unwrap()];
+
+    let data = match format {
+        1 => TextureData::RU8(flip(bytes.to_vec(), w, h, d)),
+        2 => TextureData::RgU8(flip(read_u8(bytes), w, h, d)),
+        3 => TextureData::RgbU8(flip(read_u8(bytes), w, h, d)),
+        4 => TextureData::RgbaU8(flip(read_u8(bytes), w, h, d)),
+        5 => TextureData::RF16(flip(read_f16::<1>(bytes).concat(), w, h, d)),
+        6 => TextureData::RgF16(flip(read_f16(bytes), w, h, d)),
+        7 => TextureData::RgbF16(flip(read_f16(bytes), w, h, d)),
+        8 => TextureData::RgbaF16(flip(read_f16(bytes), w, h, d)),
+        9 => TextureData::RF32(flip(read_f32::<1>(bytes).concat(), w, h, d)),
+        10 => TextureData::RgF32(flip(read_f32(bytes), w, h, d)),
+        11 => TextureData::RgbF32(flip(read_f32(bytes), w, h, d)),
+        _ => TextureData::RgbaF32(flip(read_f32(bytes), w, h, d)),
     };
     Ok(VoxelGrid {
         voxels: Texture3D {
@@ -64,6 +116,125 @@ pub fn deserialize_vol(raw_assets: &mut RawAssets, path: &PathBuf) -> Result<Vox
     })
 }
 
+///
+/// Serialize the given voxels into a .vol file where the voxels span a cube with the given size.
+/// The data type of the voxels is stored in the header, see [deserialize_vol], so all [TextureData] variants can be serialized.
+///
+pub fn serialize_vol(voxels: &Texture3D, size: Vec3, path: &Path) -> Result<RawAssets> {
+    let (format, data): (u8, Vec<u8>) = match &voxels.data {
+        TextureData::RU8(data) => (1, data.clone()),
+        TextureData::RgU8(data) => (2, data.iter().flatten().copied().collect()),
+        TextureData::RgbU8(data) => (3, data.iter().flatten().copied().collect()),
+        TextureData::RgbaU8(data) => (4, data.iter().flatten().copied().collect()),
+        TextureData::RF16(data) => (5, data.iter().flat_map(|v| v.to_be_bytes()).collect()),
+        TextureData::RgF16(data) => (
+            6,
+            data.iter()
+                .flatten()
+                .flat_map(|v| v.to_be_bytes())
+                .collect(),
+        ),
+        TextureData::RgbF16(data) => (
+            7,
+            data.iter()
+                .flatten()
+                .flat_map(|v| v.to_be_bytes())
+                .collect(),
+        ),
+        TextureData::RgbaF16(data) => (
+            8,
+            data.iter()
+                .flatten()
+                .flat_map(|v| v.to_be_bytes())
+                .collect(),
+        ),
+        TextureData::RF32(data) => (9, data.iter().flat_map(|v| v.to_be_bytes()).collect()),
+        TextureData::RgF32(data) => (
+            10,
+            data.iter()
+                .flatten()
+                .flat_map(|v| v.to_be_bytes())
+                .collect(),
+        ),
+        TextureData::RgbF32(data) => (
+            11,
+            data.iter()
+                .flatten()
+                .flat_map(|v| v.to_be_bytes())
+                .collect(),
+        ),
+        TextureData::RgbaF32(data) => (
+            12,
+            data.iter()
+                .flatten()
+                .flat_map(|v| v.to_be_bytes())
+                .collect(),
+        ),
+    };
+    let channels = voxels.data.bytes_per_texel();
+    let (width, height, depth) = (
+        voxels.width as usize,
+        voxels.height as usize,
+        voxels.depth as usize,
+    );
+    if data.len() != width * height * depth * channels {
+        Err(Error::FailedSerialize(path.to_str().unwrap().to_string()))?;
+    }
+
+    // The axes in the file are permuted compared to the texture, see deserialize_vol
+    let mut bytes = Vec::with_capacity(28 + data.len());
+    for dimension in [height, depth, width] {
+        bytes.extend((dimension as u32).to_be_bytes());
+    }
+    bytes.extend((FORMAT_TAG | format as u32).to_be_bytes());
+    for s in [size.y, size.z, size.x] {
+        bytes.extend(s.to_be_bytes());
+    }
+    for y in 0..height {
+        for z in 0..depth {
+            for x in 0..width {
+                let i = (x + y * width + z * width * height) * channels;
+                bytes.extend_from_slice(&data[i..i + channels]);
+            }
+        }
+    }
+    let mut raw_assets = RawAssets::new();
+    raw_assets.insert(path, bytes);
+    Ok(raw_assets)
+}
+
+fn bytes_per_voxel(format: u8) -> Option<usize> {
+    match format {
+        1..=4 => Some(format as usize),
+        5..=8 => Some(2 * (format as usize - 4)),
+        9..=12 => Some(4 * (format as usize - 8)),
+        _ => None,
+    }
+}
+
+fn read_u8<const N: usize>(bytes: &[u8]) -> Vec<[u8; N]> {
+    bytes
+        .chunks_exact(N)
+        .map(|c| c.try_into().unwrap())
+        .collect()
+}
+
+fn read_f16<const N: usize>(bytes: &[u8]) -> Vec<[f16; N]> {
+    bytes
+        .chunks_exact(2 * N)
+        .map(|c| std::array::from_fn(|i| f16::from_be_bytes([c[i * 2], c[i * 2 + 1]])))
+        .collect()
+}
+
+fn read_f32<const N: usize>(bytes: &[u8]) -> Vec<[f32; N]> {
+    bytes
+        .chunks_exact(4 * N)
+        .map(|c| {
+            std::array::from_fn(|i| f32::from_be_bytes(c[i * 4..i * 4 + 4].try_into().unwrap()))
+        })
+        .collect()
+}
+
 fn flip<T: Default + Clone>(data: Vec<T>, width: usize, height: usize, depth: usize) -> Vec<T> {
     let mut out_data = vec![T::default(); width * height * depth];
     for x in 0..width {
@@ -77,3 +248,145 @@ fn flip<T: Default + Clone>(data: Vec<T>, width: usize, height: usize, depth: us
     }
     out_data
 }
+
+#[cfg(test)]
+mod test {
+    use crate::io::{Deserialize, Serialize};
+    use crate::*;
+
+    #[test]
+    pub fn serialize_vol() {
+        let texture = Texture3D {
+            data: TextureData::RgbaU8(
+                (0..60u8)
+                    .map(|i| [i, i.wrapping_mul(3), 255 - i, i / 2])
+                    .collect(),
+            ),
+            width: 3,
+            height: 4,
+            depth: 5,
+            ..Default::default()
+        };
+        let mut raw_assets = texture.serialize("volume.vol").unwrap();
+        let result = Texture3D::deserialize("volume.vol", &mut raw_assets).unwrap();
+        assert_eq!(
+            (result.width, result.height, result.depth),
+            (texture.width, texture.height, texture.depth)
+        );
+        assert_eq!(result.data, texture.data);
+
+        let voxel_grid = VoxelGrid {
+            voxels: texture,
+            size: vec3(1.0, 2.0, 3.0),
+            ..Default::default()
+        };
+        let mut raw_assets = voxel_grid.serialize("grid.vol").unwrap();
+        let result = VoxelGrid::deserialize("grid.vol", &mut raw_assets).unwrap();
+        assert_eq!(result.size, voxel_grid.size);
+        assert_eq!(result.voxels.data, voxel_grid.voxels.data);
+
+        let half = |v: f32| f16::from_f32(v);
+        for data in [
+            TextureData::RU8((0..8).collect()),
+            TextureData::RgU8((0..8).map(|i| [i, 255 - i]).collect()),
+            TextureData::RgbU8((0..8).map(|i| [i, 255 - i, i * 2]).collect()),
+            TextureData::RF16((0..8).map(|i| half(i as f32)).collect()),
+            TextureData::RgF16((0..8).map(|i| [half(i as f32), half(0.5)]).collect()),
+            TextureData::RgbF16(
+                (0..8)
+                    .map(|i| [half(i as f32), half(0.5), half(-1.0)])
+                    .collect(),
+            ),
+            TextureData::RgbaF16(
+                (0..8)
+                    .map(|i| [half(i as f32), half(0.5), half(-1.0), half(2.0)])
+                    .collect(),
+            ),
+            TextureData::RF32((0..8).map(|i| i as f32).collect()),
+            TextureData::RgF32((0..8).map(|i| [i as f32, 0.5]).collect()),
+            TextureData::RgbF32((0..8).map(|i| [i as f32, 0.5, -1.0]).collect()),
+            TextureData::RgbaF32((0..8).map(|i| [i as f32, 0.5, -1.0, 2.0]).collect()),
+        ] {
+            let texture = Texture3D {
+                data,
+                width: 2,
+                height: 1,
+                depth: 4,
+                ..Default::default()
+            };
+            let mut raw_assets = texture.serialize("volume.vol").unwrap();
+            let result = Texture3D::deserialize("volume.vol", &mut raw_assets).unwrap();
+            assert_eq!(
+                (result.width, result.height, result.depth),
+                (texture.width, texture.height, texture.depth)
+            );
+            assert_eq!(result.data, texture.data);
+        }
+    }
+
+    fn header(dimensions: [u32; 3], format: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for v in dimensions {
+            bytes.extend(v.to_be_bytes());
+        }
+        bytes.extend(format.to_be_bytes());
+        bytes.extend([0; 12]);
+        bytes
+    }
+
+    #[test]
+    pub fn deserialize_vol_with_trailing_bytes() {
+        // Written by another tool with one byte per voxel and a border width of one
+        let mut bytes = header([2, 2, 2], 1);
+        bytes.extend(0..8u8);
+        bytes.extend([0; 5]);
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("legacy.vol", bytes);
+        let result = VoxelGrid::deserialize("legacy.vol", &mut raw_assets).unwrap();
+        assert!(matches!(result.voxels.data, TextureData::RU8(ref d) if d.len() == 8));
+
+        let mut bytes = header([2, 2, 2], u32::from_be_bytes([b'T', b'D', 0, 9]));
+        bytes.extend((0..8).flat_map(|i| (i as f32).to_be_bytes()));
+        bytes.extend([0; 5]);
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("tagged.vol", bytes);
+        let result = VoxelGrid::deserialize("tagged.vol", &mut raw_assets).unwrap();
+        assert!(matches!(result.voxels.data, TextureData::RF32(ref d) if d.len() == 8));
+    }
+
+    #[test]
+    pub fn deserialize_vol_corrupt_header() {
+        for (bytes, message) in [
+            (
+                header([2, 2, 2], u32::from_be_bytes([b'T', b'D', 0, 13])),
+                "unknown data type 13",
+            ),
+            (
+                header([2, 2, 2], u32::from_be_bytes([b'T', b'D', 0, 12])),
+                "expected 16 bytes",
+            ),
+            (header([u32::MAX; 3], 0), "too large"),
+        ] {
+            let mut raw_assets = crate::io::RawAssets::new();
+            raw_assets.insert("corrupt.vol", bytes);
+            let error = VoxelGrid::deserialize("corrupt.vol", &mut raw_assets).unwrap_err();
+            assert!(matches!(error, Error::VolCorruptData(..)));
+            assert!(error.to_string().contains(message));
+        }
+    }
+
+    #[test]
+    pub fn deserialize_vol_wrong_size() {
+        let mut bytes = Vec::new();
+        for v in [2u32, 2, 2, 0] {
+            bytes.extend(v.to_be_bytes());
+        }
+        bytes.extend([0; 12]);
+        bytes.extend([0; 4]);
+        let mut raw_assets = crate::io::RawAssets::new();
+        raw_assets.insert("wrong.vol", bytes);
+        let error = VoxelGrid::deserialize("wrong.vol", &mut raw_assets).unwrap_err();
+        assert!(matches!(error, Error::VolCorruptData(..)));
+        assert!(error.to_string().contains("4 bytes"));
+    }
+}
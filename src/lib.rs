@@ -40,6 +40,9 @@ pub use animation::*;
 /// and along the way calculate a transformation.
 /// For each node containing [Geometry], the [Geometry] should be visualised with the calculated transformation applied.
 ///
+/// When deserializing a file that contains several scenes, the default scene of the file is returned.
+/// Deserialize into a `Vec<Scene>` to get all of the scenes.
+///
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Scene {
@@ -49,6 +52,12 @@ pub struct Scene {
     pub children: Vec<Node>,
     /// A list of materials used in this scene. The materials are referenced by index in the relevant nodes.
     pub materials: Vec<PbrMaterial>,
+    /// The unit of the positions in this scene if it is specified by the file format, for example glTF is always in meters.
+    /// Is `None` for formats without a unit, like OBJ and STL, where the positions might be in any unit.
+    pub unit: Option<LengthUnit>,
+    /// Descriptions of the parts of the file which were skipped during import, for example glTF primitives without positions.
+    /// Is empty if everything was imported.
+    pub warnings: Vec<String>,
 }
 
 impl Default for Scene {
@@ -57,10 +66,236 @@ impl Default for Scene {
             name: "scene".to_owned(),
             children: Vec::new(),
             materials: Vec::new(),
+            unit: None,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+impl Scene {
+    ///
+    /// Returns an iterator over all nodes in the scene in depth-first order together with the accumulated transformation of each node,
+    /// ie. the transformation from the local space of the node to the space of the scene.
+    /// The [Node::animations] are not taken into account.
+    ///
+    pub fn iter_nodes(&self) -> impl Iterator<Item = (&Node, Mat4)> {
+        NodeIter {
+            stack: self
+                .children
+                .iter()
+                .rev()
+                .map(|c| (c, Mat4::identity()))
+                .collect(),
+        }
+    }
+
+    ///
+    /// Returns the number of nodes in the scene, including all descendants of the children of the scene.
+    ///
+    pub fn node_count(&self) -> usize {
+        self.iter_nodes().count()
+    }
+
+    ///
+    /// Returns the total number of vertices in the geometry of all the nodes in the scene, including the points of point clouds.
+    ///
+    pub fn vertex_count(&self) -> usize {
+        self.iter_nodes()
+            .filter_map(|(node, _)| node.geometry.as_ref())
+            .map(|geometry| geometry.vertex_count())
+            .sum()
+    }
+
+    ///
+    /// Returns the total number of triangles in the geometry of all the nodes in the scene.
+    ///
+    pub fn triangle_count(&self) -> usize {
+        self.iter_nodes()
+            .filter_map(|(node, _)| node.geometry.as_ref())
+            .map(|geometry| geometry.triangle_count())
+            .sum()
+    }
+
+    ///
+    /// Computes the [AxisAlignedBoundingBox] containing all the geometry in the scene transformed by the accumulated transformation of the nodes.
+    /// The [Node::animations] are not taken into account.
+    /// Returns [AxisAlignedBoundingBox::EMPTY] if the scene does not contain any geometry.
+    ///
+    pub fn compute_aabb(&self) -> AxisAlignedBoundingBox {
+        self.iter_nodes()
+            .filter_map(|(node, transformation)| {
+                node.geometry
+                    .as_ref()
+                    .map(|geometry| geometry.compute_aabb().transformed(transformation))
+            })
+            .collect()
+    }
+
+    ///
+    /// Moves the content of the other scene into this scene.
+    /// The materials of the other scene are appended to [Scene::materials] and the material indices of its nodes are updated accordingly.
+    /// The [Scene::warnings] of the other scene are appended to the warnings of this scene.
+    /// The children of the other scene are added as children of a new node, named after the other scene, at the root of this scene.
+    /// Since the animations are stored in the nodes they apply to, they are still valid after the merge.
+    ///
+    pub fn merge(&mut self, other: Scene) {
+        fn offset_material_indices(node: &mut Node, offset: usize) {
+            node.material_index = node.material_index.map(|i| i + offset);
+            for child in node.children.iter_mut() {
+                offset_material_indices(child, offset);
+            }
+        }
+        let mut node = Node {
+            name: other.name,
+            children: other.children,
+            ..Default::default()
+        };
+        offset_material_indices(&mut node, self.materials.len());
+        self.materials.extend(other.materials);
+        self.warnings.extend(other.warnings);
+        self.children.push(node);
+    }
+
+    ///
+    /// Returns a copy of this scene where the accumulated transformation of each node is applied to its geometry
+    /// and all of the [Node::transformation]s are the identity.
+    /// This is useful before exporting to formats without a node hierarchy.
+    /// The [Node::animations] are not taken into account.
+    ///
+    pub fn bake_transforms(&self) -> Result<Scene> {
+        fn bake(node: &Node, parent_transformation: Mat4) -> Result<Node> {
+            let transformation = parent_transformation * node.transformation;
+            let geometry = if let Some(geometry) = &node.geometry {
+                let mut geometry = geometry.clone();
+                geometry.transform(transformation)?;
+                Some(geometry)
+            } else {
+                None
+            };
+            Ok(Node {
+                name: node.name.clone(),
+                children: node
+                    .children
+                    .iter()
+                    .map(|c| bake(c, transformation))
+                    .collect::<Result<Vec<_>>>()?,
+                transformation: Mat4::identity(),
+                animations: node.animations.clone(),
+                geometry,
+                material_index: node.material_index,
+            })
+        }
+        Ok(Scene {
+            name: self.name.clone(),
+            children: self
+                .children
+                .iter()
+                .map(|c| bake(c, Mat4::identity()))
+                .collect::<Result<Vec<_>>>()?,
+            materials: self.materials.clone(),
+            unit: self.unit,
+            warnings: self.warnings.clone(),
+        })
+    }
+
+    ///
+    /// Removes materials which are equal to another material, ignoring the names of the materials and their textures, and updates the material indices of the nodes accordingly.
+    /// Returns the number of removed materials.
+    ///
+    pub fn deduplicate_materials(&mut self) -> usize {
+        fn update_material_indices(node: &mut Node, new_indices: &[usize]) {
+            node.material_index = node.material_index.map(|i| new_indices[i]);
+            for child in node.children.iter_mut() {
+                update_material_indices(child, new_indices);
+            }
+        }
+        let count = self.materials.len();
+        let new_indices = deduplicate_materials(&mut self.materials);
+        for child in self.children.iter_mut() {
+            update_material_indices(child, &new_indices);
         }
+        count - self.materials.len()
+    }
+
+    ///
+    /// Returns the unique geometries in the scene and a list of [Instance]s of those geometries, one for each node containing a geometry.
+    /// Geometries are only stored once even if they are used in several nodes, which saves memory compared to converting to a [Model].
+    /// The [Node::animations] are not taken into account.
+    ///
+    pub fn to_instanced(&self) -> (Vec<Geometry>, Vec<Instance>) {
+        let mut geometries: Vec<Geometry> = Vec::new();
+        // The indices of the unique geometries with the same fingerprint, so only those are compared in full
+        let mut candidates = std::collections::HashMap::<u64, Vec<usize>>::new();
+        let mut instances = Vec::new();
+        for (node, transformation) in self.iter_nodes() {
+            if let Some(geometry) = &node.geometry {
+                let candidates = candidates
+                    .entry(geometry_fingerprint(geometry))
+                    .or_default();
+                let index = candidates
+                    .iter()
+                    .copied()
+                    .find(|i| &geometries[*i] == geometry)
+                    .unwrap_or_else(|| {
+                        geometries.push(geometry.clone());
+                        candidates.push(geometries.len() - 1);
+                        geometries.len() - 1
+                    });
+                instances.push(Instance {
+                    geometry_index: index,
+                    transformation,
+                    material_index: node.material_index,
+                });
+            }
+        }
+        (geometries, instances)
+    }
+
+    ///
+    /// Returns the first node with the given name found by a depth-first search or `None` if no such node exist.
+    ///
+    pub fn find_node(&self, name: &str) -> Option<&Node> {
+        self.children.iter().find_map(|c| c.find(name))
+    }
+
+    ///
+    /// Returns the first node with the given name found by a depth-first search or `None` if no such node exist.
+    ///
+    pub fn find_node_mut(&mut self, name: &str) -> Option<&mut Node> {
+        self.children.iter_mut().find_map(|c| c.find_mut(name))
+    }
+}
+
+struct NodeIter<'a> {
+    stack: Vec<(&'a Node, Mat4)>,
+}
+
+impl<'a> Iterator for NodeIter<'a> {
+    type Item = (&'a Node, Mat4);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, parent_transformation) = self.stack.pop()?;
+        let transformation = parent_transformation * node.transformation;
+        self.stack
+            .extend(node.children.iter().rev().map(|c| (c, transformation)));
+        Some((node, transformation))
     }
 }
 
+///
+/// An instance of one of the unique geometries returned by [Scene::to_instanced].
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Instance {
+    /// The index into the unique geometries.
+    pub geometry_index: usize,
+    /// The accumulated transformation of the node containing the geometry.
+    pub transformation: Mat4,
+    /// The [Node::material_index] of the node containing the geometry.
+    pub material_index: Option<usize>,
+}
+
 ///
 /// A node in a [Scene] graph. Each node may contain a set of children nodes, hence the whole [Scene] representaion has a tree structure.
 ///
@@ -97,6 +332,30 @@ impl Default for Node {
     }
 }
 
+impl Node {
+    ///
+    /// Returns this node or the first of its descendants with the given name found by a depth-first search or `None` if no such node exist.
+    ///
+    pub fn find(&self, name: &str) -> Option<&Node> {
+        if self.name == name {
+            Some(self)
+        } else {
+            self.children.iter().find_map(|c| c.find(name))
+        }
+    }
+
+    ///
+    /// Returns this node or the first of its descendants with the given name found by a depth-first search or `None` if no such node exist.
+    ///
+    pub fn find_mut(&mut self, name: &str) -> Option<&mut Node> {
+        if self.name == name {
+            Some(self)
+        } else {
+            self.children.iter_mut().find_map(|c| c.find_mut(name))
+        }
+    }
+}
+
 ///
 /// A [Model] contain the same data as a [Scene], it's just stored in flat arrays instead of in a tree structure.
 /// You can convert from a [Scene] to a [Model], but not the other way, because the tree structure is lost in the conversion.
@@ -145,6 +404,303 @@ impl std::ops::DerefMut for Primitive {
     }
 }
 
+impl Primitive {
+    ///
+    /// Samples the animation with the given name (see [KeyFrameAnimation::name]) with the given number of frames per second
+    /// and returns a copy of the geometry transformed by [Primitive::transformation] and the animation transformation for each frame.
+    /// The frames cover the time from zero to the last key frame of the animation.
+    /// If the primitive does not have an animation with the given name, a single mesh transformed by [Primitive::transformation] is returned
+    /// and if the geometry is not a [TriMesh], no meshes are returned.
+    ///
+    /// **Note:** Only the transformations are applied. Morph targets and skinning are not supported,
+    /// so animations driven by morph weights or joints have no effect on the baked meshes.
+    ///
+    pub fn bake_animation(&self, name: Option<&str>, fps: f32) -> Result<Vec<TriMesh>> {
+        let Geometry::Triangles(mesh) = &self.geometry else {
+            return Ok(Vec::new());
+        };
+        let animation = self.animations.iter().find(|a| a.name.as_deref() == name);
+        let duration = animation
+            .into_iter()
+            .flat_map(|a| a.key_frames.iter())
+            .filter_map(|(_, key_frames)| key_frames.times.last().copied())
+            .fold(0.0f32, f32::max);
+        let frame_count = (duration * fps).floor() as usize + 1;
+        (0..frame_count)
+            .map(|frame| {
+                let animation_transformation = animation
+                    .map(|a| a.transformation(frame as f32 / fps))
+                    .unwrap_or_else(Mat4::identity);
+                let mut mesh = mesh.clone();
+                mesh.transform(self.transformation * animation_transformation)?;
+                Ok(mesh)
+            })
+            .collect()
+    }
+}
+
+impl Model {
+    ///
+    /// Returns the material referenced by [Primitive::material_index] of the given primitive or `None` if the primitive does not have a material.
+    ///
+    pub fn material_of(&self, primitive: &Primitive) -> Option<&PbrMaterial> {
+        primitive.material_index.and_then(|i| self.materials.get(i))
+    }
+
+    ///
+    /// Returns an iterator over all the primitives together with the material referenced by [Primitive::material_index], see [Model::material_of].
+    ///
+    pub fn primitives_with_materials(
+        &self,
+    ) -> impl Iterator<Item = (&Primitive, Option<&PbrMaterial>)> {
+        self.geometries
+            .iter()
+            .map(|primitive| (primitive, self.material_of(primitive)))
+    }
+
+    ///
+    /// Computes the [AxisAlignedBoundingBox] containing the geometry of all the primitives transformed by [Primitive::transformation].
+    /// The [Primitive::animations] are not taken into account.
+    /// Returns [AxisAlignedBoundingBox::EMPTY] if the model does not contain any geometry.
+    ///
+    pub fn compute_aabb(&self) -> AxisAlignedBoundingBox {
+        self.geometries
+            .iter()
+            .map(|primitive| {
+                primitive
+                    .geometry
+                    .compute_aabb()
+                    .transformed(primitive.transformation)
+            })
+            .collect()
+    }
+
+    ///
+    /// Removes materials which are equal to another material, ignoring the names of the materials and their textures, and updates [Primitive::material_index] accordingly.
+    /// Returns the number of removed materials.
+    ///
+    pub fn deduplicate_materials(&mut self) -> usize {
+        let count = self.materials.len();
+        let new_indices = deduplicate_materials(&mut self.materials);
+        for primitive in self.geometries.iter_mut() {
+            primitive.material_index = primitive.material_index.map(|i| new_indices[i]);
+        }
+        count - self.materials.len()
+    }
+
+    ///
+    /// Returns the total number of vertices in all the primitives, including the points of point clouds.
+    ///
+    pub fn vertex_count(&self) -> usize {
+        self.geometries
+            .iter()
+            .map(|primitive| primitive.geometry.vertex_count())
+            .sum()
+    }
+
+    ///
+    /// Returns the total number of triangles in all the primitives.
+    ///
+    pub fn triangle_count(&self) -> usize {
+        self.geometries
+            .iter()
+            .map(|primitive| primitive.geometry.triangle_count())
+            .sum()
+    }
+
+    ///
+    /// Returns statistics about the size of this model, for example to detect unexpectedly large assets before uploading them to the GPU.
+    ///
+    pub fn stats(&self) -> ModelStats {
+        let mut textures: Vec<&Texture2D> = Vec::new();
+        for material in self.materials.iter() {
+            for texture in [
+                &material.albedo_texture,
+                &material.occlusion_metallic_roughness_texture,
+                &material.metallic_roughness_texture,
+                &material.occlusion_texture,
+                &material.normal_texture,
+                &material.emissive_texture,
+                &material.transmission_texture,
+                &material.clearcoat_texture,
+                &material.clearcoat_roughness_texture,
+                &material.clearcoat_normal_texture,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                if !textures.iter().any(|t| t.content_eq(texture)) {
+                    textures.push(texture);
+                }
+            }
+        }
+        ModelStats {
+            vertex_count: self.vertex_count(),
+            triangle_count: self.triangle_count(),
+            material_count: self.materials.len(),
+            texture_count: textures.len(),
+            texture_bytes: textures
+                .iter()
+                .map(|t| t.data.len() * t.bytes_per_pixel())
+                .sum(),
+        }
+    }
+}
+
+///
+/// Statistics about the size of a [Model], see [Model::stats].
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ModelStats {
+    /// The total number of vertices in all the geometries, including the points of point clouds.
+    pub vertex_count: usize,
+    /// The total number of triangles in all the triangle meshes.
+    pub triangle_count: usize,
+    /// The number of materials.
+    pub material_count: usize,
+    /// The number of unique textures used by the materials.
+    pub texture_count: usize,
+    /// The total size in bytes of the pixel data of the unique textures, not including mipmaps.
+    pub texture_bytes: usize,
+}
+
+impl std::fmt::Display for ModelStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} vertices, {} triangles, {} materials, {} textures ({:.2} MB)",
+            self.vertex_count,
+            self.triangle_count,
+            self.material_count,
+            self.texture_count,
+            self.texture_bytes as f64 / (1024.0 * 1024.0)
+        )
+    }
+}
+
+///
+/// Returns a hash of the type, the number of vertices and indices and a few of the positions of the geometry.
+/// Equal geometries have equal fingerprints, while it is cheap to compute even for large geometries.
+///
+fn geometry_fingerprint(geometry: &Geometry) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    let positions = match geometry {
+        Geometry::Triangles(mesh) => {
+            0.hash(&mut hasher);
+            mesh.indices.len().hash(&mut hasher);
+            &mesh.positions
+        }
+        Geometry::Points(point_cloud) => {
+            1.hash(&mut hasher);
+            &point_cloud.positions
+        }
+    };
+    let count = positions.len();
+    count.hash(&mut hasher);
+    for i in [0, count / 2, count.saturating_sub(1)] {
+        // Adding zero turns -0.0 into 0.0, since they are equal
+        let position = match positions {
+            Positions::F32(p) => p.get(i).map(|p| p.map(|v| v as f64 + 0.0)),
+            Positions::F64(p) => p.get(i).map(|p| p.map(|v| v + 0.0)),
+        };
+        if let Some(p) = position {
+            [p.x.to_bits(), p.y.to_bits(), p.z.to_bits()].hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+///
+/// Removes the duplicate materials and returns the new index of each of the original materials.
+///
+fn deduplicate_materials(materials: &mut Vec<PbrMaterial>) -> Vec<usize> {
+    let mut unique: Vec<PbrMaterial> = Vec::new();
+    let mut new_indices = Vec::new();
+    for material in materials.drain(..) {
+        if let Some(i) = unique
+            .iter()
+            .position(|m| material_content_eq(m, &material))
+        {
+            new_indices.push(i);
+        } else {
+            new_indices.push(unique.len());
+            unique.push(material);
+        }
+    }
+    *materials = unique;
+    new_indices
+}
+
+///
+/// Returns whether or not the two materials are equal, ignoring the names of the materials and their textures.
+///
+fn material_content_eq(a: &PbrMaterial, b: &PbrMaterial) -> bool {
+    fn texture_eq(a: &Option<Texture2D>, b: &Option<Texture2D>) -> bool {
+        match (a, b) {
+            (Some(a), Some(b)) => a.content_eq(b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+    let PbrMaterial {
+        name: _,
+        albedo,
+        albedo_texture,
+        metallic,
+        roughness,
+        occlusion_metallic_roughness_texture,
+        metallic_roughness_texture,
+        occlusion_strength,
+        occlusion_texture,
+        normal_scale,
+        normal_texture,
+        emissive,
+        emissive_texture,
+        emissive_strength,
+        alpha_mode,
+        alpha_cutout,
+        double_sided,
+        lighting_model,
+        index_of_refraction,
+        transmission,
+        transmission_texture,
+        clearcoat,
+        clearcoat_texture,
+        clearcoat_roughness,
+        clearcoat_roughness_texture,
+        clearcoat_normal_texture,
+    } = a;
+    *albedo == b.albedo
+        && *metallic == b.metallic
+        && *roughness == b.roughness
+        && *occlusion_strength == b.occlusion_strength
+        && *normal_scale == b.normal_scale
+        && *emissive == b.emissive
+        && *emissive_strength == b.emissive_strength
+        && *alpha_mode == b.alpha_mode
+        && *alpha_cutout == b.alpha_cutout
+        && *double_sided == b.double_sided
+        && *lighting_model == b.lighting_model
+        && *index_of_refraction == b.index_of_refraction
+        && *transmission == b.transmission
+        && *clearcoat == b.clearcoat
+        && *clearcoat_roughness == b.clearcoat_roughness
+        && texture_eq(albedo_texture, &b.albedo_texture)
+        && texture_eq(
+            occlusion_metallic_roughness_texture,
+            &b.occlusion_metallic_roughness_texture,
+        )
+        && texture_eq(metallic_roughness_texture, &b.metallic_roughness_texture)
+        && texture_eq(occlusion_texture, &b.occlusion_texture)
+        && texture_eq(normal_texture, &b.normal_texture)
+        && texture_eq(emissive_texture, &b.emissive_texture)
+        && texture_eq(transmission_texture, &b.transmission_texture)
+        && texture_eq(clearcoat_texture, &b.clearcoat_texture)
+        && texture_eq(clearcoat_roughness_texture, &b.clearcoat_roughness_texture)
+        && texture_eq(clearcoat_normal_texture, &b.clearcoat_normal_texture)
+}
+
 impl std::convert::From<Scene> for Model {
     fn from(scene: Scene) -> Self {
         let mut geometries = Vec::new();
@@ -213,11 +769,21 @@ pub enum Error {
     InvalidNumberOfIndices(usize),
     #[error("the max index {0} must be less than the number of vertices {1}")]
     InvalidIndices(usize, usize),
+    #[error("the {0} at index {1} is not a finite number")]
+    NonFiniteValue(String, usize),
+    #[error("the triangle at index {0} is degenerate, ie. it has zero area")]
+    DegenerateTriangle(usize),
+    #[error("the index {0} is out of range, the size is {1}")]
+    IndexOutOfRange(usize, usize),
     #[error("the transformation matrix cannot be inverted and is therefore invalid")]
     FailedInvertingTransformationMatrix,
+    #[error("the point cloud must have normals to reconstruct a surface")]
+    PointCloudMissingNormals,
+    #[error("the mesh must have both normals and uv coordinates to be able to compute tangents")]
+    MissingNormalsOrUvs,
     #[cfg(feature = "image")]
-    #[error("error while parsing an image file")]
-    Image(#[from] image::ImageError),
+    #[error("error while parsing the image file {0}: {1}")]
+    Image(String, #[source] image::ImageError),
 
     #[cfg(feature = "svg")]
     #[error("error while parsing svg file")]
@@ -238,13 +804,28 @@ pub enum Error {
     #[error("error while parsing a .gltf file")]
     Gltf(#[from] ::gltf::Error),
     #[cfg(feature = "gltf")]
-    #[error("the .gltf file contain corrupt buffer data")]
-    GltfCorruptData,
+    #[error("the .gltf file {0} contain corrupt buffer data")]
+    GltfCorruptData(String),
     #[cfg(feature = "gltf")]
-    #[error("the .gltf file contain missing buffer data")]
-    GltfMissingData,
-    #[error("the .vol file contain wrong data size")]
-    VolCorruptData,
+    #[error("the .gltf file {0} contain missing buffer data")]
+    GltfMissingData(String),
+    #[cfg(feature = "gltf")]
+    #[error("the .gltf file {0} refer to the files {files} which were not loaded or otherwise added to the raw assets", files = .1.join(", "))]
+    GltfMissingFiles(String, Vec<String>),
+    #[cfg(feature = "gltf")]
+    #[error("the .gltf file contain data which require the unsupported extension {0}")]
+    GltfUnsupportedExtension(String),
+    #[cfg(feature = "stl")]
+    #[error("the .stl file {0} is neither a valid ascii nor binary file")]
+    StlCorruptData(String),
+    #[cfg(feature = "stl")]
+    #[error("the .stl file {0} can be read both as an ascii and a binary file")]
+    StlAmbiguousFormat(String),
+    #[error("the .vol file {0} contain corrupt data: {1}")]
+    VolCorruptData(String, String),
+    #[cfg(feature = "las")]
+    #[error("the .las file {0} contain corrupt data")]
+    LasCorruptData(String),
     #[cfg(not(target_arch = "wasm32"))]
     #[error("error while loading the file {0}: {1}")]
     FailedLoading(String, std::io::Error),
@@ -262,6 +843,8 @@ pub enum Error {
     FailedParsingDataUrl(String, String),
     #[error("tried to use {0} which was not loaded or otherwise added to the raw assets")]
     NotLoaded(String),
+    #[error("the path {0} matches more than one of the raw assets: {1}")]
+    AmbiguousPath(String, String),
     #[error("the feature {0} is needed")]
     FeatureMissing(String),
     #[error("failed to deserialize the file {0}")]
@@ -271,3 +854,350 @@ pub enum Error {
     #[error("failed to find {0} in the file {1}")]
     FailedConvertion(String, String),
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn scene() -> Scene {
+        Scene {
+            children: vec![
+                Node {
+                    name: "a".to_owned(),
+                    transformation: Mat4::from_translation(vec3(1.0, 0.0, 0.0)),
+                    children: vec![Node {
+                        name: "b".to_owned(),
+                        transformation: Mat4::from_scale(2.0),
+                        children: vec![Node {
+                            name: "c".to_owned(),
+                            transformation: Mat4::from_translation(vec3(0.0, 1.0, 0.0)),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+                Node {
+                    name: "d".to_owned(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    pub fn bake_animation() {
+        let key_frames = std::sync::Arc::new(KeyFrames {
+            times: vec![0.0, 1.0],
+            translations: Some(vec![vec3(0.0, 0.0, 0.0), vec3(2.0, 0.0, 0.0)]),
+            ..Default::default()
+        });
+        let primitive = Primitive {
+            name: String::new(),
+            transformation: Mat4::from_translation(vec3(0.0, 1.0, 0.0)),
+            animations: vec![KeyFrameAnimation {
+                name: Some("move".to_owned()),
+                key_frames: vec![(Mat4::identity(), key_frames)],
+            }],
+            geometry: Geometry::Triangles(TriMesh::square()),
+            material_index: None,
+        };
+        let meshes = primitive.bake_animation(Some("move"), 4.0).unwrap();
+        assert_eq!(meshes.len(), 5);
+        for (i, mesh) in meshes.iter().enumerate() {
+            let aabb = mesh.compute_aabb();
+            assert_eq!(aabb.center(), vec3(i as f32 * 0.5, 1.0, 0.0));
+        }
+        let meshes = primitive.bake_animation(None, 4.0).unwrap();
+        assert_eq!(meshes.len(), 1);
+        assert_eq!(meshes[0].compute_aabb().center(), vec3(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    pub fn stats() {
+        let mut scene = scene();
+        scene.find_node_mut("c").unwrap().geometry = Some(Geometry::Points(PointCloud::cube()));
+        scene.find_node_mut("d").unwrap().geometry = Some(Geometry::Triangles(TriMesh::cube()));
+        let texture = Texture2D::solid(4, 2, Srgba::RED);
+        scene.materials = vec![
+            PbrMaterial {
+                albedo_texture: Some(texture.clone()),
+                normal_texture: Some(Texture2D::solid(2, 2, Srgba::BLUE)),
+                ..Default::default()
+            },
+            PbrMaterial {
+                emissive_texture: Some(texture.clone()),
+                // The same image loaded under another name
+                transmission_texture: Some(Texture2D {
+                    name: "other".to_owned(),
+                    ..texture
+                }),
+                clearcoat_texture: Some(Texture2D::solid(1, 3, Srgba::GREEN)),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(scene.vertex_count(), 8 + 36);
+        assert_eq!(scene.triangle_count(), 12);
+        assert_eq!(scene.node_count(), 4);
+        let model = Model::from(scene);
+        assert_eq!(model.vertex_count(), 8 + 36);
+        assert_eq!(model.triangle_count(), 12);
+        let stats = model.stats();
+        assert_eq!(
+            stats,
+            ModelStats {
+                vertex_count: 8 + 36,
+                triangle_count: 12,
+                material_count: 2,
+                texture_count: 3,
+                texture_bytes: 4 * 2 * 4 + 2 * 2 * 4 + 1 * 3 * 4,
+            }
+        );
+        assert_eq!(
+            stats.to_string(),
+            "44 vertices, 12 triangles, 2 materials, 3 textures (0.00 MB)"
+        );
+    }
+
+    #[test]
+    pub fn iter_nodes() {
+        let scene = scene();
+        let nodes = scene.iter_nodes().collect::<Vec<_>>();
+        assert_eq!(
+            nodes
+                .iter()
+                .map(|(n, _)| n.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "c", "d"]
+        );
+        assert_eq!(
+            nodes[2].1,
+            Mat4::from_translation(vec3(1.0, 2.0, 0.0)) * Mat4::from_scale(2.0)
+        );
+        assert_eq!(nodes[3].1, Mat4::identity());
+    }
+
+    #[test]
+    pub fn find_node() {
+        let mut scene = scene();
+        assert_eq!(scene.find_node("c").unwrap().name, "c");
+        assert!(scene.find_node("e").is_none());
+        assert_eq!(scene.children[0].find("b").unwrap().children.len(), 1);
+        assert!(scene.children[1].find("b").is_none());
+
+        scene.find_node_mut("c").unwrap().material_index = Some(1);
+        assert_eq!(scene.find_node("c").unwrap().material_index, Some(1));
+    }
+
+    #[test]
+    pub fn compute_aabb() {
+        assert!(Scene::default().compute_aabb().is_empty());
+        let mut scene = scene();
+        assert!(scene.compute_aabb().is_empty());
+        scene.find_node_mut("c").unwrap().geometry = Some(Geometry::Points(PointCloud::cube()));
+        scene.find_node_mut("d").unwrap().geometry = Some(Geometry::Triangles(TriMesh::cube()));
+        let aabb = scene.compute_aabb();
+        assert_eq!(aabb.min(), vec3(-1.0, -1.0, -2.0));
+        assert_eq!(aabb.max(), vec3(3.0, 4.0, 2.0));
+
+        let aabb = Model::from(scene).compute_aabb();
+        assert_eq!(aabb.min(), vec3(-1.0, -1.0, -2.0));
+        assert_eq!(aabb.max(), vec3(3.0, 4.0, 2.0));
+    }
+
+    #[test]
+    pub fn merge() {
+        let mut scene = scene();
+        scene.materials.push(PbrMaterial::default());
+        let mut other = scene.clone();
+        other.name = "other".to_owned();
+        other.find_node_mut("b").unwrap().material_index = Some(0);
+        scene.merge(other);
+        assert_eq!(scene.materials.len(), 2);
+        assert_eq!(scene.children.len(), 3);
+        assert_eq!(scene.children[2].name, "other");
+        assert_eq!(scene.children[2].children.len(), 2);
+        assert_eq!(scene.children[2].find("b").unwrap().material_index, Some(1));
+        assert_eq!(scene.find_node("b").unwrap().material_index, None);
+    }
+
+    #[test]
+    pub fn bake_transforms() {
+        let mut scene = scene();
+        scene.find_node_mut("c").unwrap().geometry = Some(Geometry::Triangles(TriMesh::cube()));
+        let baked = scene.bake_transforms().unwrap();
+        assert!(baked
+            .iter_nodes()
+            .all(|(_, transformation)| transformation == Mat4::identity()));
+        assert_eq!(baked.compute_aabb().min(), scene.compute_aabb().min());
+        assert_eq!(baked.compute_aabb().max(), scene.compute_aabb().max());
+    }
+
+    #[test]
+    pub fn deduplicate_materials() {
+        let mut scene = scene();
+        let red = PbrMaterial {
+            name: "red".to_owned(),
+            albedo: Srgba::RED,
+            ..Default::default()
+        };
+        scene.materials = vec![
+            red.clone(),
+            PbrMaterial::default(),
+            PbrMaterial {
+                name: "also red".to_owned(),
+                ..red
+            },
+        ];
+        scene.find_node_mut("b").unwrap().material_index = Some(2);
+        scene.find_node_mut("c").unwrap().material_index = Some(1);
+        scene.find_node_mut("c").unwrap().geometry = Some(Geometry::Points(PointCloud::cube()));
+        let mut model = Model::from(scene.clone());
+
+        assert_eq!(scene.deduplicate_materials(), 1);
+        assert_eq!(scene.materials.len(), 2);
+        assert_eq!(scene.materials[0].name, "red");
+        assert_eq!(scene.find_node("b").unwrap().material_index, Some(0));
+        assert_eq!(scene.find_node("c").unwrap().material_index, Some(1));
+
+        assert_eq!(model.deduplicate_materials(), 1);
+        assert_eq!(model.materials.len(), 2);
+        assert_eq!(model.geometries[0].material_index, Some(1));
+        assert_eq!(
+            model.material_of(&model.geometries[0]),
+            Some(&PbrMaterial::default())
+        );
+    }
+
+    #[test]
+    pub fn primitives_with_materials() {
+        let mut scene = scene();
+        scene.materials = vec![PbrMaterial {
+            name: "red".to_owned(),
+            albedo: Srgba::RED,
+            ..Default::default()
+        }];
+        for name in ["b", "c", "d"] {
+            scene.find_node_mut(name).unwrap().geometry =
+                Some(Geometry::Points(PointCloud::cube()));
+        }
+        scene.find_node_mut("b").unwrap().material_index = Some(0);
+        scene.find_node_mut("c").unwrap().material_index = Some(1);
+        let model = Model::from(scene);
+
+        let materials = model
+            .primitives_with_materials()
+            .map(|(primitive, material)| {
+                (primitive.name.as_str(), material.map(|m| m.name.as_str()))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(materials.len(), 3);
+        for (name, material) in materials {
+            assert_eq!(material, if name == "b" { Some("red") } else { None });
+        }
+    }
+
+    #[test]
+    pub fn deduplicate_materials_with_textures() {
+        let texture = Texture2D::solid(2, 2, Srgba::RED);
+        let material = PbrMaterial {
+            albedo_texture: Some(Texture2D {
+                name: "a.png".to_owned(),
+                ..texture.clone()
+            }),
+            ..Default::default()
+        };
+        let mut materials = vec![
+            material.clone(),
+            PbrMaterial {
+                albedo_texture: Some(Texture2D {
+                    name: "b.png".to_owned(),
+                    ..texture.clone()
+                }),
+                ..Default::default()
+            },
+            PbrMaterial {
+                albedo_texture: Some(Texture2D::solid(2, 2, Srgba::BLUE)),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(super::deduplicate_materials(&mut materials), vec![0, 0, 1]);
+        assert_eq!(materials.len(), 2);
+        assert_eq!(materials[0], material);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn serde() {
+        let mut scene = scene();
+        scene.materials = vec![PbrMaterial {
+            albedo_texture: Some(Texture2D::checkerboard(4, 4, Srgba::RED, Srgba::BLUE, 2)),
+            ..Default::default()
+        }];
+        let b = scene.find_node_mut("b").unwrap();
+        b.geometry = Some(Geometry::Triangles(TriMesh::sphere(4)));
+        b.material_index = Some(0);
+        b.animations = vec![(
+            Some("move".to_owned()),
+            KeyFrames {
+                times: vec![0.0, 1.0],
+                translations: Some(vec![vec3(0.0, 0.0, 0.0), vec3(2.0, 0.0, 0.0)]),
+                ..Default::default()
+            },
+        )];
+        scene.find_node_mut("d").unwrap().geometry = Some(Geometry::Points(PointCloud::cube()));
+        let model = Model::from(scene);
+
+        let json = serde_json::to_string(&model).unwrap();
+        let deserialized: Model = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.geometries.len(), 2);
+        assert_eq!(deserialized.materials, model.materials);
+        assert_eq!(deserialized.geometries[0].animations.len(), 1);
+        assert_eq!(serde_json::to_string(&deserialized).unwrap(), json);
+    }
+
+    #[test]
+    pub fn to_instanced() {
+        let mut scene = scene();
+        for name in ["a", "c", "d"] {
+            scene.find_node_mut(name).unwrap().geometry =
+                Some(Geometry::Triangles(TriMesh::cube()));
+        }
+        scene.find_node_mut("b").unwrap().geometry = Some(Geometry::Points(PointCloud::cube()));
+        let (geometries, instances) = scene.to_instanced();
+        assert_eq!(geometries.len(), 2);
+        assert_eq!(
+            instances
+                .iter()
+                .map(|i| i.geometry_index)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 0, 0]
+        );
+        assert_eq!(instances[3].transformation, Mat4::identity());
+
+        // The same geometry with different materials
+        scene.find_node_mut("a").unwrap().material_index = Some(1);
+        scene.find_node_mut("c").unwrap().material_index = Some(0);
+        let mut sphere = TriMesh::sphere(4);
+        scene.find_node_mut("b").unwrap().geometry = Some(Geometry::Triangles(sphere.clone()));
+        // Same fingerprint, but different geometry
+        sphere.positions = match sphere.positions {
+            Positions::F32(mut p) => {
+                p[1].x += 1.0;
+                Positions::F32(p)
+            }
+            p => p,
+        };
+        scene.find_node_mut("d").unwrap().geometry = Some(Geometry::Triangles(sphere));
+        let (geometries, instances) = scene.to_instanced();
+        assert_eq!(geometries.len(), 3);
+        assert_eq!(
+            instances
+                .iter()
+                .map(|i| (i.geometry_index, i.material_index))
+                .collect::<Vec<_>>(),
+            vec![(0, Some(1)), (1, None), (0, Some(0)), (2, None)]
+        );
+    }
+}
@@ -39,10 +39,23 @@ pub enum NormalDistributionFunction {
     TrowbridgeReitzGGX,
 }
 
+/// Specifies how the alpha value of a material, ie. the alpha value of the [PbrMaterial::albedo] and [PbrMaterial::albedo_texture], is interpreted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AlphaMode {
+    /// The alpha value is ignored and the material is rendered fully opaque.
+    #[default]
+    Opaque,
+    /// The material is either fully opaque or fully transparent depending on whether the alpha value is above or below the [PbrMaterial::alpha_cutout].
+    Mask,
+    /// The alpha value is used to blend the material with the background.
+    Blend,
+}
+
 ///
 /// A CPU-side version of a material used for physically based rendering (PBR).
 ///
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PbrMaterial {
     /// Name. Used for matching geometry and material.
@@ -55,6 +68,8 @@ pub struct PbrMaterial {
     /// A value in the range `[0..1]` specifying how metallic the material is.
     pub metallic: f32,
     /// A value in the range `[0..1]` specifying how rough the material surface is.
+    /// This is the perceptual roughness as defined by glTF, ie. the square root of the `α` parameter of the microfacet distribution.
+    /// All importers convert the roughness into this convention, for example using [PbrMaterial::roughness_from_specular_exponent] for OBJ files.
     pub roughness: f32,
     /// Texture containing the occlusion, metallic and roughness parameters.
     /// The occlusion values are sampled from the red channel, metallic from the blue channel and the roughness from the green channel.
@@ -79,8 +94,15 @@ pub struct PbrMaterial {
     /// Texture with color of light shining from an object.
     /// The colors are assumed to be in sRGB (`RgbU8`), sRGB with an alpha channel (`RgbaU8`) or HDR color space.
     pub emissive_texture: Option<Texture2D>,
+    /// A scalar multiplier applied to the [Self::emissive] color and the [Self::emissive_texture], allowing emission brighter than the color range permits.
+    pub emissive_strength: f32,
+    /// Specifies how the alpha value is interpreted, see [AlphaMode].
+    pub alpha_mode: AlphaMode,
     /// Alpha cutout value for transparency in deferred rendering pipeline.
+    /// Only meaningful when the [Self::alpha_mode] is [AlphaMode::Mask].
     pub alpha_cutout: Option<f32>,
+    /// Whether or not the back faces of the geometry should be rendered, ie. back-face culling should be disabled.
+    pub double_sided: bool,
     /// The lighting model used when rendering this material
     pub lighting_model: LightingModel,
     /// The index of refraction for this material    
@@ -89,6 +111,19 @@ pub struct PbrMaterial {
     pub transmission: f32,
     /// Texture containing the transmission parameter which are multiplied with the [Self::transmission] to get the final parameter.
     pub transmission_texture: Option<Texture2D>,
+    /// A value in the range `[0..1]` specifying the intensity of a clear coat layer on top of the material, for example lacquer or car paint.
+    /// A value of 0.0 means no clear coat.
+    pub clearcoat: f32,
+    /// Texture containing the clear coat intensity which is multiplied with the [Self::clearcoat] to get the final parameter.
+    /// The clear coat values are sampled from the red channel.
+    pub clearcoat_texture: Option<Texture2D>,
+    /// A value in the range `[0..1]` specifying how rough the clear coat layer is.
+    pub clearcoat_roughness: f32,
+    /// Texture containing the clear coat roughness which is multiplied with the [Self::clearcoat_roughness] to get the final parameter.
+    /// The roughness values are sampled from the green channel.
+    pub clearcoat_roughness_texture: Option<Texture2D>,
+    /// A tangent space normal map for the clear coat layer.
+    pub clearcoat_normal_texture: Option<Texture2D>,
 }
 
 impl Default for PbrMaterial {
@@ -107,11 +142,110 @@ impl Default for PbrMaterial {
             normal_scale: 1.0,
             emissive: Srgba::BLACK,
             emissive_texture: None,
+            emissive_strength: 1.0,
             index_of_refraction: 1.5,
             transmission: 0.0,
             transmission_texture: None,
+            clearcoat: 0.0,
+            clearcoat_texture: None,
+            clearcoat_roughness: 0.0,
+            clearcoat_roughness_texture: None,
+            clearcoat_normal_texture: None,
+            alpha_mode: AlphaMode::Opaque,
             alpha_cutout: None,
+            double_sided: false,
             lighting_model: LightingModel::Blinn,
         }
     }
 }
+
+impl PbrMaterial {
+    ///
+    /// Converts a Blinn-Phong specular exponent, for example the `Ns` value in an OBJ material, into the perceptual roughness used by [PbrMaterial::roughness].
+    /// The exponent `n` is mapped to the Beckmann roughness `α = sqrt(2 / (n + 2))` which is then converted into perceptual roughness `sqrt(α)`.
+    ///
+    pub fn roughness_from_specular_exponent(exponent: f32) -> f32 {
+        (2.0 / (exponent.max(0.0) + 2.0)).sqrt().sqrt()
+    }
+
+    ///
+    /// Returns whether the material is (partly) see-through and therefore should be blended with what is behind it, for example when sorting objects for rendering.
+    /// This is the case if
+    /// - the [PbrMaterial::albedo] alpha value is below 255,
+    /// - the [PbrMaterial::transmission] is above zero,
+    /// - the [PbrMaterial::alpha_cutout] is specified, or
+    /// - the [PbrMaterial::albedo_texture] has an alpha channel with at least one pixel which is not fully opaque.
+    ///
+    /// Note that the [PbrMaterial::alpha_mode] and the [PbrMaterial::transmission_texture] are not taken into account,
+    /// so a material with a non-opaque albedo is considered transparent even if the alpha mode is [AlphaMode::Opaque].
+    ///
+    pub fn is_transparent(&self) -> bool {
+        self.transmission > 0.0
+            || self.albedo.a < 255
+            || self.alpha_cutout.is_some()
+            || self
+                .albedo_texture
+                .as_ref()
+                .is_some_and(|texture| match &texture.data {
+                    TextureData::RgbaU8(data) => data.iter().any(|c| c[3] < 255),
+                    TextureData::RgbaF16(data) => {
+                        data.iter().any(|c| c[3] < crate::prelude::f16::ONE)
+                    }
+                    TextureData::RgbaF32(data) => data.iter().any(|c| c[3] < 1.0),
+                    _ => false,
+                })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn roughness_from_specular_exponent() {
+        assert_eq!(PbrMaterial::roughness_from_specular_exponent(0.0), 1.0);
+        assert_eq!(PbrMaterial::roughness_from_specular_exponent(-1.0), 1.0);
+        let roughness = PbrMaterial::roughness_from_specular_exponent(30.0);
+        assert!((roughness * roughness - 0.25).abs() < 0.0001);
+        assert!(PbrMaterial::roughness_from_specular_exponent(1000.0) < 0.25);
+    }
+
+    #[test]
+    pub fn is_transparent() {
+        let mut material = PbrMaterial {
+            albedo: Srgba::new(255, 255, 255, 100),
+            ..Default::default()
+        };
+        assert!(material.is_transparent());
+        material.alpha_mode = AlphaMode::Blend;
+        assert!(material.is_transparent());
+
+        material.albedo.a = 255;
+        assert!(!material.is_transparent());
+        material.albedo_texture = Some(Texture2D {
+            data: TextureData::RgbaU8(vec![[255, 0, 0, 255], [0, 255, 0, 254]]),
+            width: 2,
+            height: 1,
+            ..Default::default()
+        });
+        assert!(material.is_transparent());
+        material.albedo_texture = Some(Texture2D {
+            data: TextureData::RgbU8(vec![[255, 0, 0], [0, 255, 0]]),
+            width: 2,
+            height: 1,
+            ..Default::default()
+        });
+        assert!(!material.is_transparent());
+
+        material.alpha_mode = AlphaMode::Opaque;
+        assert!(!material.is_transparent());
+        material.alpha_cutout = Some(0.5);
+        assert!(material.is_transparent());
+
+        let material = PbrMaterial {
+            transmission: 0.5,
+            ..Default::default()
+        };
+        assert!(material.is_transparent());
+    }
+}
@@ -269,3 +269,51 @@ impl AxisAlignedBoundingBox {
         }
     }
 }
+
+impl FromIterator<AxisAlignedBoundingBox> for AxisAlignedBoundingBox {
+    ///
+    /// Constructs the smallest bounding box containing all of the given bounding boxes.
+    /// Returns [AxisAlignedBoundingBox::EMPTY] if there are no bounding boxes.
+    ///
+    fn from_iter<T: IntoIterator<Item = AxisAlignedBoundingBox>>(iter: T) -> Self {
+        let mut aabb = Self::EMPTY;
+        for other in iter {
+            aabb.expand_with_aabb(other);
+        }
+        aabb
+    }
+}
+
+impl std::iter::Sum for AxisAlignedBoundingBox {
+    ///
+    /// Same as [AxisAlignedBoundingBox::from_iter], ie. the smallest bounding box containing all of the given bounding boxes.
+    ///
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn collect() {
+        let aabbs = [
+            AxisAlignedBoundingBox::new_with_positions(&[vec3(0.0, 0.0, 0.0), vec3(1.0, 1.0, 1.0)]),
+            AxisAlignedBoundingBox::EMPTY,
+            AxisAlignedBoundingBox::new_with_positions(&[vec3(-1.0, 2.0, 0.5)]),
+        ];
+        let aabb: AxisAlignedBoundingBox = aabbs.into_iter().collect();
+        assert_eq!(aabb.min(), vec3(-1.0, 0.0, 0.0));
+        assert_eq!(aabb.max(), vec3(1.0, 2.0, 1.0));
+
+        let aabb: AxisAlignedBoundingBox = aabbs.into_iter().sum();
+        assert_eq!(aabb.min(), vec3(-1.0, 0.0, 0.0));
+        assert_eq!(aabb.max(), vec3(1.0, 2.0, 1.0));
+
+        assert!(std::iter::empty::<AxisAlignedBoundingBox>()
+            .collect::<AxisAlignedBoundingBox>()
+            .is_empty());
+    }
+}
@@ -50,6 +50,48 @@ impl Srgba {
         )
     }
 
+    ///
+    /// Creates a new sRGBA color from the given color in linear sRGB color space, ie. the inverse of [Srgba::to_linear_srgb].
+    /// The values are clamped to the [0..1] range.
+    ///
+    pub fn from_linear_srgb(color: Vec4) -> Self {
+        let convert = |c: f32| {
+            let c = if c <= 0.0031308 {
+                12.92 * c
+            } else {
+                1.055 * c.powf(1.0 / 2.4) - 0.055
+            };
+            (c.clamp(0.0, 1.0) * 255.0).round() as u8
+        };
+        Self {
+            r: convert(color.x),
+            g: convert(color.y),
+            b: convert(color.z),
+            a: (color.w.clamp(0.0, 1.0) * 255.0).round() as u8,
+        }
+    }
+
+    ///
+    /// Returns the color with the red, green and blue components multiplied by the alpha value, which is needed for correct blending.
+    /// The multiplication is done in linear color space, so the result is the sRGB encoding of the premultiplied linear color.
+    ///
+    pub fn premultiplied(&self) -> Self {
+        let color = self.to_linear_srgb();
+        Self::from_linear_srgb((color.truncate() * color.w).extend(color.w))
+    }
+
+    ///
+    /// Returns the color with the red, green and blue components divided by the alpha value, ie. the inverse of [Srgba::premultiplied].
+    /// A fully transparent color is returned unchanged, since the color is lost when premultiplying with zero.
+    ///
+    pub fn unpremultiplied(&self) -> Self {
+        if self.a == 0 {
+            return *self;
+        }
+        let color = self.to_linear_srgb();
+        Self::from_linear_srgb((color.truncate() / color.w).extend(color.w))
+    }
+
     /// Opaque red
     pub const RED: Self = Self::new_opaque(255, 0, 0);
     /// Opaque green
@@ -186,3 +228,35 @@ impl Default for Srgba {
         Self::WHITE
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn linear_srgb() {
+        for c in 0..=255 {
+            let color = Srgba::new(c, 255 - c, c / 2, c);
+            assert_eq!(Srgba::from_linear_srgb(color.to_linear_srgb()), color);
+        }
+    }
+
+    #[test]
+    pub fn premultiplied() {
+        assert_eq!(Srgba::WHITE.premultiplied(), Srgba::WHITE);
+        assert_eq!(
+            Srgba::new(255, 128, 0, 0).premultiplied(),
+            Srgba::new(0, 0, 0, 0)
+        );
+        // Half of the linear intensity of white is not half of the sRGB value
+        assert_eq!(
+            Srgba::new(255, 255, 255, 128).premultiplied(),
+            Srgba::new(188, 188, 188, 128)
+        );
+
+        let color = Srgba::new(200, 100, 50, 200);
+        assert_eq!(color.premultiplied().unpremultiplied(), color);
+        let transparent = Srgba::new(0, 0, 0, 0);
+        assert_eq!(transparent.unpremultiplied(), transparent);
+    }
+}
@@ -70,6 +70,132 @@ pub const fn radians<T>(v: T) -> Rad<T> {
     cgmath::Rad(v)
 }
 
+///
+/// One of the three coordinate axes.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Axis {
+    /// The x-axis.
+    X,
+    /// The y-axis.
+    Y,
+    /// The z-axis.
+    Z,
+}
+
+///
+/// The handedness of a coordinate system, ie. whether the z-axis is the cross product of the x-axis and y-axis (right-handed) or the opposite (left-handed).
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Handedness {
+    /// A right-handed coordinate system as used by for example glTF, Blender and Maya.
+    #[default]
+    Right,
+    /// A left-handed coordinate system as used by for example Unity and Unreal.
+    Left,
+}
+
+///
+/// A coordinate system defined by which axis is pointing up and the handedness.
+/// The default is Y up and right-handed which is the coordinate system used by glTF.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoordinateSystem {
+    /// The axis pointing up.
+    pub up_axis: Axis,
+    /// The handedness.
+    pub handedness: Handedness,
+}
+
+impl Default for CoordinateSystem {
+    fn default() -> Self {
+        Self {
+            up_axis: Axis::Y,
+            handedness: Handedness::Right,
+        }
+    }
+}
+
+impl CoordinateSystem {
+    ///
+    /// Returns the transformation that converts positions in this coordinate system into the target coordinate system.
+    /// The up axis is rotated onto the up axis of the target and, if the handedness differs, the axis which ends up as the z-axis
+    /// of a Y up coordinate system is mirrored.
+    /// Note that a mirroring transformation reverses the winding order of the triangles.
+    ///
+    pub fn conversion_to(&self, target: CoordinateSystem) -> Mat4 {
+        target.to_y_up_right_handed().transpose() * self.to_y_up_right_handed()
+    }
+
+    fn to_y_up_right_handed(self) -> Mat4 {
+        let rotation = match self.up_axis {
+            Axis::X => Mat4::from_cols(
+                vec4(0.0, 1.0, 0.0, 0.0),
+                vec4(-1.0, 0.0, 0.0, 0.0),
+                vec4(0.0, 0.0, 1.0, 0.0),
+                vec4(0.0, 0.0, 0.0, 1.0),
+            ),
+            Axis::Y => Mat4::identity(),
+            Axis::Z => Mat4::from_cols(
+                vec4(1.0, 0.0, 0.0, 0.0),
+                vec4(0.0, 0.0, -1.0, 0.0),
+                vec4(0.0, 1.0, 0.0, 0.0),
+                vec4(0.0, 0.0, 0.0, 1.0),
+            ),
+        };
+        match self.handedness {
+            Handedness::Right => rotation,
+            Handedness::Left => Mat4::from_nonuniform_scale(1.0, 1.0, -1.0) * rotation,
+        }
+    }
+}
+
+///
+/// A unit of length used to interpret the positions of an asset, see [ImportOptions](crate::io::ImportOptions).
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LengthUnit {
+    /// A micrometer, ie. 0.000001 meters.
+    Micrometer,
+    /// A millimeter, ie. 0.001 meters.
+    Millimeter,
+    /// A centimeter, ie. 0.01 meters.
+    Centimeter,
+    /// A meter.
+    Meter,
+    /// An inch, ie. 0.0254 meters.
+    Inch,
+    /// A foot, ie. 0.3048 meters.
+    Foot,
+}
+
+impl LengthUnit {
+    ///
+    /// Returns the length of this unit in meters.
+    ///
+    pub fn meters(&self) -> f64 {
+        match self {
+            Self::Micrometer => 0.000001,
+            Self::Millimeter => 0.001,
+            Self::Centimeter => 0.01,
+            Self::Meter => 1.0,
+            Self::Inch => 0.0254,
+            Self::Foot => 0.3048,
+        }
+    }
+
+    ///
+    /// Returns the factor that converts a length in this unit into a length in the target unit.
+    ///
+    pub fn conversion_to(&self, target: LengthUnit) -> f32 {
+        (self.meters() / target.meters()) as f32
+    }
+}
+
 ///
 /// Constructs a rotation matrix that rotates from the source direction to the target direction.
 ///
@@ -20,7 +20,6 @@ use crate::Srgba;
 pub enum Interpolation {
     Nearest,
     Linear,
-    CubicSpline,
 }
 
 impl Default for Interpolation {
@@ -66,6 +65,49 @@ pub enum Wrapping {
     ClampToEdge,
 }
 
+impl Wrapping {
+    ///
+    /// Maps the given normalized texture coordinate into the [0..1] range according to this wrapping mode.
+    /// A repeated coordinate is in the range [0..1), so for example both 0.0 and 1.0 maps to 0.0.
+    ///
+    pub fn apply(&self, coord: f32) -> f32 {
+        match self {
+            Wrapping::Repeat => coord - coord.floor(),
+            Wrapping::MirroredRepeat => {
+                let c = coord.rem_euclid(2.0);
+                if c > 1.0 {
+                    2.0 - c
+                } else {
+                    c
+                }
+            }
+            Wrapping::ClampToEdge => coord.clamp(0.0, 1.0),
+        }
+    }
+
+    ///
+    /// Maps the given texel index into the range `0..size` according to this wrapping mode,
+    /// for example the index -1 maps to `size - 1` when repeating and to 0 when mirroring or clamping.
+    /// A size of zero is treated as a size of one.
+    ///
+    pub fn apply_index(&self, index: i32, size: u32) -> u32 {
+        let size = size.max(1) as i64;
+        let index = index as i64;
+        (match self {
+            Wrapping::Repeat => index.rem_euclid(size),
+            Wrapping::ClampToEdge => index.clamp(0, size - 1),
+            Wrapping::MirroredRepeat => {
+                let i = index.rem_euclid(2 * size);
+                if i < size {
+                    i
+                } else {
+                    2 * size - 1 - i
+                }
+            }
+        }) as u32
+    }
+}
+
 ///
 /// The pixel/texel data for a [Texture2D] or [Texture3D].
 ///
@@ -142,6 +184,70 @@ impl std::fmt::Debug for TextureData {
 }
 
 impl TextureData {
+    ///
+    /// Returns the number of channels, ie. `1` for red, `2` for red and green, `3` for red, green and blue and `4` for red, green, blue and alpha.
+    ///
+    pub fn channels(&self) -> u8 {
+        match self {
+            Self::RU8(_) | Self::RF16(_) | Self::RF32(_) => 1,
+            Self::RgU8(_) | Self::RgF16(_) | Self::RgF32(_) => 2,
+            Self::RgbU8(_) | Self::RgbF16(_) | Self::RgbF32(_) => 3,
+            Self::RgbaU8(_) | Self::RgbaF16(_) | Self::RgbaF32(_) => 4,
+        }
+    }
+
+    ///
+    /// Returns the size in bytes of the value in a single channel, ie. `1` for `u8`, `2` for `f16` and `4` for `f32`.
+    ///
+    pub fn element_size(&self) -> usize {
+        match self {
+            Self::RU8(_) | Self::RgU8(_) | Self::RgbU8(_) | Self::RgbaU8(_) => 1,
+            Self::RF16(_) | Self::RgF16(_) | Self::RgbF16(_) | Self::RgbaF16(_) => 2,
+            Self::RF32(_) | Self::RgF32(_) | Self::RgbF32(_) | Self::RgbaF32(_) => 4,
+        }
+    }
+
+    ///
+    /// Returns the number of texels.
+    ///
+    pub fn len(&self) -> usize {
+        match self {
+            Self::RU8(values) => values.len(),
+            Self::RgU8(values) => values.len(),
+            Self::RgbU8(values) => values.len(),
+            Self::RgbaU8(values) => values.len(),
+            Self::RF16(values) => values.len(),
+            Self::RgF16(values) => values.len(),
+            Self::RgbF16(values) => values.len(),
+            Self::RgbaF16(values) => values.len(),
+            Self::RF32(values) => values.len(),
+            Self::RgF32(values) => values.len(),
+            Self::RgbF32(values) => values.len(),
+            Self::RgbaF32(values) => values.len(),
+        }
+    }
+
+    ///
+    /// Returns whether or not there are no texels.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///
+    /// Returns whether or not the values are floating point values, ie. either `f16` or `f32`.
+    ///
+    pub fn is_float(&self) -> bool {
+        self.element_size() > 1
+    }
+
+    ///
+    /// Returns the size in bytes of a single texel, ie. the number of channels times the size of each channel.
+    ///
+    pub fn bytes_per_texel(&self) -> usize {
+        self.channels() as usize * self.element_size()
+    }
+
     ///
     /// Converts the texture data to linear sRGB color space if the data is either
     /// [TextureData::RgbU8] (assuming sRGB color space) or [TextureData::RgbaU8] (assuming sRGB color space with an alpha channel).
@@ -173,3 +279,48 @@ impl TextureData {
         };
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn serde() {
+        use half::f16;
+        let data = [
+            TextureData::RU8(vec![1, 2]),
+            TextureData::RgbaU8(vec![[1, 2, 3, 4]]),
+            TextureData::RF16(vec![f16::from_f32(0.5), f16::from_f32(-2.0)]),
+            TextureData::RgbaF16(vec![[f16::ONE, f16::ZERO, f16::from_f32(0.25), f16::MAX]]),
+            TextureData::RgF32(vec![[0.5, 1.5]]),
+            TextureData::RgbaF32(vec![[0.1, 0.2, 0.3, 0.4]]),
+        ];
+        for data in data {
+            let json = serde_json::to_string(&data).unwrap();
+            assert!(serde_json::from_str::<TextureData>(&json).unwrap() == data);
+        }
+    }
+
+    #[test]
+    pub fn wrapping() {
+        assert_eq!(Wrapping::Repeat.apply(1.25), 0.25);
+        assert_eq!(Wrapping::Repeat.apply(-0.25), 0.75);
+        assert_eq!(Wrapping::Repeat.apply(1.0), 0.0);
+        assert_eq!(Wrapping::MirroredRepeat.apply(1.25), 0.75);
+        assert_eq!(Wrapping::MirroredRepeat.apply(-0.25), 0.25);
+        assert_eq!(Wrapping::MirroredRepeat.apply(2.25), 0.25);
+        assert_eq!(Wrapping::ClampToEdge.apply(1.25), 1.0);
+        assert_eq!(Wrapping::ClampToEdge.apply(-0.25), 0.0);
+        assert_eq!(Wrapping::ClampToEdge.apply(0.5), 0.5);
+
+        assert_eq!(Wrapping::Repeat.apply_index(-1, 4), 3);
+        assert_eq!(Wrapping::Repeat.apply_index(9, 4), 1);
+        assert_eq!(Wrapping::MirroredRepeat.apply_index(-1, 4), 0);
+        assert_eq!(Wrapping::MirroredRepeat.apply_index(5, 4), 2);
+        assert_eq!(Wrapping::MirroredRepeat.apply_index(8, 4), 0);
+        assert_eq!(Wrapping::ClampToEdge.apply_index(-5, 4), 0);
+        assert_eq!(Wrapping::ClampToEdge.apply_index(7, 4), 3);
+        assert_eq!(Wrapping::Repeat.apply_index(3, 0), 0);
+    }
+}
@@ -1,5 +1,76 @@
 #[doc(inline)]
 pub use super::{Interpolation, Mipmap, TextureData, Wrapping};
+use crate::prelude::{vec2, vec4, Mat3, Srgba, Vec2, Vec4};
+
+///
+/// A transformation of the uv coordinates used to sample a texture, for example to tile or offset the texture.
+/// The uv coordinates are first scaled, then rotated and finally translated by the offset.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextureTransform {
+    /// The offset of the uv coordinates.
+    pub offset: Vec2,
+    /// The scale of the uv coordinates.
+    pub scale: Vec2,
+    /// The rotation of the uv coordinates in radians counter-clockwise around the origin.
+    pub rotation: f32,
+}
+
+impl Default for TextureTransform {
+    fn default() -> Self {
+        Self {
+            offset: vec2(0.0, 0.0),
+            scale: vec2(1.0, 1.0),
+            rotation: 0.0,
+        }
+    }
+}
+
+impl TextureTransform {
+    ///
+    /// Returns whether or not this transform leaves the uv coordinates unchanged.
+    ///
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    ///
+    /// Returns the transform as a matrix which can be multiplied with the uv coordinates extended with a third coordinate of one.
+    ///
+    pub fn matrix(&self) -> Mat3 {
+        let (sin, cos) = self.rotation.sin_cos();
+        #[rustfmt::skip]
+        let matrix = Mat3::new(
+            cos * self.scale.x, -sin * self.scale.x, 0.0,
+            sin * self.scale.y, cos * self.scale.y, 0.0,
+            self.offset.x, self.offset.y, 1.0,
+        );
+        matrix
+    }
+}
+
+///
+/// The operator used by [Texture2D::tonemap] to map high dynamic range values into the [0..1] range.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ToneMapOperator {
+    /// The Reinhard operator `c / (1 + c)`.
+    Reinhard,
+    /// The filmic ACES operator using the curve fit by Krzysztof Narkowicz.
+    Aces,
+}
+
+impl ToneMapOperator {
+    fn apply(&self, c: f32) -> f32 {
+        let c = c.max(0.0);
+        match self {
+            Self::Reinhard => c / (1.0 + c),
+            Self::Aces => (c * (2.51 * c + 0.03)) / (c * (2.43 * c + 0.59) + 0.14),
+        }
+    }
+}
 
 ///
 /// A CPU-side version of a 2D texture.
@@ -25,6 +96,8 @@ pub struct Texture2D {
     pub wrap_s: Wrapping,
     /// Determines how the texture is sampled outside the [0..1] t coordinate range (the second value of the uv coordinates).
     pub wrap_t: Wrapping,
+    /// The transformation applied to the uv coordinates before sampling the texture.
+    pub uv_transform: TextureTransform,
 }
 
 impl Default for Texture2D {
@@ -39,6 +112,332 @@ impl Default for Texture2D {
             mipmap: Some(Mipmap::default()),
             wrap_s: Wrapping::Repeat,
             wrap_t: Wrapping::Repeat,
+            uv_transform: TextureTransform::default(),
+        }
+    }
+}
+
+impl Texture2D {
+    ///
+    /// Returns a texture of the given size where all pixels have the given color.
+    ///
+    pub fn solid(width: u32, height: u32, color: Srgba) -> Self {
+        Self {
+            name: "solid".to_owned(),
+            data: TextureData::RgbaU8(vec![color.into(); (width * height) as usize]),
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// Returns whether or not this texture is equal to the other texture, ignoring the [Texture2D::name].
+    /// This is useful for finding identical textures which are loaded from different paths.
+    ///
+    pub fn content_eq(&self, other: &Texture2D) -> bool {
+        let Texture2D {
+            name: _,
+            data,
+            width,
+            height,
+            min_filter,
+            mag_filter,
+            mipmap,
+            wrap_s,
+            wrap_t,
+            uv_transform,
+        } = self;
+        *width == other.width
+            && *height == other.height
+            && *min_filter == other.min_filter
+            && *mag_filter == other.mag_filter
+            && *mipmap == other.mipmap
+            && *wrap_s == other.wrap_s
+            && *wrap_t == other.wrap_t
+            && *uv_transform == other.uv_transform
+            && *data == other.data
+    }
+
+    ///
+    /// Returns a texture of the given size with a checkerboard pattern alternating between the colors `a` and `b`.
+    /// The pattern consists of `cells` squares in both directions.
+    /// A magenta and black checkerboard is a common placeholder for a missing texture.
+    ///
+    pub fn checkerboard(width: u32, height: u32, a: Srgba, b: Srgba, cells: u32) -> Self {
+        let cells = cells.max(1);
+        let mut data = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let is_a = (x * cells / width + y * cells / height) & 1 == 0;
+                data.push(if is_a { a.into() } else { b.into() });
+            }
+        }
+        Self {
+            name: "checkerboard".to_owned(),
+            data: TextureData::RgbaU8(data),
+            width,
+            height,
+            mag_filter: Interpolation::Nearest,
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// Returns the size in bytes of a single pixel, see [TextureData::bytes_per_texel].
+    ///
+    pub fn bytes_per_pixel(&self) -> usize {
+        self.data.bytes_per_texel()
+    }
+
+    ///
+    /// Returns the pixel data as a contiguous array of bytes, for example for uploading the texture to the GPU.
+    /// The bytes of the floating point values are in the native endianness of the platform
+    /// and the channels of a pixel are stored next to each other in the order red, green, blue, alpha.
+    ///
+    pub fn as_bytes(&self) -> std::borrow::Cow<'_, [u8]> {
+        use bytemuck::cast_slice;
+        std::borrow::Cow::Borrowed(match &self.data {
+            TextureData::RU8(data) => data,
+            TextureData::RgU8(data) => cast_slice(data),
+            TextureData::RgbU8(data) => cast_slice(data),
+            TextureData::RgbaU8(data) => cast_slice(data),
+            TextureData::RF16(data) => cast_slice(data),
+            TextureData::RgF16(data) => cast_slice(data),
+            TextureData::RgbF16(data) => cast_slice(data),
+            TextureData::RgbaF16(data) => cast_slice(data),
+            TextureData::RF32(data) => cast_slice(data),
+            TextureData::RgF32(data) => cast_slice(data),
+            TextureData::RgbF32(data) => cast_slice(data),
+            TextureData::RgbaF32(data) => cast_slice(data),
+        })
+    }
+
+    ///
+    /// Returns a low dynamic range version of this texture with [TextureData::RgbaU8] data in sRGB color space,
+    /// for example for previewing a high dynamic range environment map.
+    /// The linear color values are multiplied by the exposure, mapped into the [0..1] range using the given operator and finally gamma corrected.
+    /// Floating point data is assumed to be in linear color space and byte data in sRGB color space.
+    /// A single channel is interpreted as gray scale and the alpha value is copied without tone mapping.
+    ///
+    pub fn tonemap(&self, exposure: f32, operator: ToneMapOperator) -> Texture2D {
+        let apply = |c: f32| operator.apply(c * exposure);
+        let data = self
+            .linear_pixels()
+            .into_iter()
+            .map(|p| Srgba::from_linear_srgb(vec4(apply(p.x), apply(p.y), apply(p.z), p.w)).into())
+            .collect();
+        Texture2D {
+            data: TextureData::RgbaU8(data),
+            ..self.clone()
+        }
+    }
+
+    ///
+    /// Returns a copy of this texture where the red, green and blue values are multiplied by the alpha value, which is needed for correct blending.
+    /// The multiplication is done in linear color space, so byte data is converted from sRGB color space before
+    /// multiplying and back afterwards, see [Srgba::premultiplied], while floating point data is assumed to be linear.
+    /// Data without an alpha channel is copied unchanged.
+    ///
+    pub fn premultiply_alpha(&self) -> Texture2D {
+        let data = match &self.data {
+            TextureData::RgbaU8(data) => TextureData::RgbaU8(
+                data.iter()
+                    .map(|c| Srgba::from(*c).premultiplied().into())
+                    .collect(),
+            ),
+            TextureData::RgbaF16(data) => TextureData::RgbaF16(
+                data.iter()
+                    .map(|c| [c[0] * c[3], c[1] * c[3], c[2] * c[3], c[3]])
+                    .collect(),
+            ),
+            TextureData::RgbaF32(data) => TextureData::RgbaF32(
+                data.iter()
+                    .map(|c| [c[0] * c[3], c[1] * c[3], c[2] * c[3], c[3]])
+                    .collect(),
+            ),
+            data => data.clone(),
+        };
+        Texture2D {
+            data,
+            ..self.clone()
+        }
+    }
+
+    fn linear_pixels(&self) -> Vec<Vec4> {
+        let srgb = |c: [u8; 4]| Srgba::from(c).to_linear_srgb();
+        match &self.data {
+            TextureData::RU8(data) => data.iter().map(|c| srgb([*c, *c, *c, 255])).collect(),
+            TextureData::RgU8(data) => data.iter().map(|c| srgb([c[0], c[1], 0, 255])).collect(),
+            TextureData::RgbU8(data) => {
+                data.iter().map(|c| srgb([c[0], c[1], c[2], 255])).collect()
+            }
+            TextureData::RgbaU8(data) => data.iter().map(|c| srgb(*c)).collect(),
+            TextureData::RF16(data) => data
+                .iter()
+                .map(|c| vec4(c.to_f32(), c.to_f32(), c.to_f32(), 1.0))
+                .collect(),
+            TextureData::RgF16(data) => data
+                .iter()
+                .map(|c| vec4(c[0].to_f32(), c[1].to_f32(), 0.0, 1.0))
+                .collect(),
+            TextureData::RgbF16(data) => data
+                .iter()
+                .map(|c| vec4(c[0].to_f32(), c[1].to_f32(), c[2].to_f32(), 1.0))
+                .collect(),
+            TextureData::RgbaF16(data) => data
+                .iter()
+                .map(|c| vec4(c[0].to_f32(), c[1].to_f32(), c[2].to_f32(), c[3].to_f32()))
+                .collect(),
+            TextureData::RF32(data) => data.iter().map(|c| vec4(*c, *c, *c, 1.0)).collect(),
+            TextureData::RgF32(data) => data.iter().map(|c| vec4(c[0], c[1], 0.0, 1.0)).collect(),
+            TextureData::RgbF32(data) => data.iter().map(|c| vec4(c[0], c[1], c[2], 1.0)).collect(),
+            TextureData::RgbaF32(data) => {
+                data.iter().map(|c| vec4(c[0], c[1], c[2], c[3])).collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn content_eq() {
+        let texture = Texture2D::solid(2, 2, Srgba::RED);
+        let renamed = Texture2D {
+            name: "renamed".to_owned(),
+            ..texture.clone()
+        };
+        assert_ne!(texture, renamed);
+        assert!(texture.content_eq(&renamed));
+        assert!(!texture.content_eq(&Texture2D {
+            wrap_s: Wrapping::ClampToEdge,
+            ..renamed.clone()
+        }));
+        assert!(!texture.content_eq(&Texture2D::solid(2, 2, Srgba::BLUE)));
+    }
+
+    #[test]
+    pub fn checkerboard() {
+        let texture = Texture2D::checkerboard(4, 4, Srgba::RED, Srgba::BLUE, 2);
+        let red = Srgba::RED.into();
+        let blue = Srgba::BLUE.into();
+        assert_eq!(
+            texture.data,
+            TextureData::RgbaU8(vec![
+                red, red, blue, blue, red, red, blue, blue, blue, blue, red, red, blue, blue, red,
+                red
+            ])
+        );
+        assert_eq!(
+            Texture2D::solid(2, 1, Srgba::RED).data,
+            TextureData::RgbaU8(vec![red, red])
+        );
+    }
+
+    #[test]
+    pub fn data_format() {
+        let texture = Texture2D::solid(2, 3, Srgba::RED);
+        assert_eq!(texture.data.channels(), 4);
+        assert_eq!(texture.data.element_size(), 1);
+        assert_eq!(texture.data.len(), 6);
+        assert!(!texture.data.is_float());
+        assert_eq!(texture.bytes_per_pixel(), 4);
+
+        let data = TextureData::RgbF16(vec![[crate::prelude::f16::ZERO; 3]; 5]);
+        assert_eq!(data.channels(), 3);
+        assert_eq!(data.element_size(), 2);
+        assert_eq!(data.len(), 5);
+        assert!(data.is_float());
+        assert_eq!(data.bytes_per_texel(), 6);
+    }
+
+    #[test]
+    pub fn as_bytes() {
+        let texture = Texture2D::solid(2, 1, Srgba::new(1, 2, 3, 4));
+        assert_eq!(&*texture.as_bytes(), &[1, 2, 3, 4, 1, 2, 3, 4]);
+
+        let texture = Texture2D {
+            data: TextureData::RgF32(vec![[1.0, 2.0]]),
+            width: 1,
+            height: 1,
+            ..Default::default()
+        };
+        let bytes = texture.as_bytes();
+        assert_eq!(bytes.len(), texture.bytes_per_pixel());
+        assert_eq!(&bytes[4..8], &2.0f32.to_ne_bytes());
+    }
+
+    #[test]
+    pub fn premultiply_alpha() {
+        let color = Srgba::new(255, 255, 255, 128);
+        let texture = Texture2D::solid(2, 2, color).premultiply_alpha();
+        assert_eq!(
+            texture.data,
+            TextureData::RgbaU8(vec![color.premultiplied().into(); 4])
+        );
+
+        let texture = Texture2D {
+            data: TextureData::RgbaF32(vec![[1.0, 0.5, 0.0, 0.5], [1.0, 1.0, 1.0, 0.0]]),
+            width: 2,
+            height: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            texture.premultiply_alpha().data,
+            TextureData::RgbaF32(vec![[0.5, 0.25, 0.0, 0.5], [0.0, 0.0, 0.0, 0.0]])
+        );
+
+        let texture = Texture2D {
+            data: TextureData::RgbU8(vec![[255, 128, 0]]),
+            ..Default::default()
+        };
+        assert_eq!(texture.premultiply_alpha().data, texture.data);
+    }
+
+    #[test]
+    pub fn tonemap() {
+        let texture = Texture2D {
+            data: TextureData::RgbF32(vec![[0.0, 1.0, 1000.0], [0.5, 4.0, -1.0]]),
+            width: 2,
+            height: 1,
+            ..Default::default()
+        };
+        for operator in [ToneMapOperator::Reinhard, ToneMapOperator::Aces] {
+            let result = texture.tonemap(1.0, operator);
+            assert_eq!(result.width, 2);
+            let TextureData::RgbaU8(data) = &result.data else {
+                panic!("expected RgbaU8 data");
+            };
+            assert_eq!(data[0][0], 0);
+            assert!(data[0][1] > 0 && data[0][1] < 255);
+            assert!(data[0][2] >= 254);
+            assert!(data[1][0] < data[0][1] && data[0][1] < data[1][1]);
+            assert_eq!(data[1][2], 0);
+            assert_eq!(data[0][3], 255);
+
+            let brighter = texture.tonemap(2.0, operator);
+            let TextureData::RgbaU8(brighter) = &brighter.data else {
+                panic!("expected RgbaU8 data");
+            };
+            assert!(brighter[1][0] > data[1][0]);
         }
     }
+
+    #[test]
+    pub fn texture_transform() {
+        use crate::prelude::*;
+        assert!(TextureTransform::default().is_identity());
+        assert_eq!(TextureTransform::default().matrix(), Mat3::identity());
+        let transform = TextureTransform {
+            offset: vec2(0.5, 0.0),
+            scale: vec2(2.0, 3.0),
+            rotation: std::f32::consts::FRAC_PI_2,
+        };
+        assert!(!transform.is_identity());
+        let uv = transform.matrix() * vec3(1.0, 1.0, 1.0);
+        assert!((uv - vec3(3.5, -2.0, 1.0)).magnitude() < 1e-6);
+    }
 }
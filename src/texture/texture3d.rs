@@ -1,5 +1,6 @@
 #[doc(inline)]
 pub use super::{Interpolation, Mipmap, TextureData, Wrapping};
+use crate::{prelude::Axis, Error, Result, Texture2D};
 
 ///
 /// A CPU-side version of a 3D texture.
@@ -48,3 +49,112 @@ impl Default for Texture3D {
         }
     }
 }
+
+impl Texture3D {
+    ///
+    /// Returns the 2D slice of this texture orthogonal to the given axis at the given index, ie. the column, row or layer with that index.
+    /// The data format is the same as for this texture. The width and height of the slice are along the remaining two axes in the order x, y and z,
+    /// for example the width is along the x-axis and the height along the z-axis for a slice orthogonal to the y-axis.
+    /// Returns an error if the index is outside the texture.
+    ///
+    pub fn slice(&self, axis: Axis, index: u32) -> Result<Texture2D> {
+        let (w, h, d) = (
+            self.width as usize,
+            self.height as usize,
+            self.depth as usize,
+        );
+        let (size, width, height, wrap_s, wrap_t) = match axis {
+            Axis::X => (w, h, d, self.wrap_t, self.wrap_r),
+            Axis::Y => (h, w, d, self.wrap_s, self.wrap_r),
+            Axis::Z => (d, w, h, self.wrap_s, self.wrap_t),
+        };
+        if index as usize >= size {
+            Err(Error::IndexOutOfRange(index as usize, size))?;
+        }
+        let i = index as usize;
+        let texel = |a: usize, b: usize| match axis {
+            Axis::X => i + a * w + b * w * h,
+            Axis::Y => a + i * w + b * w * h,
+            Axis::Z => a + b * w + i * w * h,
+        };
+        fn slice<T: Copy>(
+            data: &[T],
+            width: usize,
+            height: usize,
+            texel: impl Fn(usize, usize) -> usize,
+        ) -> Vec<T> {
+            (0..height)
+                .flat_map(|b| (0..width).map(move |a| (a, b)))
+                .map(|(a, b)| data[texel(a, b)])
+                .collect()
+        }
+        let data = match &self.data {
+            TextureData::RU8(data) => TextureData::RU8(slice(data, width, height, texel)),
+            TextureData::RgU8(data) => TextureData::RgU8(slice(data, width, height, texel)),
+            TextureData::RgbU8(data) => TextureData::RgbU8(slice(data, width, height, texel)),
+            TextureData::RgbaU8(data) => TextureData::RgbaU8(slice(data, width, height, texel)),
+            TextureData::RF16(data) => TextureData::RF16(slice(data, width, height, texel)),
+            TextureData::RgF16(data) => TextureData::RgF16(slice(data, width, height, texel)),
+            TextureData::RgbF16(data) => TextureData::RgbF16(slice(data, width, height, texel)),
+            TextureData::RgbaF16(data) => TextureData::RgbaF16(slice(data, width, height, texel)),
+            TextureData::RF32(data) => TextureData::RF32(slice(data, width, height, texel)),
+            TextureData::RgF32(data) => TextureData::RgF32(slice(data, width, height, texel)),
+            TextureData::RgbF32(data) => TextureData::RgbF32(slice(data, width, height, texel)),
+            TextureData::RgbaF32(data) => TextureData::RgbaF32(slice(data, width, height, texel)),
+        };
+        Ok(Texture2D {
+            name: self.name.clone(),
+            data,
+            width: width as u32,
+            height: height as u32,
+            min_filter: self.min_filter,
+            mag_filter: self.mag_filter,
+            mipmap: self.mipmap,
+            wrap_s,
+            wrap_t,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn slice() {
+        // The value of each voxel is x + 10 * y + 100 * z
+        let texture = Texture3D {
+            data: TextureData::RU8(
+                (0..3)
+                    .flat_map(|z| {
+                        (0..2).flat_map(move |y| (0..4).map(move |x| x + 10 * y + 100 * z))
+                    })
+                    .collect(),
+            ),
+            width: 4,
+            height: 2,
+            depth: 3,
+            ..Default::default()
+        };
+        let slice = texture.slice(Axis::X, 1).unwrap();
+        assert_eq!((slice.width, slice.height), (2, 3));
+        assert_eq!(
+            slice.data,
+            TextureData::RU8(vec![1, 11, 101, 111, 201, 211])
+        );
+        let slice = texture.slice(Axis::Y, 1).unwrap();
+        assert_eq!((slice.width, slice.height), (4, 3));
+        assert_eq!(
+            slice.data,
+            TextureData::RU8(vec![10, 11, 12, 13, 110, 111, 112, 113, 210, 211, 212, 213])
+        );
+        let slice = texture.slice(Axis::Z, 2).unwrap();
+        assert_eq!((slice.width, slice.height), (4, 2));
+        assert_eq!(
+            slice.data,
+            TextureData::RU8(vec![200, 201, 202, 203, 210, 211, 212, 213])
+        );
+        assert!(texture.slice(Axis::Z, 3).is_err());
+    }
+}
@@ -30,3 +30,164 @@ impl std::default::Default for VoxelGrid {
         }
     }
 }
+
+impl VoxelGrid {
+    ///
+    /// Returns the 2D slice of the voxels orthogonal to the given axis at the given index, see [Texture3D::slice].
+    /// Returns an error if the index is outside the voxel grid.
+    ///
+    pub fn slice(&self, axis: Axis, index: u32) -> crate::Result<crate::Texture2D> {
+        self.voxels.slice(axis, index)
+    }
+
+    ///
+    /// Samples the voxels at the given position using trilinear interpolation.
+    /// The voxel grid is a cube with dimensions [VoxelGrid::size] centered at origo, so the position `-size / 2` maps to the corner of the first voxel
+    /// and `size / 2` to the opposite corner of the last voxel, while positions outside the cube are handled according to the wrap modes of the [VoxelGrid::voxels].
+    /// The x, y and z coordinates correspond to the width, height and depth of the voxels respectively.
+    ///
+    /// The channels are returned in the order red, green, blue, alpha with missing channels set to zero.
+    /// Values stored as bytes are converted to the range `[0, 1]`.
+    ///
+    pub fn sample(&self, position: Vec3) -> Vec4 {
+        let voxels = &self.voxels;
+        let size = [voxels.width, voxels.height, voxels.depth];
+        let wrapping = [voxels.wrap_s, voxels.wrap_t, voxels.wrap_r];
+        let uvw = [
+            position.x / self.size.x + 0.5,
+            position.y / self.size.y + 0.5,
+            position.z / self.size.z + 0.5,
+        ];
+        // For each axis, the two voxels to interpolate between and the interpolation factor
+        let mut indices = [[0usize; 2]; 3];
+        let mut factors = [0.0f32; 3];
+        for axis in 0..3 {
+            let coordinate = uvw[axis] * size[axis] as f32 - 0.5;
+            let first = coordinate.floor();
+            factors[axis] = coordinate - first;
+            indices[axis] = [
+                wrapping[axis].apply_index(first as i32, size[axis]) as usize,
+                wrapping[axis].apply_index((first as i32).saturating_add(1), size[axis]) as usize,
+            ];
+        }
+        let mut result = Vec4::zero();
+        for corner in 0..8 {
+            let [i, j, k] = [corner & 1, (corner >> 1) & 1, (corner >> 2) & 1];
+            let weight = [i, j, k]
+                .iter()
+                .zip(factors)
+                .map(|(c, f)| if *c == 0 { 1.0 - f } else { f })
+                .product::<f32>();
+            if weight > 0.0 {
+                let index = indices[0][i]
+                    + indices[1][j] * size[0] as usize
+                    + indices[2][k] * size[0] as usize * size[1] as usize;
+                result += voxel(&voxels.data, index) * weight;
+            }
+        }
+        result
+    }
+}
+
+fn voxel(data: &TextureData, index: usize) -> Vec4 {
+    let byte = |v: u8| v as f32 / 255.0;
+    match data {
+        TextureData::RU8(d) => vec4(byte(d[index]), 0.0, 0.0, 0.0),
+        TextureData::RgU8(d) => vec4(byte(d[index][0]), byte(d[index][1]), 0.0, 0.0),
+        TextureData::RgbU8(d) => {
+            let [r, g, b] = d[index];
+            vec4(byte(r), byte(g), byte(b), 0.0)
+        }
+        TextureData::RgbaU8(d) => {
+            let [r, g, b, a] = d[index];
+            vec4(byte(r), byte(g), byte(b), byte(a))
+        }
+        TextureData::RF16(d) => vec4(d[index].to_f32(), 0.0, 0.0, 0.0),
+        TextureData::RgF16(d) => vec4(d[index][0].to_f32(), d[index][1].to_f32(), 0.0, 0.0),
+        TextureData::RgbF16(d) => {
+            let [r, g, b] = d[index];
+            vec4(r.to_f32(), g.to_f32(), b.to_f32(), 0.0)
+        }
+        TextureData::RgbaF16(d) => {
+            let [r, g, b, a] = d[index];
+            vec4(r.to_f32(), g.to_f32(), b.to_f32(), a.to_f32())
+        }
+        TextureData::RF32(d) => vec4(d[index], 0.0, 0.0, 0.0),
+        TextureData::RgF32(d) => vec4(d[index][0], d[index][1], 0.0, 0.0),
+        TextureData::RgbF32(d) => {
+            let [r, g, b] = d[index];
+            vec4(r, g, b, 0.0)
+        }
+        TextureData::RgbaF32(d) => {
+            let [r, g, b, a] = d[index];
+            vec4(r, g, b, a)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn sample() {
+        let mut voxel_grid = VoxelGrid {
+            voxels: Texture3D {
+                data: TextureData::RF32(vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]),
+                width: 2,
+                height: 2,
+                depth: 2,
+                wrap_s: Wrapping::ClampToEdge,
+                wrap_t: Wrapping::ClampToEdge,
+                wrap_r: Wrapping::ClampToEdge,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        // The voxel centers are at -0.5 and 0.5 in all directions
+        assert_eq!(voxel_grid.sample(vec3(-0.5, -0.5, -0.5)).x, 0.0);
+        assert_eq!(voxel_grid.sample(vec3(0.5, 0.5, 0.5)).x, 7.0);
+        assert_eq!(voxel_grid.sample(vec3(0.0, -0.5, -0.5)).x, 0.5);
+        assert_eq!(voxel_grid.sample(vec3(0.0, 0.0, 0.0)).x, 3.5);
+        assert_eq!(voxel_grid.sample(vec3(0.5, 0.5, 1.0)).x, 7.0);
+        assert_eq!(
+            voxel_grid.sample(vec3(-0.5, -0.5, 0.0)),
+            vec4(2.0, 0.0, 0.0, 0.0)
+        );
+
+        voxel_grid.voxels.wrap_s = Wrapping::Repeat;
+        // Halfway between the last voxel and the first voxel repeated
+        assert_eq!(voxel_grid.sample(vec3(1.0, -0.5, -0.5)).x, 0.5);
+    }
+
+    #[test]
+    pub fn sample_far_outside() {
+        for wrapping in [
+            Wrapping::Repeat,
+            Wrapping::MirroredRepeat,
+            Wrapping::ClampToEdge,
+        ] {
+            let voxel_grid = VoxelGrid {
+                voxels: Texture3D {
+                    data: TextureData::RF32(vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]),
+                    width: 2,
+                    height: 2,
+                    depth: 2,
+                    wrap_s: wrapping,
+                    wrap_t: wrapping,
+                    wrap_r: wrapping,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            for position in [vec3(1e10, -0.5, -0.5), vec3(-1e10, 1e10, -1e10)] {
+                let value = voxel_grid.sample(position).x;
+                assert!((0.0..=7.0).contains(&value));
+            }
+            if wrapping == Wrapping::ClampToEdge {
+                assert_eq!(voxel_grid.sample(vec3(1e10, -0.5, -0.5)).x, 1.0);
+                assert_eq!(voxel_grid.sample(vec3(-1e10, 1e10, -1e10)).x, 2.0);
+            }
+        }
+    }
+}